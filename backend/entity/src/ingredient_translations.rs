@@ -17,6 +17,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::ingredient_lexicon_links::Entity")]
+    IngredientLexiconLinks,
     #[sea_orm(
         belongs_to = "super::ingredients::Entity",
         from = "Column::IngredientId",
@@ -35,6 +37,12 @@ pub enum Relation {
     Languages,
 }
 
+impl Related<super::ingredient_lexicon_links::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::IngredientLexiconLinks.def()
+    }
+}
+
 impl Related<super::ingredients::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Ingredients.def()
@@ -32,6 +32,10 @@ pub enum Relation {
     SelfRef,
     #[sea_orm(has_many = "super::ingredients::Entity")]
     Ingredients,
+    #[sea_orm(has_many = "super::pantry_items::Entity")]
+    PantryItems,
+    #[sea_orm(has_many = "super::shopping_list_items::Entity")]
+    ShoppingListItems,
 }
 
 impl Related<super::ingredients::Entity> for Entity {
@@ -40,4 +44,16 @@ impl Related<super::ingredients::Entity> for Entity {
     }
 }
 
+impl Related<super::pantry_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PantryItems.def()
+    }
+}
+
+impl Related<super::shopping_list_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ShoppingListItems.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -12,10 +12,19 @@ pub struct Model {
     pub quantity: Decimal,
     pub position: i32,
     pub unit_id: Uuid,
+    pub canonical_ingredient_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::canonical_ingredients::Entity",
+        from = "Column::CanonicalIngredientId",
+        to = "super::canonical_ingredients::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    CanonicalIngredients,
     #[sea_orm(
         belongs_to = "super::ingredient_groups::Entity",
         from = "Column::IngredientGroupId",
@@ -36,6 +45,12 @@ pub enum Relation {
     IngredientUnits,
 }
 
+impl Related<super::canonical_ingredients::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CanonicalIngredients.def()
+    }
+}
+
 impl Related<super::ingredient_groups::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::IngredientGroups.def()
@@ -15,6 +15,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::canonical_ingredient_translations::Entity")]
+    CanonicalIngredientTranslations,
     #[sea_orm(has_many = "super::ingredient_group_translations::Entity")]
     IngredientGroupTranslations,
     #[sea_orm(has_many = "super::ingredient_translations::Entity")]
@@ -31,6 +33,12 @@ pub enum Relation {
     StepTranslations,
 }
 
+impl Related<super::canonical_ingredient_translations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CanonicalIngredientTranslations.def()
+    }
+}
+
 impl Related<super::ingredient_group_translations::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::IngredientGroupTranslations.def()
@@ -2,26 +2,50 @@
 
 pub mod prelude;
 
+pub mod canonical_ingredient_aliases;
+pub mod canonical_ingredient_foods;
+pub mod canonical_ingredient_translations;
+pub mod canonical_ingredients;
+pub mod collection_recipes;
+pub mod collections;
+pub mod dietary_rules;
 pub mod email_verification_tokens;
 pub mod favorites;
+pub mod foods;
+pub mod household_invitations;
+pub mod household_members;
+pub mod households;
+pub mod ingredient_densities;
 pub mod ingredient_group_translations;
 pub mod ingredient_groups;
+pub mod ingredient_lexicon_links;
 pub mod ingredient_translations;
 pub mod ingredient_units;
 pub mod ingredients;
 pub mod languages;
+pub mod meal_plan_feeds;
+pub mod meal_plans;
 pub mod notification_templates;
 pub mod notifications;
+pub mod pantry_items;
 pub mod password_reset_tokens;
 pub mod recipe_analytics;
+pub mod recipe_collaborators;
 pub mod recipe_comments;
+pub mod recipe_dietary_labels;
+pub mod recipe_prints;
 pub mod recipe_ratings;
+pub mod recipe_share_links;
 pub mod recipe_tags;
 pub mod recipe_translations;
 pub mod recipe_versions;
 pub mod recipes;
 pub mod roles;
 pub mod sessions;
+pub mod shopping_list_items;
+pub mod shopping_list_members;
+pub mod shopping_list_recipes;
+pub mod shopping_lists;
 pub mod step_group_translations;
 pub mod step_groups;
 pub mod step_translations;
@@ -1,25 +1,49 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0
 
+pub use super::canonical_ingredient_aliases::Entity as CanonicalIngredientAliases;
+pub use super::canonical_ingredient_foods::Entity as CanonicalIngredientFoods;
+pub use super::canonical_ingredient_translations::Entity as CanonicalIngredientTranslations;
+pub use super::canonical_ingredients::Entity as CanonicalIngredients;
+pub use super::collection_recipes::Entity as CollectionRecipes;
+pub use super::collections::Entity as Collections;
+pub use super::dietary_rules::Entity as DietaryRules;
 pub use super::email_verification_tokens::Entity as EmailVerificationTokens;
 pub use super::favorites::Entity as Favorites;
+pub use super::foods::Entity as Foods;
+pub use super::household_invitations::Entity as HouseholdInvitations;
+pub use super::household_members::Entity as HouseholdMembers;
+pub use super::households::Entity as Households;
+pub use super::ingredient_densities::Entity as IngredientDensities;
 pub use super::ingredient_group_translations::Entity as IngredientGroupTranslations;
 pub use super::ingredient_groups::Entity as IngredientGroups;
+pub use super::ingredient_lexicon_links::Entity as IngredientLexiconLinks;
 pub use super::ingredient_translations::Entity as IngredientTranslations;
 pub use super::ingredient_units::Entity as IngredientUnits;
 pub use super::ingredients::Entity as Ingredients;
 pub use super::languages::Entity as Languages;
+pub use super::meal_plan_feeds::Entity as MealPlanFeeds;
+pub use super::meal_plans::Entity as MealPlans;
 pub use super::notification_templates::Entity as NotificationTemplates;
 pub use super::notifications::Entity as Notifications;
+pub use super::pantry_items::Entity as PantryItems;
 pub use super::password_reset_tokens::Entity as PasswordResetTokens;
 pub use super::recipe_analytics::Entity as RecipeAnalytics;
+pub use super::recipe_collaborators::Entity as RecipeCollaborators;
 pub use super::recipe_comments::Entity as RecipeComments;
+pub use super::recipe_dietary_labels::Entity as RecipeDietaryLabels;
+pub use super::recipe_prints::Entity as RecipePrints;
 pub use super::recipe_ratings::Entity as RecipeRatings;
+pub use super::recipe_share_links::Entity as RecipeShareLinks;
 pub use super::recipe_tags::Entity as RecipeTags;
 pub use super::recipe_translations::Entity as RecipeTranslations;
 pub use super::recipe_versions::Entity as RecipeVersions;
 pub use super::recipes::Entity as Recipes;
 pub use super::roles::Entity as Roles;
 pub use super::sessions::Entity as Sessions;
+pub use super::shopping_list_items::Entity as ShoppingListItems;
+pub use super::shopping_list_members::Entity as ShoppingListMembers;
+pub use super::shopping_list_recipes::Entity as ShoppingListRecipes;
+pub use super::shopping_lists::Entity as ShoppingLists;
 pub use super::step_group_translations::Entity as StepGroupTranslations;
 pub use super::step_groups::Entity as StepGroups;
 pub use super::step_translations::Entity as StepTranslations;
@@ -12,6 +12,7 @@ pub struct Model {
     pub data: Json,
     pub edited_by: Option<Uuid>,
     pub created_at: DateTimeWithTimeZone,
+    pub edited_as: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
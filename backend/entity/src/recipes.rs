@@ -18,12 +18,24 @@ pub struct Model {
     pub updated_at: Option<DateTimeWithTimeZone>,
     #[sea_orm(column_type = "Text")]
     pub original_language_code: String,
+    pub forked_from: Option<Uuid>,
+    pub household_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::collection_recipes::Entity")]
+    CollectionRecipes,
     #[sea_orm(has_many = "super::favorites::Entity")]
     Favorites,
+    #[sea_orm(
+        belongs_to = "super::households::Entity",
+        from = "Column::HouseholdId",
+        to = "super::households::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Households,
     #[sea_orm(has_many = "super::ingredient_groups::Entity")]
     IngredientGroups,
     #[sea_orm(
@@ -34,18 +46,38 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     Languages,
+    #[sea_orm(has_many = "super::meal_plans::Entity")]
+    MealPlans,
     #[sea_orm(has_many = "super::recipe_analytics::Entity")]
     RecipeAnalytics,
+    #[sea_orm(has_many = "super::recipe_collaborators::Entity")]
+    RecipeCollaborators,
+    #[sea_orm(has_many = "super::recipe_prints::Entity")]
+    RecipePrints,
     #[sea_orm(has_many = "super::recipe_comments::Entity")]
     RecipeComments,
+    #[sea_orm(has_many = "super::recipe_dietary_labels::Entity")]
+    RecipeDietaryLabels,
     #[sea_orm(has_many = "super::recipe_ratings::Entity")]
     RecipeRatings,
+    #[sea_orm(has_many = "super::recipe_share_links::Entity")]
+    RecipeShareLinks,
     #[sea_orm(has_many = "super::recipe_tags::Entity")]
     RecipeTags,
     #[sea_orm(has_many = "super::recipe_translations::Entity")]
     RecipeTranslations,
     #[sea_orm(has_many = "super::recipe_versions::Entity")]
     RecipeVersions,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ForkedFrom",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    SelfRef,
+    #[sea_orm(has_many = "super::shopping_list_recipes::Entity")]
+    ShoppingListRecipes,
     #[sea_orm(has_many = "super::step_groups::Entity")]
     StepGroups,
     #[sea_orm(
@@ -58,12 +90,24 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::collection_recipes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CollectionRecipes.def()
+    }
+}
+
 impl Related<super::favorites::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Favorites.def()
     }
 }
 
+impl Related<super::households::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Households.def()
+    }
+}
+
 impl Related<super::ingredient_groups::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::IngredientGroups.def()
@@ -76,24 +120,54 @@ impl Related<super::languages::Entity> for Entity {
     }
 }
 
+impl Related<super::meal_plans::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MealPlans.def()
+    }
+}
+
 impl Related<super::recipe_analytics::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::RecipeAnalytics.def()
     }
 }
 
+impl Related<super::recipe_collaborators::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecipeCollaborators.def()
+    }
+}
+
+impl Related<super::recipe_prints::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecipePrints.def()
+    }
+}
+
 impl Related<super::recipe_comments::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::RecipeComments.def()
     }
 }
 
+impl Related<super::recipe_dietary_labels::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecipeDietaryLabels.def()
+    }
+}
+
 impl Related<super::recipe_ratings::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::RecipeRatings.def()
     }
 }
 
+impl Related<super::recipe_share_links::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecipeShareLinks.def()
+    }
+}
+
 impl Related<super::recipe_tags::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::RecipeTags.def()
@@ -112,6 +186,12 @@ impl Related<super::recipe_versions::Entity> for Entity {
     }
 }
 
+impl Related<super::shopping_list_recipes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ShoppingListRecipes.def()
+    }
+}
+
 impl Related<super::step_groups::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::StepGroups.def()
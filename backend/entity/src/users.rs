@@ -31,30 +31,60 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::collections::Entity")]
+    Collections,
     #[sea_orm(has_many = "super::email_verification_tokens::Entity")]
     EmailVerificationTokens,
     #[sea_orm(has_many = "super::favorites::Entity")]
     Favorites,
+    #[sea_orm(has_many = "super::household_invitations::Entity")]
+    HouseholdInvitations,
+    #[sea_orm(has_many = "super::household_members::Entity")]
+    HouseholdMembers,
+    #[sea_orm(has_many = "super::households::Entity")]
+    Households,
+    #[sea_orm(has_many = "super::meal_plan_feeds::Entity")]
+    MealPlanFeeds,
+    #[sea_orm(has_many = "super::meal_plans::Entity")]
+    MealPlans,
     #[sea_orm(has_many = "super::notifications::Entity")]
     Notifications,
+    #[sea_orm(has_many = "super::pantry_items::Entity")]
+    PantryItems,
     #[sea_orm(has_many = "super::password_reset_tokens::Entity")]
     PasswordResetTokens,
     #[sea_orm(has_many = "super::recipe_analytics::Entity")]
     RecipeAnalytics,
+    #[sea_orm(has_many = "super::recipe_collaborators::Entity")]
+    RecipeCollaborators,
+    #[sea_orm(has_many = "super::recipe_prints::Entity")]
+    RecipePrints,
     #[sea_orm(has_many = "super::recipe_comments::Entity")]
     RecipeComments,
     #[sea_orm(has_many = "super::recipe_ratings::Entity")]
     RecipeRatings,
+    #[sea_orm(has_many = "super::recipe_share_links::Entity")]
+    RecipeShareLinks,
     #[sea_orm(has_many = "super::recipe_versions::Entity")]
     RecipeVersions,
     #[sea_orm(has_many = "super::recipes::Entity")]
     Recipes,
     #[sea_orm(has_many = "super::sessions::Entity")]
     Sessions,
+    #[sea_orm(has_many = "super::shopping_list_members::Entity")]
+    ShoppingListMembers,
+    #[sea_orm(has_many = "super::shopping_lists::Entity")]
+    ShoppingLists,
     #[sea_orm(has_many = "super::user_roles::Entity")]
     UserRoles,
 }
 
+impl Related<super::collections::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Collections.def()
+    }
+}
+
 impl Related<super::email_verification_tokens::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::EmailVerificationTokens.def()
@@ -67,12 +97,48 @@ impl Related<super::favorites::Entity> for Entity {
     }
 }
 
+impl Related<super::household_invitations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::HouseholdInvitations.def()
+    }
+}
+
+impl Related<super::household_members::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::HouseholdMembers.def()
+    }
+}
+
+impl Related<super::households::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Households.def()
+    }
+}
+
+impl Related<super::meal_plan_feeds::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MealPlanFeeds.def()
+    }
+}
+
+impl Related<super::meal_plans::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MealPlans.def()
+    }
+}
+
 impl Related<super::notifications::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Notifications.def()
     }
 }
 
+impl Related<super::pantry_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PantryItems.def()
+    }
+}
+
 impl Related<super::password_reset_tokens::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PasswordResetTokens.def()
@@ -85,6 +151,18 @@ impl Related<super::recipe_analytics::Entity> for Entity {
     }
 }
 
+impl Related<super::recipe_collaborators::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecipeCollaborators.def()
+    }
+}
+
+impl Related<super::recipe_prints::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecipePrints.def()
+    }
+}
+
 impl Related<super::recipe_comments::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::RecipeComments.def()
@@ -97,6 +175,12 @@ impl Related<super::recipe_ratings::Entity> for Entity {
     }
 }
 
+impl Related<super::recipe_share_links::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecipeShareLinks.def()
+    }
+}
+
 impl Related<super::recipe_versions::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::RecipeVersions.def()
@@ -115,6 +199,18 @@ impl Related<super::sessions::Entity> for Entity {
     }
 }
 
+impl Related<super::shopping_list_members::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ShoppingListMembers.def()
+    }
+}
+
+impl Related<super::shopping_lists::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ShoppingLists.def()
+    }
+}
+
 impl Related<super::user_roles::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::UserRoles.def()
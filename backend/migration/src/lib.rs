@@ -35,6 +35,24 @@ mod m20260224_023906_add_more_unit;
 mod m20260414_022539_create_notification_table;
 mod m20260414_030931_add_default_notification_templates;
 mod m20260415_003748_add_comment_reply_notification_template;
+mod m20261018_120000_link_ingredient_units_to_base_units;
+mod m20261018_130000_add_us_customary_units;
+mod m20261018_140000_create_ingredient_densities_table;
+mod m20261018_150000_create_recipe_prints_table;
+mod m20261018_160000_add_full_text_search;
+mod m20261018_170000_create_ingredient_lexicon_links_table;
+mod m20261018_180000_create_canonical_ingredients_tables;
+mod m20261018_190000_create_foods_tables;
+mod m20261018_200000_create_dietary_labels_tables;
+mod m20261018_210000_create_shopping_lists_tables;
+mod m20261018_220000_create_meal_plans_tables;
+mod m20261018_230000_create_pantry_items_table;
+mod m20261019_000000_create_collections_tables;
+mod m20261019_010000_add_recipe_forks;
+mod m20261019_020000_create_recipe_share_links_table;
+mod m20261019_030000_create_households_tables;
+mod m20261019_040000_create_recipe_collaborators_table;
+mod m20261019_050000_add_html_escape_function;
 
 pub struct Migrator;
 
@@ -77,6 +95,24 @@ impl MigratorTrait for Migrator {
             Box::new(m20260414_022539_create_notification_table::Migration),
             Box::new(m20260414_030931_add_default_notification_templates::Migration),
             Box::new(m20260415_003748_add_comment_reply_notification_template::Migration),
+            Box::new(m20261018_120000_link_ingredient_units_to_base_units::Migration),
+            Box::new(m20261018_130000_add_us_customary_units::Migration),
+            Box::new(m20261018_140000_create_ingredient_densities_table::Migration),
+            Box::new(m20261018_150000_create_recipe_prints_table::Migration),
+            Box::new(m20261018_160000_add_full_text_search::Migration),
+            Box::new(m20261018_170000_create_ingredient_lexicon_links_table::Migration),
+            Box::new(m20261018_180000_create_canonical_ingredients_tables::Migration),
+            Box::new(m20261018_190000_create_foods_tables::Migration),
+            Box::new(m20261018_200000_create_dietary_labels_tables::Migration),
+            Box::new(m20261018_210000_create_shopping_lists_tables::Migration),
+            Box::new(m20261018_220000_create_meal_plans_tables::Migration),
+            Box::new(m20261018_230000_create_pantry_items_table::Migration),
+            Box::new(m20261019_000000_create_collections_tables::Migration),
+            Box::new(m20261019_010000_add_recipe_forks::Migration),
+            Box::new(m20261019_020000_create_recipe_share_links_table::Migration),
+            Box::new(m20261019_030000_create_households_tables::Migration),
+            Box::new(m20261019_040000_create_recipe_collaborators_table::Migration),
+            Box::new(m20261019_050000_add_html_escape_function::Migration),
         ]
     }
 }
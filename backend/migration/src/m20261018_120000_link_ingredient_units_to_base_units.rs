@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Every convertible unit points at the unit its conversion_factor is expressed in.
+        let links = vec![
+            ("KILOGRAM", "GRAM"),
+            ("POUND", "GRAM"),
+            ("OUNCE", "GRAM"),
+            ("LITER", "MILLILITER"),
+            ("CUP", "MILLILITER"),
+            ("TABLESPOON", "MILLILITER"),
+            ("TEASPOON", "MILLILITER"),
+        ];
+
+        for (code, base) in links {
+            let sql = format!(
+                "UPDATE ingredient_units
+                 SET base_unit_id = (SELECT id FROM ingredient_units WHERE code = '{}')
+                 WHERE code = '{}'",
+                base, code
+            );
+            manager.get_connection().execute_unprepared(&sql).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE ingredient_units SET base_unit_id = NULL
+                 WHERE code IN ('KILOGRAM', 'POUND', 'OUNCE', 'LITER', 'CUP', 'TABLESPOON', 'TEASPOON')",
+            )
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let units = vec![
+            // CODE, SYMBOL, EN, FR, SYSTEM, FACTOR, FRACTION
+            ("US_CUP", "cup", "Cup (US)", "Tasse (US)", "imperial", 236.59, true),
+            ("US_TABLESPOON", "tbsp", "Tablespoon (US)", "Cuillère à soupe (US)", "imperial", 14.79, true),
+            ("US_TEASPOON", "tsp", "Teaspoon (US)", "Cuillère à thé (US)", "imperial", 4.93, true),
+        ];
+
+        for (code, symbol, en, fr, sys, factor, fraction) in units {
+            manager.exec_stmt(
+                Query::insert()
+                    .into_table(AliasIngredientUnits::Table)
+                    .columns([
+                        AliasIngredientUnits::Code, AliasIngredientUnits::Symbol,
+                        AliasIngredientUnits::NameEn, AliasIngredientUnits::NameFr, AliasIngredientUnits::System,
+                        AliasIngredientUnits::ConversionFactor, AliasIngredientUnits::IsFractionAllowed
+                    ])
+                    .values_panic([
+                        code.into(), symbol.into(),
+                        en.into(), fr.into(), sys.into(),
+                        factor.into(), fraction.into()
+                    ])
+                    .to_owned()
+            ).await?;
+
+            let sql = format!(
+                "UPDATE ingredient_units
+                 SET base_unit_id = (SELECT id FROM ingredient_units WHERE code = 'MILLILITER')
+                 WHERE code = '{}'",
+                code
+            );
+            manager.get_connection().execute_unprepared(&sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DELETE FROM ingredient_units WHERE code IN ('US_CUP', 'US_TABLESPOON', 'US_TEASPOON')",
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum AliasIngredientUnits {
+    #[iden = "ingredient_units"]
+    Table,
+    Code,
+    Symbol,
+    NameEn,
+    NameFr,
+    System,
+    ConversionFactor,
+    IsFractionAllowed,
+}
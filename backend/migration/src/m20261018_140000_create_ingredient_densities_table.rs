@@ -0,0 +1,81 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(IngredientDensities::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Alias::new("id")).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(string(IngredientDensities::NameEn).unique_key())
+                    .col(string(IngredientDensities::NameFr).unique_key())
+                    .col(ColumnDef::new(IngredientDensities::GramsPerMl).double().not_null())
+                    .check(Expr::col(IngredientDensities::GramsPerMl).gt(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        let densities = vec![
+            // EN, FR, GRAMS PER ML
+            ("water", "eau", 1.0),
+            ("milk", "lait", 1.03),
+            ("cream", "crème", 1.0),
+            ("yogurt", "yogourt", 1.03),
+            ("butter", "beurre", 0.96),
+            ("oil", "huile", 0.92),
+            ("olive oil", "huile d'olive", 0.91),
+            ("honey", "miel", 1.42),
+            ("maple syrup", "sirop d'érable", 1.32),
+            ("flour", "farine", 0.53),
+            ("whole wheat flour", "farine de blé entier", 0.55),
+            ("sugar", "sucre", 0.85),
+            ("brown sugar", "cassonade", 0.93),
+            ("icing sugar", "sucre à glacer", 0.56),
+            ("salt", "sel", 1.22),
+            ("baking powder", "poudre à pâte", 0.81),
+            ("baking soda", "bicarbonate de soude", 0.97),
+            ("cornstarch", "fécule de maïs", 0.54),
+            ("cocoa powder", "cacao", 0.42),
+            ("rice", "riz", 0.85),
+            ("rolled oats", "flocons d'avoine", 0.41),
+            ("ground almonds", "poudre d'amandes", 0.41),
+            ("chocolate chips", "pépites de chocolat", 0.72),
+            ("grated parmesan", "parmesan râpé", 0.4),
+            ("peanut butter", "beurre d'arachide", 1.08),
+        ];
+
+        for (en, fr, grams_per_ml) in densities {
+            manager.exec_stmt(
+                Query::insert()
+                    .into_table(IngredientDensities::Table)
+                    .columns([
+                        IngredientDensities::NameEn,
+                        IngredientDensities::NameFr,
+                        IngredientDensities::GramsPerMl,
+                    ])
+                    .values_panic([en.into(), fr.into(), grams_per_ml.into()])
+                    .to_owned()
+            ).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IngredientDensities::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum IngredientDensities {
+    Table,
+    NameEn,
+    NameFr,
+    GramsPerMl,
+}
@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RecipePrints::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Alias::new("id")).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(RecipePrints::RecipeId).uuid().not_null())
+                    .col(ColumnDef::new(RecipePrints::UserId).uuid()) // Nullable for anonymous prints
+                    .col(ColumnDef::new(RecipePrints::Layout).string().not_null())
+                    .col(
+                        ColumnDef::new(RecipePrints::PrintedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_prints_recipe")
+                            .from(RecipePrints::Table, RecipePrints::RecipeId)
+                            .to(Alias::new("recipes"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_prints_user")
+                            .from(RecipePrints::Table, RecipePrints::UserId)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager.create_index(
+            Index::create()
+                .name("idx_prints_recipe_id")
+                .table(RecipePrints::Table)
+                .col(RecipePrints::RecipeId)
+                .to_owned()
+        ).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RecipePrints::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RecipePrints {
+    Table,
+    RecipeId,
+    UserId,
+    Layout,
+    PrintedAt,
+}
@@ -0,0 +1,241 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Maps a language code to its text search configuration. It has to be IMMUTABLE
+        // to be usable in generated columns; languages without a stemmer fall back to 'simple'.
+        db.execute_unprepared(
+            r#"
+            CREATE OR REPLACE FUNCTION search_config(language_code TEXT)
+            RETURNS regconfig AS $$
+                SELECT CASE language_code
+                    WHEN 'fr' THEN 'french'::regconfig
+                    WHEN 'en' THEN 'english'::regconfig
+                    ELSE 'simple'::regconfig
+                END;
+            $$ LANGUAGE sql IMMUTABLE;
+            "#,
+        )
+            .await?;
+
+        // Per-row vectors, weighted so that title > description > ingredients > steps.
+        let vectors = vec![
+            (
+                "recipe_translations",
+                "setweight(to_tsvector(search_config(language_code), coalesce(title, '')), 'A') || \
+                 setweight(to_tsvector(search_config(language_code), coalesce(description, '')), 'B')",
+            ),
+            (
+                "ingredient_translations",
+                "setweight(to_tsvector(search_config(language_code), coalesce(data, '')), 'C') || \
+                 setweight(to_tsvector(search_config(language_code), coalesce(note, '')), 'D')",
+            ),
+            (
+                "step_translations",
+                "setweight(to_tsvector(search_config(language_code), coalesce(instruction, '')), 'D')",
+            ),
+        ];
+
+        for (table, vector) in vectors {
+            db.execute_unprepared(&format!(
+                "ALTER TABLE {table} ADD COLUMN search_vector tsvector GENERATED ALWAYS AS ({vector}) STORED"
+            ))
+                .await?;
+        }
+
+        // One document per recipe and language, so that every search term may be found
+        // in a different part of the recipe.
+        manager
+            .create_table(
+                Table::create()
+                    .table(RecipeSearchDocuments::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(RecipeSearchDocuments::RecipeId).uuid().not_null())
+                    .col(ColumnDef::new(RecipeSearchDocuments::LanguageCode).string().not_null())
+                    .col(ColumnDef::new(RecipeSearchDocuments::Document).custom(Alias::new("tsvector")).not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(RecipeSearchDocuments::RecipeId)
+                            .col(RecipeSearchDocuments::LanguageCode),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_search_documents_recipe")
+                            .from(RecipeSearchDocuments::Table, RecipeSearchDocuments::RecipeId)
+                            .to(Alias::new("recipes"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE INDEX idx_search_documents_document ON recipe_search_documents USING GIN (document);
+
+            CREATE AGGREGATE tsvector_agg(tsvector) (
+                SFUNC = tsvector_concat,
+                STYPE = tsvector,
+                INITCOND = ''
+            );
+
+            CREATE OR REPLACE FUNCTION refresh_recipe_search_documents(target_recipe_id UUID)
+            RETURNS void AS $$
+            BEGIN
+                DELETE FROM recipe_search_documents WHERE recipe_id = target_recipe_id;
+
+                INSERT INTO recipe_search_documents (recipe_id, language_code, document)
+                SELECT
+                    rt.recipe_id,
+                    rt.language_code,
+                    rt.search_vector
+                        || coalesce((
+                            SELECT tsvector_agg(it.search_vector)
+                            FROM ingredient_groups ig
+                            JOIN ingredients i ON i.ingredient_group_id = ig.id
+                            JOIN ingredient_translations it ON it.ingredient_id = i.id
+                            WHERE ig.recipe_id = rt.recipe_id AND it.language_code = rt.language_code
+                        ), '')
+                        || coalesce((
+                            SELECT tsvector_agg(st.search_vector)
+                            FROM step_groups sg
+                            JOIN steps s ON s.step_group_id = sg.id
+                            JOIN step_translations st ON st.step_id = s.id
+                            WHERE sg.recipe_id = rt.recipe_id AND st.language_code = rt.language_code
+                        ), '')
+                FROM recipe_translations rt
+                -- Skips recipes that are being deleted, their rows cascade one table at a time.
+                JOIN recipes r ON r.id = rt.recipe_id
+                WHERE rt.recipe_id = target_recipe_id;
+            END;
+            $$ LANGUAGE plpgsql;
+
+            -- Resolves the recipe a changed row belongs to. When a parent row is deleted, the
+            -- cascade runs before these triggers, so the parent's own trigger does the refresh.
+            CREATE OR REPLACE FUNCTION search_document_recipe_id(source TEXT, row_data JSONB)
+            RETURNS UUID AS $$
+                SELECT CASE source
+                    WHEN 'recipe_translations' THEN (row_data->>'recipe_id')::uuid
+                    WHEN 'ingredient_groups' THEN (row_data->>'recipe_id')::uuid
+                    WHEN 'step_groups' THEN (row_data->>'recipe_id')::uuid
+                    WHEN 'ingredients' THEN (
+                        SELECT recipe_id FROM ingredient_groups
+                        WHERE id = (row_data->>'ingredient_group_id')::uuid
+                    )
+                    WHEN 'steps' THEN (
+                        SELECT recipe_id FROM step_groups
+                        WHERE id = (row_data->>'step_group_id')::uuid
+                    )
+                    WHEN 'ingredient_translations' THEN (
+                        SELECT ig.recipe_id FROM ingredients i
+                        JOIN ingredient_groups ig ON ig.id = i.ingredient_group_id
+                        WHERE i.id = (row_data->>'ingredient_id')::uuid
+                    )
+                    WHEN 'step_translations' THEN (
+                        SELECT sg.recipe_id FROM steps s
+                        JOIN step_groups sg ON sg.id = s.step_group_id
+                        WHERE s.id = (row_data->>'step_id')::uuid
+                    )
+                END;
+            $$ LANGUAGE sql STABLE;
+
+            CREATE OR REPLACE FUNCTION refresh_recipe_search_documents_trigger()
+            RETURNS TRIGGER AS $$
+            DECLARE
+                old_recipe_id UUID;
+                new_recipe_id UUID;
+            BEGIN
+                IF TG_OP <> 'INSERT' THEN
+                    old_recipe_id := search_document_recipe_id(TG_TABLE_NAME, to_jsonb(OLD));
+                END IF;
+                IF TG_OP <> 'DELETE' THEN
+                    new_recipe_id := search_document_recipe_id(TG_TABLE_NAME, to_jsonb(NEW));
+                END IF;
+
+                IF old_recipe_id IS NOT NULL THEN
+                    PERFORM refresh_recipe_search_documents(old_recipe_id);
+                END IF;
+                IF new_recipe_id IS NOT NULL AND new_recipe_id IS DISTINCT FROM old_recipe_id THEN
+                    PERFORM refresh_recipe_search_documents(new_recipe_id);
+                END IF;
+                RETURN NULL;
+            END;
+            $$ LANGUAGE plpgsql;
+            "#,
+        )
+            .await?;
+
+        // Text changes go through the translation tables; the structural tables are only
+        // watched for deletes and moves, which would otherwise leave stale words behind.
+        for (table, events) in WATCHED_TABLES {
+            db.execute_unprepared(&format!(
+                "CREATE TRIGGER trg_{table}_search_documents
+                 AFTER {events} ON {table}
+                 FOR EACH ROW EXECUTE FUNCTION refresh_recipe_search_documents_trigger()"
+            ))
+                .await?;
+        }
+
+        // Backfill the existing recipes.
+        db.execute_unprepared("SELECT refresh_recipe_search_documents(id) FROM recipes")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        for (table, _) in WATCHED_TABLES {
+            db.execute_unprepared(&format!("DROP TRIGGER IF EXISTS trg_{table}_search_documents ON {table}"))
+                .await?;
+        }
+
+        db.execute_unprepared(
+            r#"
+            DROP FUNCTION IF EXISTS refresh_recipe_search_documents_trigger();
+            DROP FUNCTION IF EXISTS search_document_recipe_id(TEXT, JSONB);
+            DROP FUNCTION IF EXISTS refresh_recipe_search_documents(UUID);
+            DROP AGGREGATE IF EXISTS tsvector_agg(tsvector);
+            "#,
+        )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(RecipeSearchDocuments::Table).if_exists().to_owned())
+            .await?;
+
+        for table in ["recipe_translations", "ingredient_translations", "step_translations"] {
+            db.execute_unprepared(&format!("ALTER TABLE {table} DROP COLUMN IF EXISTS search_vector"))
+                .await?;
+        }
+
+        db.execute_unprepared("DROP FUNCTION IF EXISTS search_config(TEXT)")
+            .await?;
+        Ok(())
+    }
+}
+
+const WATCHED_TABLES: [(&str, &str); 7] = [
+    ("recipe_translations", "INSERT OR UPDATE OR DELETE"),
+    ("ingredient_translations", "INSERT OR UPDATE OR DELETE"),
+    ("step_translations", "INSERT OR UPDATE OR DELETE"),
+    ("ingredients", "UPDATE OF ingredient_group_id OR DELETE"),
+    ("steps", "UPDATE OF step_group_id OR DELETE"),
+    ("ingredient_groups", "UPDATE OF recipe_id OR DELETE"),
+    ("step_groups", "UPDATE OF recipe_id OR DELETE"),
+];
+
+#[derive(DeriveIden)]
+enum RecipeSearchDocuments {
+    Table,
+    RecipeId,
+    LanguageCode,
+    Document,
+}
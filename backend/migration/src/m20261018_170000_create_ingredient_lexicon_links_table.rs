@@ -0,0 +1,52 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The lexicon lives in the SQLite dictionary, so links are resolved by the
+        // application and keep the text they were resolved from to detect edits.
+        manager
+            .create_table(
+                Table::create()
+                    .table(IngredientLexiconLinks::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(IngredientLexiconLinks::IngredientTranslationId).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(IngredientLexiconLinks::LexiconId).integer()) // Null when nothing matched
+                    .col(ColumnDef::new(IngredientLexiconLinks::SourceText).text().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_lexicon_links_ingredient_translation")
+                            .from(IngredientLexiconLinks::Table, IngredientLexiconLinks::IngredientTranslationId)
+                            .to(Alias::new("ingredient_translations"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager.create_index(
+            Index::create()
+                .name("idx_lexicon_links_lexicon_id")
+                .table(IngredientLexiconLinks::Table)
+                .col(IngredientLexiconLinks::LexiconId)
+                .to_owned()
+        ).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IngredientLexiconLinks::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum IngredientLexiconLinks {
+    Table,
+    IngredientTranslationId,
+    LexiconId,
+    SourceText,
+}
@@ -0,0 +1,160 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CanonicalIngredients::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(CanonicalIngredients::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    // Entry of the SQLite lexicon it was seeded from, if any
+                    .col(ColumnDef::new(CanonicalIngredients::LexiconId).integer().unique_key())
+                    .col(
+                        ColumnDef::new(CanonicalIngredients::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(CanonicalIngredientTranslations::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(CanonicalIngredientTranslations::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(CanonicalIngredientTranslations::CanonicalIngredientId).uuid().not_null())
+                    .col(ColumnDef::new(CanonicalIngredientTranslations::LanguageCode).string().not_null())
+                    .col(ColumnDef::new(CanonicalIngredientTranslations::Name).string().not_null())
+                    .index(
+                        Index::create()
+                            .name("unique_canonical_ingredient_language")
+                            .col(CanonicalIngredientTranslations::CanonicalIngredientId)
+                            .col(CanonicalIngredientTranslations::LanguageCode)
+                            .unique(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_canonical_translations_ingredient")
+                            .from(CanonicalIngredientTranslations::Table, CanonicalIngredientTranslations::CanonicalIngredientId)
+                            .to(CanonicalIngredients::Table, CanonicalIngredients::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_canonical_translations_language")
+                            .from(CanonicalIngredientTranslations::Table, CanonicalIngredientTranslations::LanguageCode)
+                            .to(Alias::new("languages"), Alias::new("code"))
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Other spellings, stored lowercase so that each one points to a single ingredient.
+        manager
+            .create_table(
+                Table::create()
+                    .table(CanonicalIngredientAliases::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(CanonicalIngredientAliases::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(CanonicalIngredientAliases::CanonicalIngredientId).uuid().not_null())
+                    .col(ColumnDef::new(CanonicalIngredientAliases::Alias).string().not_null().unique_key())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_canonical_aliases_ingredient")
+                            .from(CanonicalIngredientAliases::Table, CanonicalIngredientAliases::CanonicalIngredientId)
+                            .to(CanonicalIngredients::Table, CanonicalIngredients::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ingredients::Table)
+                    .add_column(ColumnDef::new(Ingredients::CanonicalIngredientId).uuid())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_ingredients_canonical_ingredient")
+                            .from_tbl(Ingredients::Table)
+                            .from_col(Ingredients::CanonicalIngredientId)
+                            .to_tbl(CanonicalIngredients::Table)
+                            .to_col(CanonicalIngredients::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager.create_index(
+            Index::create()
+                .name("idx_ingredients_canonical_ingredient_id")
+                .table(Ingredients::Table)
+                .col(Ingredients::CanonicalIngredientId)
+                .to_owned()
+        ).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ingredients::Table)
+                    .drop_foreign_key(Alias::new("fk_ingredients_canonical_ingredient"))
+                    .drop_column(Ingredients::CanonicalIngredientId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(CanonicalIngredientAliases::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(CanonicalIngredientTranslations::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(CanonicalIngredients::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CanonicalIngredients {
+    Table,
+    Id,
+    LexiconId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum CanonicalIngredientTranslations {
+    Table,
+    Id,
+    CanonicalIngredientId,
+    LanguageCode,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum CanonicalIngredientAliases {
+    Table,
+    Id,
+    CanonicalIngredientId,
+    Alias,
+}
+
+#[derive(DeriveIden)]
+enum Ingredients {
+    Table,
+    CanonicalIngredientId,
+}
@@ -0,0 +1,113 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Foods of an imported composition table (CIQUAL, USDA, ...), nutrients per 100 g.
+        manager
+            .create_table(
+                Table::create()
+                    .table(Foods::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Foods::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(Foods::Source).string().not_null())
+                    // Identifier of the food in its source, used to update it on a later import
+                    .col(ColumnDef::new(Foods::SourceCode).string().not_null())
+                    .col(ColumnDef::new(Foods::NameEn).text())
+                    .col(ColumnDef::new(Foods::NameFr).text())
+                    .col(ColumnDef::new(Foods::EnergyKcal).double())
+                    .col(ColumnDef::new(Foods::ProteinG).double())
+                    .col(ColumnDef::new(Foods::FatG).double())
+                    .col(ColumnDef::new(Foods::CarbohydratesG).double())
+                    .col(ColumnDef::new(Foods::FiberG).double())
+                    .col(ColumnDef::new(Foods::SodiumMg).double())
+                    .col(
+                        ColumnDef::new(Foods::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .index(
+                        Index::create()
+                            .name("unique_food_source_code")
+                            .col(Foods::Source)
+                            .col(Foods::SourceCode)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(CanonicalIngredientFoods::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(CanonicalIngredientFoods::CanonicalIngredientId).uuid().primary_key())
+                    .col(ColumnDef::new(CanonicalIngredientFoods::FoodId).uuid().not_null())
+                    // Weight of one piece, clove, slice... for lines counted rather than measured
+                    .col(ColumnDef::new(CanonicalIngredientFoods::GramsPerUnit).double())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_canonical_foods_ingredient")
+                            .from(CanonicalIngredientFoods::Table, CanonicalIngredientFoods::CanonicalIngredientId)
+                            .to(Alias::new("canonical_ingredients"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_canonical_foods_food")
+                            .from(CanonicalIngredientFoods::Table, CanonicalIngredientFoods::FoodId)
+                            .to(Foods::Table, Foods::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager.create_index(
+            Index::create()
+                .name("idx_canonical_ingredient_foods_food_id")
+                .table(CanonicalIngredientFoods::Table)
+                .col(CanonicalIngredientFoods::FoodId)
+                .to_owned()
+        ).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CanonicalIngredientFoods::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Foods::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Foods {
+    Table,
+    Id,
+    Source,
+    SourceCode,
+    NameEn,
+    NameFr,
+    EnergyKcal,
+    ProteinG,
+    FatG,
+    CarbohydratesG,
+    FiberG,
+    SodiumMg,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum CanonicalIngredientFoods {
+    Table,
+    CanonicalIngredientId,
+    FoodId,
+    GramsPerUnit,
+}
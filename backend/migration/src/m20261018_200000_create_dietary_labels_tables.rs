@@ -0,0 +1,113 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // An ingredient, known by its lexicon entry or its catalog entry, belongs to a
+        // dietary category (nuts, gluten, dairy, meat...).
+        manager
+            .create_table(
+                Table::create()
+                    .table(DietaryRules::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(DietaryRules::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(DietaryRules::Category).string().not_null())
+                    .col(ColumnDef::new(DietaryRules::LexiconId).integer())
+                    .col(ColumnDef::new(DietaryRules::CanonicalIngredientId).uuid())
+                    .col(
+                        ColumnDef::new(DietaryRules::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .check(Expr::cust("(lexicon_id IS NULL) <> (canonical_ingredient_id IS NULL)"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dietary_rules_canonical_ingredient")
+                            .from(DietaryRules::Table, DietaryRules::CanonicalIngredientId)
+                            .to(Alias::new("canonical_ingredients"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE UNIQUE INDEX unique_dietary_rule_lexicon ON dietary_rules (category, lexicon_id)
+                WHERE lexicon_id IS NOT NULL;
+            CREATE UNIQUE INDEX unique_dietary_rule_canonical ON dietary_rules (category, canonical_ingredient_id)
+                WHERE canonical_ingredient_id IS NOT NULL;
+            "#,
+        )
+            .await?;
+
+        // `derived` is what the rules found, `override` what the author decided; the
+        // label applies when `coalesce(override, derived)` holds.
+        manager
+            .create_table(
+                Table::create()
+                    .table(RecipeDietaryLabels::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(RecipeDietaryLabels::RecipeId).uuid().not_null())
+                    .col(ColumnDef::new(RecipeDietaryLabels::Label).string().not_null())
+                    .col(ColumnDef::new(RecipeDietaryLabels::Derived).boolean().not_null().default(false))
+                    .col(ColumnDef::new(RecipeDietaryLabels::Override).boolean())
+                    .primary_key(
+                        Index::create()
+                            .col(RecipeDietaryLabels::RecipeId)
+                            .col(RecipeDietaryLabels::Label),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dietary_labels_recipe")
+                            .from(RecipeDietaryLabels::Table, RecipeDietaryLabels::RecipeId)
+                            .to(Alias::new("recipes"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager.create_index(
+            Index::create()
+                .name("idx_recipe_dietary_labels_label")
+                .table(RecipeDietaryLabels::Table)
+                .col(RecipeDietaryLabels::Label)
+                .to_owned()
+        ).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RecipeDietaryLabels::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(DietaryRules::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DietaryRules {
+    Table,
+    Id,
+    Category,
+    LexiconId,
+    CanonicalIngredientId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum RecipeDietaryLabels {
+    Table,
+    RecipeId,
+    Label,
+    Derived,
+    Override,
+}
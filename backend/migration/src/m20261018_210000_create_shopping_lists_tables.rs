@@ -0,0 +1,262 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Shelf the ingredient is found on, used to group shopping list items
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CanonicalIngredients::Table)
+                    .add_column(ColumnDef::new(CanonicalIngredients::Aisle).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShoppingLists::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ShoppingLists::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(ShoppingLists::OwnerId).uuid().not_null())
+                    .col(ColumnDef::new(ShoppingLists::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(ShoppingLists::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .col(
+                        ColumnDef::new(ShoppingLists::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shopping_lists_owner")
+                            .from(ShoppingLists::Table, ShoppingLists::OwnerId)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShoppingListMembers::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ShoppingListMembers::ShoppingListId).uuid().not_null())
+                    .col(ColumnDef::new(ShoppingListMembers::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ShoppingListMembers::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ShoppingListMembers::ShoppingListId)
+                            .col(ShoppingListMembers::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shopping_list_members_list")
+                            .from(ShoppingListMembers::Table, ShoppingListMembers::ShoppingListId)
+                            .to(ShoppingLists::Table, ShoppingLists::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shopping_list_members_user")
+                            .from(ShoppingListMembers::Table, ShoppingListMembers::UserId)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShoppingListRecipes::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ShoppingListRecipes::ShoppingListId).uuid().not_null())
+                    .col(ColumnDef::new(ShoppingListRecipes::RecipeId).uuid().not_null())
+                    .col(ColumnDef::new(ShoppingListRecipes::Servings).integer().not_null())
+                    .col(
+                        ColumnDef::new(ShoppingListRecipes::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ShoppingListRecipes::ShoppingListId)
+                            .col(ShoppingListRecipes::RecipeId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shopping_list_recipes_list")
+                            .from(ShoppingListRecipes::Table, ShoppingListRecipes::ShoppingListId)
+                            .to(ShoppingLists::Table, ShoppingLists::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shopping_list_recipes_recipe")
+                            .from(ShoppingListRecipes::Table, ShoppingListRecipes::RecipeId)
+                            .to(Alias::new("recipes"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Items added from recipes carry the key they were merged under, so that a
+        // rebuild keeps their checked state; items added by hand have none.
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShoppingListItems::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ShoppingListItems::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(ShoppingListItems::ShoppingListId).uuid().not_null())
+                    .col(ColumnDef::new(ShoppingListItems::ItemKey).text())
+                    .col(ColumnDef::new(ShoppingListItems::Name).text().not_null())
+                    .col(ColumnDef::new(ShoppingListItems::Quantity).double())
+                    .col(ColumnDef::new(ShoppingListItems::UnitId).uuid())
+                    .col(ColumnDef::new(ShoppingListItems::CanonicalIngredientId).uuid())
+                    .col(ColumnDef::new(ShoppingListItems::Aisle).string().not_null())
+                    .col(ColumnDef::new(ShoppingListItems::Checked).boolean().not_null().default(false))
+                    .col(
+                        ColumnDef::new(ShoppingListItems::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .col(
+                        ColumnDef::new(ShoppingListItems::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shopping_list_items_list")
+                            .from(ShoppingListItems::Table, ShoppingListItems::ShoppingListId)
+                            .to(ShoppingLists::Table, ShoppingLists::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shopping_list_items_unit")
+                            .from(ShoppingListItems::Table, ShoppingListItems::UnitId)
+                            .to(Alias::new("ingredient_units"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shopping_list_items_canonical_ingredient")
+                            .from(ShoppingListItems::Table, ShoppingListItems::CanonicalIngredientId)
+                            .to(CanonicalIngredients::Table, CanonicalIngredients::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE UNIQUE INDEX unique_shopping_list_item_key ON shopping_list_items (shopping_list_id, item_key)
+                WHERE item_key IS NOT NULL;
+            CREATE INDEX idx_shopping_list_members_user_id ON shopping_list_members (user_id);
+            "#,
+        )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ShoppingListItems::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ShoppingListRecipes::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ShoppingListMembers::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ShoppingLists::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CanonicalIngredients::Table)
+                    .drop_column(CanonicalIngredients::Aisle)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CanonicalIngredients {
+    Table,
+    Id,
+    Aisle,
+}
+
+#[derive(DeriveIden)]
+enum ShoppingLists {
+    Table,
+    Id,
+    OwnerId,
+    Name,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ShoppingListMembers {
+    Table,
+    ShoppingListId,
+    UserId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ShoppingListRecipes {
+    Table,
+    ShoppingListId,
+    RecipeId,
+    Servings,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ShoppingListItems {
+    Table,
+    Id,
+    ShoppingListId,
+    ItemKey,
+    Name,
+    Quantity,
+    UnitId,
+    CanonicalIngredientId,
+    Aisle,
+    Checked,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,123 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // The recipe title is copied on the plan so that a recipe deleted later can
+        // still be named when warning about it.
+        manager
+            .create_table(
+                Table::create()
+                    .table(MealPlans::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(MealPlans::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(MealPlans::UserId).uuid().not_null())
+                    .col(ColumnDef::new(MealPlans::PlanDate).date().not_null())
+                    .col(ColumnDef::new(MealPlans::Slot).string().not_null())
+                    .col(ColumnDef::new(MealPlans::RecipeId).uuid())
+                    .col(ColumnDef::new(MealPlans::RecipeTitle).text())
+                    .col(ColumnDef::new(MealPlans::Servings).integer().not_null())
+                    .col(ColumnDef::new(MealPlans::Note).text())
+                    .col(
+                        ColumnDef::new(MealPlans::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .col(
+                        ColumnDef::new(MealPlans::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_meal_plans_user")
+                            .from(MealPlans::Table, MealPlans::UserId)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_meal_plans_recipe")
+                            .from(MealPlans::Table, MealPlans::RecipeId)
+                            .to(Alias::new("recipes"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Secret token of the calendar feed, since calendar apps cannot send a session
+        manager
+            .create_table(
+                Table::create()
+                    .table(MealPlanFeeds::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(MealPlanFeeds::UserId).uuid().primary_key())
+                    .col(ColumnDef::new(MealPlanFeeds::Token).string().not_null().unique_key())
+                    .col(
+                        ColumnDef::new(MealPlanFeeds::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_meal_plan_feeds_user")
+                            .from(MealPlanFeeds::Table, MealPlanFeeds::UserId)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE INDEX idx_meal_plans_user_date ON meal_plans (user_id, plan_date);
+            CREATE INDEX idx_meal_plans_recipe_id ON meal_plans (recipe_id);
+            "#,
+        )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MealPlanFeeds::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(MealPlans::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MealPlans {
+    Table,
+    Id,
+    UserId,
+    PlanDate,
+    Slot,
+    RecipeId,
+    RecipeTitle,
+    Servings,
+    Note,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum MealPlanFeeds {
+    Table,
+    UserId,
+    Token,
+    CreatedAt,
+}
@@ -0,0 +1,136 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // `expiry_notified_at` is set once the owner was told the item is expiring, and
+        // cleared when its best-before date changes.
+        manager
+            .create_table(
+                Table::create()
+                    .table(PantryItems::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(PantryItems::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(PantryItems::UserId).uuid().not_null())
+                    .col(ColumnDef::new(PantryItems::Name).text().not_null())
+                    .col(ColumnDef::new(PantryItems::CanonicalIngredientId).uuid())
+                    .col(ColumnDef::new(PantryItems::Quantity).double())
+                    .col(ColumnDef::new(PantryItems::UnitId).uuid())
+                    .col(ColumnDef::new(PantryItems::Location).string().not_null())
+                    .col(ColumnDef::new(PantryItems::BestBefore).date())
+                    .col(ColumnDef::new(PantryItems::ExpiryNotifiedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(PantryItems::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .col(
+                        ColumnDef::new(PantryItems::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pantry_items_user")
+                            .from(PantryItems::Table, PantryItems::UserId)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pantry_items_canonical_ingredient")
+                            .from(PantryItems::Table, PantryItems::CanonicalIngredientId)
+                            .to(Alias::new("canonical_ingredients"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pantry_items_unit")
+                            .from(PantryItems::Table, PantryItems::UnitId)
+                            .to(Alias::new("ingredient_units"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE INDEX idx_pantry_items_user_id ON pantry_items (user_id);
+            CREATE INDEX idx_pantry_items_best_before ON pantry_items (best_before)
+                WHERE expiry_notified_at IS NULL;
+            "#,
+        )
+            .await?;
+
+        let insert = Query::insert()
+            .into_table(NotificationTemplates::Table)
+            .columns([
+                NotificationTemplates::Category,
+                NotificationTemplates::LanguageCode,
+                NotificationTemplates::TitleTemplate,
+                NotificationTemplates::MessageTemplate,
+            ])
+            .values_panic([
+                "pantry_expiring".into(),
+                "en".into(),
+                "Use It Soon".into(),
+                "{item_name} is best before {best_before}.".into(),
+            ])
+            .values_panic([
+                "pantry_expiring".into(),
+                "fr".into(),
+                "À utiliser bientôt".into(),
+                "{item_name} est à consommer de préférence avant le {best_before}.".into(),
+            ])
+            .to_owned();
+
+        manager.exec_stmt(insert).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(NotificationTemplates::Table)
+                    .and_where(Expr::col(NotificationTemplates::Category).eq("pantry_expiring"))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(PantryItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PantryItems {
+    Table,
+    Id,
+    UserId,
+    Name,
+    CanonicalIngredientId,
+    Quantity,
+    UnitId,
+    Location,
+    BestBefore,
+    ExpiryNotifiedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum NotificationTemplates {
+    Table,
+    Category,
+    LanguageCode,
+    TitleTemplate,
+    MessageTemplate,
+}
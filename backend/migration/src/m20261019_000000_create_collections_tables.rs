@@ -0,0 +1,124 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Collections::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Collections::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(Collections::OwnerId).uuid().not_null())
+                    .col(ColumnDef::new(Collections::Title).string().not_null())
+                    .col(ColumnDef::new(Collections::Description).text())
+                    .col(ColumnDef::new(Collections::CoverImageUrl).string())
+                    .col(ColumnDef::new(Collections::IsPublic).boolean().not_null().default(false))
+                    .col(
+                        ColumnDef::new(Collections::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .col(
+                        ColumnDef::new(Collections::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_collections_owner")
+                            .from(Collections::Table, Collections::OwnerId)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(CollectionRecipes::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(CollectionRecipes::CollectionId).uuid().not_null())
+                    .col(ColumnDef::new(CollectionRecipes::RecipeId).uuid().not_null())
+                    .col(ColumnDef::new(CollectionRecipes::Position).integer().not_null())
+                    .col(
+                        ColumnDef::new(CollectionRecipes::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(CollectionRecipes::CollectionId)
+                            .col(CollectionRecipes::RecipeId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_collection_recipes_collection")
+                            .from(CollectionRecipes::Table, CollectionRecipes::CollectionId)
+                            .to(Collections::Table, Collections::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_collection_recipes_recipe")
+                            .from(CollectionRecipes::Table, CollectionRecipes::RecipeId)
+                            .to(Alias::new("recipes"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE INDEX idx_collections_owner_id ON collections (owner_id);
+            CREATE INDEX idx_collection_recipes_recipe_id ON collection_recipes (recipe_id);
+            CREATE INDEX idx_collection_recipes_position ON collection_recipes (collection_id, position);
+            "#,
+        )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CollectionRecipes::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Collections::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Collections {
+    Table,
+    Id,
+    OwnerId,
+    Title,
+    Description,
+    CoverImageUrl,
+    IsPublic,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum CollectionRecipes {
+    Table,
+    CollectionId,
+    RecipeId,
+    Position,
+    CreatedAt,
+}
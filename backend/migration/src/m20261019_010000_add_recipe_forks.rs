@@ -0,0 +1,93 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // A fork outlives its original; the link is only cleared when that one is deleted
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Recipes::Table)
+                    .add_column(ColumnDef::new(Recipes::ForkedFrom).uuid())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_recipes_forked_from")
+                            .from_tbl(Recipes::Table)
+                            .from_col(Recipes::ForkedFrom)
+                            .to_tbl(Recipes::Table)
+                            .to_col(Recipes::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared("CREATE INDEX idx_recipes_forked_from ON recipes (forked_from);")
+            .await?;
+
+        let insert = Query::insert()
+            .into_table(NotificationTemplates::Table)
+            .columns([
+                NotificationTemplates::Category,
+                NotificationTemplates::LanguageCode,
+                NotificationTemplates::TitleTemplate,
+                NotificationTemplates::MessageTemplate,
+            ])
+            .values_panic([
+                "recipe_forked".into(),
+                "en".into(),
+                "Recipe Forked".into(),
+                "{actor} made their own version of {recipe_title}.".into(),
+            ])
+            .values_panic([
+                "recipe_forked".into(),
+                "fr".into(),
+                "Recette adaptée".into(),
+                "{actor} a créé sa propre version de {recipe_title}.".into(),
+            ])
+            .to_owned();
+
+        manager.exec_stmt(insert).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(NotificationTemplates::Table)
+                    .and_where(Expr::col(NotificationTemplates::Category).eq("recipe_forked"))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Recipes::Table)
+                    .drop_foreign_key(Alias::new("fk_recipes_forked_from"))
+                    .drop_column(Recipes::ForkedFrom)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Recipes {
+    Table,
+    Id,
+    ForkedFrom,
+}
+
+#[derive(DeriveIden)]
+enum NotificationTemplates {
+    Table,
+    Category,
+    LanguageCode,
+    TitleTemplate,
+    MessageTemplate,
+}
@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RecipeShareLinks::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(RecipeShareLinks::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(RecipeShareLinks::RecipeId).uuid().not_null())
+                    .col(ColumnDef::new(RecipeShareLinks::CreatedBy).uuid().not_null())
+                    .col(ColumnDef::new(RecipeShareLinks::Token).string().not_null().unique_key())
+                    .col(ColumnDef::new(RecipeShareLinks::Label).string())
+                    .col(ColumnDef::new(RecipeShareLinks::ExpiresAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(RecipeShareLinks::RevokedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(RecipeShareLinks::OpenCount).big_integer().not_null().default(0))
+                    .col(ColumnDef::new(RecipeShareLinks::LastOpenedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(RecipeShareLinks::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_recipe_share_links_recipe")
+                            .from(RecipeShareLinks::Table, RecipeShareLinks::RecipeId)
+                            .to(Alias::new("recipes"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_recipe_share_links_created_by")
+                            .from(RecipeShareLinks::Table, RecipeShareLinks::CreatedBy)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE INDEX idx_recipe_share_links_recipe_id ON recipe_share_links (recipe_id);
+            "#,
+        )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RecipeShareLinks::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RecipeShareLinks {
+    Table,
+    Id,
+    RecipeId,
+    CreatedBy,
+    Token,
+    Label,
+    ExpiresAt,
+    RevokedAt,
+    OpenCount,
+    LastOpenedAt,
+    CreatedAt,
+}
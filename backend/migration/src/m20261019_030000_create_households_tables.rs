@@ -0,0 +1,208 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Households::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Households::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(Households::OwnerId).uuid().not_null())
+                    .col(ColumnDef::new(Households::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(Households::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .col(
+                        ColumnDef::new(Households::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_households_owner")
+                            .from(Households::Table, Households::OwnerId)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The owner is kept on the household itself; members are editors or viewers
+        manager
+            .create_table(
+                Table::create()
+                    .table(HouseholdMembers::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(HouseholdMembers::HouseholdId).uuid().not_null())
+                    .col(ColumnDef::new(HouseholdMembers::UserId).uuid().not_null())
+                    .col(ColumnDef::new(HouseholdMembers::Role).string().not_null())
+                    .col(
+                        ColumnDef::new(HouseholdMembers::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(HouseholdMembers::HouseholdId)
+                            .col(HouseholdMembers::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_household_members_household")
+                            .from(HouseholdMembers::Table, HouseholdMembers::HouseholdId)
+                            .to(Households::Table, Households::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_household_members_user")
+                            .from(HouseholdMembers::Table, HouseholdMembers::UserId)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(HouseholdInvitations::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(HouseholdInvitations::Id).uuid().primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(ColumnDef::new(HouseholdInvitations::HouseholdId).uuid().not_null())
+                    .col(ColumnDef::new(HouseholdInvitations::InvitedBy).uuid().not_null())
+                    .col(ColumnDef::new(HouseholdInvitations::Email).string().not_null())
+                    .col(ColumnDef::new(HouseholdInvitations::Role).string().not_null())
+                    .col(ColumnDef::new(HouseholdInvitations::Token).uuid().not_null().unique_key())
+                    .col(ColumnDef::new(HouseholdInvitations::ExpiresAt).timestamp_with_time_zone().not_null())
+                    .col(ColumnDef::new(HouseholdInvitations::AcceptedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(HouseholdInvitations::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_household_invitations_household")
+                            .from(HouseholdInvitations::Table, HouseholdInvitations::HouseholdId)
+                            .to(Households::Table, Households::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_household_invitations_invited_by")
+                            .from(HouseholdInvitations::Table, HouseholdInvitations::InvitedBy)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Recipes go back to their author alone when the household is deleted
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Recipes::Table)
+                    .add_column(ColumnDef::new(Recipes::HouseholdId).uuid())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_recipes_household")
+                            .from_tbl(Recipes::Table)
+                            .from_col(Recipes::HouseholdId)
+                            .to_tbl(Households::Table)
+                            .to_col(Households::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE INDEX idx_households_owner_id ON households (owner_id);
+            CREATE INDEX idx_household_members_user_id ON household_members (user_id);
+            CREATE INDEX idx_household_invitations_household_id ON household_invitations (household_id);
+            CREATE INDEX idx_recipes_household_id ON recipes (household_id);
+            "#,
+        )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Recipes::Table)
+                    .drop_foreign_key(Alias::new("fk_recipes_household"))
+                    .drop_column(Recipes::HouseholdId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(HouseholdInvitations::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(HouseholdMembers::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Households::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Households {
+    Table,
+    Id,
+    OwnerId,
+    Name,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum HouseholdMembers {
+    Table,
+    HouseholdId,
+    UserId,
+    Role,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum HouseholdInvitations {
+    Table,
+    Id,
+    HouseholdId,
+    InvitedBy,
+    Email,
+    Role,
+    Token,
+    ExpiresAt,
+    AcceptedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Recipes {
+    Table,
+    HouseholdId,
+}
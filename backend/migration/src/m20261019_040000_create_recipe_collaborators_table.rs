@@ -0,0 +1,102 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RecipeCollaborators::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(RecipeCollaborators::RecipeId).uuid().not_null())
+                    .col(ColumnDef::new(RecipeCollaborators::UserId).uuid().not_null())
+                    .col(ColumnDef::new(RecipeCollaborators::Role).string().not_null())
+                    .col(ColumnDef::new(RecipeCollaborators::AddedBy).uuid())
+                    .col(
+                        ColumnDef::new(RecipeCollaborators::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::cust("now()")),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(RecipeCollaborators::RecipeId)
+                            .col(RecipeCollaborators::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_recipe_collaborators_recipe")
+                            .from(RecipeCollaborators::Table, RecipeCollaborators::RecipeId)
+                            .to(Alias::new("recipes"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_recipe_collaborators_user")
+                            .from(RecipeCollaborators::Table, RecipeCollaborators::UserId)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_recipe_collaborators_added_by")
+                            .from(RecipeCollaborators::Table, RecipeCollaborators::AddedBy)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Role the editor held on the recipe when the version was made, empty for its
+        // author and for moderators
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RecipeVersions::Table)
+                    .add_column(ColumnDef::new(RecipeVersions::EditedAs).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared("CREATE INDEX idx_recipe_collaborators_user_id ON recipe_collaborators (user_id);")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RecipeVersions::Table)
+                    .drop_column(RecipeVersions::EditedAs)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(RecipeCollaborators::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RecipeCollaborators {
+    Table,
+    RecipeId,
+    UserId,
+    Role,
+    AddedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum RecipeVersions {
+    Table,
+    EditedAs,
+}
@@ -0,0 +1,35 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Search snippets are rendered as HTML, so the text is escaped before `ts_headline`
+        // adds its own `<mark>` tags.
+        db.execute_unprepared(
+            r#"
+            CREATE OR REPLACE FUNCTION html_escape(value TEXT)
+            RETURNS TEXT AS $$
+                SELECT replace(replace(replace(replace(replace(value,
+                    '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;'), '''', '&#39;');
+            $$ LANGUAGE sql IMMUTABLE;
+            "#,
+        )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared("DROP FUNCTION IF EXISTS html_escape(TEXT)")
+            .await?;
+
+        Ok(())
+    }
+}
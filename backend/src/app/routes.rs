@@ -1,6 +1,6 @@
 use actix_web::web;
 
-use crate::controllers::{auth_controller, files_controller, ingredients_controller, languages_controller, notification_controller, ocr_controller, recipes_controller, studio_controller, system_controller, tags_controller, unit_controller, upload_controller, users_controller};
+use crate::controllers::{auth_controller, collection_controller, density_controller, dietary_controller, files_controller, household_controller, ingredients_controller, languages_controller, meal_plan_controller, notification_controller, nutrition_controller, ocr_controller, pantry_controller, recipes_controller, shopping_list_controller, studio_controller, system_controller, tags_controller, unit_controller, upload_controller, users_controller};
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -13,6 +13,14 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .configure(tags_controller::configure)
             .configure(languages_controller::configure)
             .configure(unit_controller::configure)
+            .configure(density_controller::configure)
+            .configure(nutrition_controller::configure)
+            .configure(dietary_controller::configure)
+            .configure(shopping_list_controller::configure)
+            .configure(meal_plan_controller::configure)
+            .configure(pantry_controller::configure)
+            .configure(collection_controller::configure)
+            .configure(household_controller::configure)
             .configure(studio_controller::configure)
             .configure(notification_controller::configure)
             .configure(ocr_controller::configure)
@@ -10,6 +10,7 @@ use crate::app::middleware::auth_middleware;
 use crate::config::Config;
 use crate::errors;
 use crate::openapi::ApiDoc;
+use crate::services::{ingredient_service, pantry_service};
 use crate::utils::notification_hub::NotificationHub;
 use super::state::AppState;
 use super::{routes, middleware};
@@ -44,6 +45,21 @@ pub async fn start(config: Config) -> std::io::Result<()> {
 
     let bind_address = config.bind_address.clone();
 
+    actix_web::rt::spawn(ingredient_service::run_lexicon_sync(AppState {
+        db: db.clone(),
+        dict_db: dict_db.clone(),
+        redis: redis.clone(),
+        config: config.clone(),
+        notification_hub: notification_hub.clone(),
+    }));
+    actix_web::rt::spawn(pantry_service::run_expiry_reminders(AppState {
+        db: db.clone(),
+        dict_db: dict_db.clone(),
+        redis: redis.clone(),
+        config: config.clone(),
+        notification_hub: notification_hub.clone(),
+    }));
+
 
     println!("You can access the server at {}", config.bind_address);
 
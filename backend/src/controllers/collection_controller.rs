@@ -0,0 +1,131 @@
+use crate::app::state::AppState;
+use crate::domain::user::AuthenticatedUser;
+use crate::dto::collection_dto::{CollectionInput, CollectionOrderInput, CollectionRecipeInput, FavoritesToCollectionInput};
+use crate::errors::Error;
+use crate::services::collection_service;
+use crate::utils::header_extractor::extract_language;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::ops::Deref;
+use uuid::Uuid;
+use validator::Validate;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+
+    cfg.service(
+        web::scope("/collections")
+            .route("", web::post().to(create))
+            .route("/mine", web::get().to(get_mine))
+            .route("/from-favorites", web::post().to(create_from_favorites))
+            .route("/user/{user_id}", web::get().to(get_by_owner))
+            .route("/{id}", web::get().to(get))
+            .route("/{id}", web::put().to(update))
+            .route("/{id}", web::delete().to(delete))
+            .route("/{id}/recipes", web::post().to(add_recipe))
+            .route("/{id}/recipes/order", web::put().to(reorder))
+            .route("/{id}/recipes/{recipe_id}", web::delete().to(remove_recipe))
+    );
+}
+pub async fn get_mine(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    let collections = collection_service::get_mine(&state.db, auth.user.id).await?;
+    Ok(HttpResponse::Ok().json(collections))
+}
+/// Collections listed on a user's profile.
+pub async fn get_by_owner(
+    state: Data<AppState>,
+    auth: Option<AuthenticatedUser>,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    let viewer_id = auth.map(|a| a.user.id);
+    let collections = collection_service::get_by_owner(&state.db, path.into_inner(), viewer_id).await?;
+    Ok(HttpResponse::Ok().json(collections))
+}
+pub async fn get(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: Option<AuthenticatedUser>,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let viewer_id = auth.map(|a| a.user.id);
+    let collection = collection_service::get(&state.db, path.into_inner(), viewer_id, lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(collection))
+}
+pub async fn create(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    input: Json<CollectionInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let collection = collection_service::create(&state.db, auth.user.id, input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Created().json(collection))
+}
+pub async fn create_from_favorites(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    input: Json<FavoritesToCollectionInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let collection = collection_service::create_from_favorites(&state.db, auth.user.id, input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Created().json(collection))
+}
+pub async fn update(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<CollectionInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let collection = collection_service::update(&state.db, auth.user.id, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(collection))
+}
+pub async fn delete(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    collection_service::delete(&state.db, auth.user.id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+pub async fn add_recipe(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<CollectionRecipeInput>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let collection = collection_service::add_recipe(&state.db, auth.user.id, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(collection))
+}
+pub async fn remove_recipe(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, Error> {
+    let (id, recipe_id) = path.into_inner();
+    let lang_code = extract_language(&req);
+    let collection = collection_service::remove_recipe(&state.db, auth.user.id, id, recipe_id, lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(collection))
+}
+pub async fn reorder(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<CollectionOrderInput>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let collection = collection_service::reorder(&state.db, auth.user.id, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(collection))
+}
@@ -0,0 +1,68 @@
+use crate::app::state::AppState;
+use crate::domain::user::{AuthenticatedUser, Role};
+use crate::dto::density_dto::DensityInputDto;
+use crate::errors::Error;
+use crate::services::density_service;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{web, HttpResponse};
+use uuid::Uuid;
+use validator::Validate;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+
+    cfg.service(
+        web::scope("/densities")
+            .route("", web::get().to(list))
+            .route("", web::post().to(create))
+            .route("/{id}", web::get().to(get))
+            .route("/{id}", web::put().to(update))
+            .route("/{id}", web::delete().to(delete))
+    );
+}
+pub async fn list(
+    state: Data<AppState>,
+)-> Result<HttpResponse, Error>{
+    let densities = density_service::get_all(&state.db).await?;
+    Ok(HttpResponse::Ok().json(densities))
+}
+pub async fn get(
+    state: Data<AppState>,
+    path: Path<Uuid>,
+)-> Result<HttpResponse, Error>{
+    let result = density_service::get(&state.db, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+pub async fn create(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    input: Json<DensityInputDto>
+)-> Result<HttpResponse, Error>{
+    auth.require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
+    let new_density = input.into_inner();
+    new_density.validate()?;
+
+    let result = density_service::create(&state.db, new_density).await?;
+    Ok(HttpResponse::Created().json(result))
+}
+pub async fn update(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<DensityInputDto>
+)-> Result<HttpResponse, Error>{
+    auth.require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
+    let updated_density = input.into_inner();
+    updated_density.validate()?;
+
+    let result = density_service::update(&state.db, path.into_inner(), updated_density).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+pub async fn delete(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+)-> Result<HttpResponse, Error>{
+    auth.require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
+    density_service::delete(&state.db, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
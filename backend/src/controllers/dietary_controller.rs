@@ -0,0 +1,60 @@
+use crate::app::state::AppState;
+use crate::domain::user::{AuthenticatedUser, Role};
+use crate::dto::dietary_dto::DietaryRuleInput;
+use crate::errors::Error;
+use crate::services::dietary_service;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{web, HttpResponse};
+use uuid::Uuid;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+
+    cfg.service(
+        web::scope("/dietary")
+            .route("/rules", web::get().to(list_rules))
+            .route("/rules", web::post().to(create_rule))
+            .route("/rules/seed", web::post().to(seed_rules))
+            .route("/rules/{id}", web::delete().to(delete_rule))
+            .route("/refresh", web::post().to(refresh))
+    );
+}
+pub async fn list_rules(
+    state: Data<AppState>,
+) -> Result<HttpResponse, Error> {
+    let rules = dietary_service::get_rules(&state.db).await?;
+    Ok(HttpResponse::Ok().json(rules))
+}
+pub async fn create_rule(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    input: Json<DietaryRuleInput>,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin, Role::Superuser])?;
+    let rule = dietary_service::create_rule(&state.db, input.into_inner()).await?;
+    Ok(HttpResponse::Created().json(rule))
+}
+pub async fn delete_rule(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin, Role::Superuser])?;
+    dietary_service::delete_rule(&state.db, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+pub async fn seed_rules(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin, Role::Superuser])?;
+    let result = dietary_service::seed_rules(&state.db, &state.dict_db).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+pub async fn refresh(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin, Role::Superuser])?;
+    let result = dietary_service::refresh(&state.db, &state.dict_db).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
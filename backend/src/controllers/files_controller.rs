@@ -1,11 +1,23 @@
-use actix_files::Files;
-use actix_web::web;
-use std::env;
+use crate::app::state::AppState;
+use crate::domain::user::AuthenticatedUser;
+use crate::errors::Error;
+use crate::services::recipe_service;
+use crate::utils::file_upload::asset_root;
+use actix_files::NamedFile;
+use actix_web::web::{Data, Path};
+use actix_web::{web, HttpRequest, HttpResponse};
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    let file_url = env::var("FILE_URL").unwrap_or_else(|_| "./assets".to_string());
-    println!("Static files being served from: {}", file_url);
-    cfg.service(
-        Files::new("/assets", file_url).show_files_listing()
-    );
-}
\ No newline at end of file
+    println!("Static files being served from: {}", asset_root().display());
+    cfg.route("/assets/{path:.*}", web::get().to(get_file));
+}
+
+pub async fn get_file(
+    state: Data<AppState>,
+    req: HttpRequest,
+    path: Path<String>,
+    auth: Option<AuthenticatedUser>,
+) -> Result<HttpResponse, Error> {
+    let file = recipe_service::authorize_asset(&state.db, &path, auth.as_ref()).await?;
+    Ok(NamedFile::open(file)?.into_response(&req))
+}
@@ -0,0 +1,147 @@
+use crate::app::state::AppState;
+use crate::domain::user::AuthenticatedUser;
+use crate::dto::household_dto::{HouseholdInput, HouseholdInvitationInput, HouseholdMemberInput, HouseholdRecipeInput};
+use crate::errors::Error;
+use crate::services::household_service;
+use crate::utils::header_extractor::extract_language;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::ops::Deref;
+use uuid::Uuid;
+use validator::Validate;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+
+    cfg.service(
+        web::scope("/households")
+            .route("", web::get().to(list))
+            .route("", web::post().to(create))
+            .route("/invitations/{token}/accept", web::post().to(accept_invitation))
+            .route("/{id}", web::get().to(get))
+            .route("/{id}", web::put().to(rename))
+            .route("/{id}", web::delete().to(delete))
+            .route("/{id}/invitations", web::post().to(invite))
+            .route("/{id}/invitations/{invitation_id}", web::delete().to(revoke_invitation))
+            .route("/{id}/members/{user_id}", web::put().to(set_member_role))
+            .route("/{id}/members/{user_id}", web::delete().to(remove_member))
+            .route("/{id}/recipes", web::get().to(get_recipes))
+            .route("/{id}/recipes", web::post().to(add_recipe))
+            .route("/{id}/recipes/{recipe_id}", web::delete().to(remove_recipe))
+    );
+}
+pub async fn list(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    let households = household_service::get_all(&state.db, auth.user.id).await?;
+    Ok(HttpResponse::Ok().json(households))
+}
+pub async fn create(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    input: Json<HouseholdInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let household = household_service::create(&state.db, auth.user.id, input.into_inner()).await?;
+    Ok(HttpResponse::Created().json(household))
+}
+pub async fn get(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    let household = household_service::get(&state.db, auth.user.id, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(household))
+}
+pub async fn rename(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<HouseholdInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let household = household_service::rename(&state.db, auth.user.id, path.into_inner(), input.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(household))
+}
+pub async fn delete(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    household_service::delete(&state.db, auth.user.id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+pub async fn invite(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<HouseholdInvitationInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let invitation = household_service::invite(&state.db, &auth.user, path.into_inner(), input.into_inner()).await?;
+    Ok(HttpResponse::Created().json(invitation))
+}
+pub async fn revoke_invitation(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, Error> {
+    let (id, invitation_id) = path.into_inner();
+    household_service::revoke_invitation(&state.db, auth.user.id, id, invitation_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+pub async fn accept_invitation(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    let household = household_service::accept_invitation(&state.db, &auth.user, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(household))
+}
+pub async fn set_member_role(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<(Uuid, Uuid)>,
+    input: Json<HouseholdMemberInput>,
+) -> Result<HttpResponse, Error> {
+    let (id, user_id) = path.into_inner();
+    let household = household_service::set_member_role(&state.db, auth.user.id, id, user_id, input.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(household))
+}
+pub async fn remove_member(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, Error> {
+    let (id, user_id) = path.into_inner();
+    household_service::remove_member(&state.db, auth.user.id, id, user_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+pub async fn get_recipes(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let recipes = household_service::get_recipes(&state.db, auth.user.id, path.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(recipes))
+}
+pub async fn add_recipe(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<HouseholdRecipeInput>,
+) -> Result<HttpResponse, Error> {
+    household_service::add_recipe(&state.db, &auth, path.into_inner(), input.recipe_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+pub async fn remove_recipe(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, Error> {
+    let (id, recipe_id) = path.into_inner();
+    household_service::remove_recipe(&state.db, &auth, id, recipe_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
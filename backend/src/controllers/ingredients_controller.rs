@@ -1,9 +1,14 @@
 use std::ops::Deref;
 use actix_web::{web, HttpRequest, HttpResponse};
+use actix_web::web::{Json, Path};
+use uuid::Uuid;
+use validator::Validate;
 use crate::app::state::AppState;
-use crate::dto::ingredient_dto::{IngredientList, IngredientViewDto};
+use crate::domain::user::{AuthenticatedUser, Role};
+use crate::dto::ingredient_dto::{CanonicalIngredientAisleInput, CanonicalIngredientDto, IngredientList, MergeCanonicalIngredientsInput};
 use crate::errors::Error;
-use crate::services::{ingredient_service};
+use crate::dto::nutrition_dto::FoodMappingInput;
+use crate::services::{ingredient_service, nutrition_service};
 use crate::utils::header_extractor::extract_language;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -11,6 +16,13 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/ingredient")
             .route("/list", web::get().to(list))
+            .route("/catalog/seed", web::post().to(seed_catalog))
+            .route("/catalog/{id}", web::get().to(get))
+            .route("/catalog/{id}/merge", web::post().to(merge))
+            .route("/catalog/{id}/aisle", web::put().to(set_aisle))
+            .route("/catalog/{id}/food", web::get().to(get_food))
+            .route("/catalog/{id}/food", web::put().to(set_food))
+            .route("/catalog/{id}/food", web::delete().to(delete_food))
 
     );
 }
@@ -21,6 +33,82 @@ pub async fn list(
     req: HttpRequest,
 ) -> Result<HttpResponse, Error> {
     let lang_code = extract_language(&req);
-    let ingredients: Vec<IngredientViewDto> = ingredient_service::get_all(&state.db, query.search.clone(), query.limit, lang_code.deref()).await?;
+    let ingredients: Vec<CanonicalIngredientDto> = ingredient_service::get_all(&state.db, query.search.clone(), query.limit, lang_code.deref()).await?;
     Ok(HttpResponse::Created().json(ingredients))
-}
\ No newline at end of file
+}
+
+pub async fn get(
+    state: web::Data<AppState>,
+    path: Path<Uuid>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let ingredient = ingredient_service::get(&state.db, path.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(ingredient))
+}
+
+pub async fn seed_catalog(
+    state: web::Data<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin, Role::Superuser])?;
+    let result = ingredient_service::seed_catalog(&state.db, &state.dict_db).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+pub async fn merge(
+    state: web::Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<MergeCanonicalIngredientsInput>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin, Role::Superuser])?;
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let ingredient = ingredient_service::merge(&state.db, &state.dict_db, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(ingredient))
+}
+
+pub async fn set_aisle(
+    state: web::Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<CanonicalIngredientAisleInput>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin, Role::Superuser])?;
+    let lang_code = extract_language(&req);
+    let ingredient = ingredient_service::set_aisle(&state.db, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(ingredient))
+}
+
+pub async fn get_food(
+    state: web::Data<AppState>,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    let mapping = nutrition_service::get_mapping(&state.db, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(mapping))
+}
+
+pub async fn set_food(
+    state: web::Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<FoodMappingInput>,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin, Role::Superuser])?;
+    input.validate()?;
+    let mapping = nutrition_service::save_mapping(&state.db, path.into_inner(), input.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(mapping))
+}
+
+pub async fn delete_food(
+    state: web::Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin, Role::Superuser])?;
+    nutrition_service::delete_mapping(&state.db, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,127 @@
+use crate::app::state::AppState;
+use crate::domain::user::AuthenticatedUser;
+use crate::dto::meal_plan_dto::{MealPlanCopyWeekInput, MealPlanInput, MealPlanMoveInput, MealPlanWeekQuery};
+use crate::errors::Error;
+use crate::services::meal_plan_service;
+use crate::utils::header_extractor::extract_language;
+use actix_web::web::{Data, Json, Path, Query};
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::ops::Deref;
+use uuid::Uuid;
+use validator::Validate;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+
+    cfg.service(
+        web::scope("/meal-plans")
+            .route("", web::get().to(get_week))
+            .route("", web::post().to(create))
+            .route("/copy-week", web::post().to(copy_week))
+            .route("/feed", web::post().to(create_feed))
+            .route("/feed", web::delete().to(delete_feed))
+            .route("/feed/{token}.ics", web::get().to(feed))
+            .route("/{id}", web::put().to(update))
+            .route("/{id}", web::delete().to(delete))
+            .route("/{id}/move", web::post().to(move_plan))
+            .route("/{id}/copy", web::post().to(copy_plan))
+    );
+}
+pub async fn get_week(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    query: Query<MealPlanWeekQuery>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let week = meal_plan_service::get_week(&state.db, auth.user.id, query.week, lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(week))
+}
+pub async fn create(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    input: Json<MealPlanInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let plan = meal_plan_service::create(&state.db, auth.user.id, input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Created().json(plan))
+}
+pub async fn update(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<MealPlanInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let plan = meal_plan_service::update(&state.db, auth.user.id, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(plan))
+}
+pub async fn delete(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    meal_plan_service::delete(&state.db, auth.user.id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+pub async fn move_plan(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<MealPlanMoveInput>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let plan = meal_plan_service::move_plan(&state.db, auth.user.id, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(plan))
+}
+pub async fn copy_plan(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<MealPlanMoveInput>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let plan = meal_plan_service::copy_plan(&state.db, auth.user.id, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Created().json(plan))
+}
+pub async fn copy_week(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    input: Json<MealPlanCopyWeekInput>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let week = meal_plan_service::copy_week(&state.db, auth.user.id, input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(week))
+}
+pub async fn create_feed(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    let feed = meal_plan_service::create_feed(&state.db, auth.user.id).await?;
+    Ok(HttpResponse::Ok().json(feed))
+}
+pub async fn delete_feed(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    meal_plan_service::delete_feed(&state.db, auth.user.id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+/// Calendar apps cannot log in, so the feed is reached through its secret token.
+pub async fn feed(
+    state: Data<AppState>,
+    req: HttpRequest,
+    path: Path<String>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let calendar = meal_plan_service::get_feed(&state.db, &path.into_inner(), &state.config.frontend_origin, lang_code.deref()).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .body(calendar))
+}
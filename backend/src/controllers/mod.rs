@@ -7,7 +7,15 @@ pub mod ingredients_controller;
 pub mod tags_controller;
 pub mod languages_controller;
 pub mod unit_controller;
+pub mod density_controller;
 pub mod ocr_controller;
 pub mod system_controller;
 pub mod notification_controller;
-pub mod studio_controller;
\ No newline at end of file
+pub mod studio_controller;
+pub mod nutrition_controller;
+pub mod dietary_controller;
+pub mod shopping_list_controller;
+pub mod meal_plan_controller;
+pub mod pantry_controller;
+pub mod collection_controller;
+pub mod household_controller;
\ No newline at end of file
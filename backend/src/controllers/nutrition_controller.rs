@@ -0,0 +1,40 @@
+use crate::app::state::AppState;
+use crate::domain::user::{AuthenticatedUser, Role};
+use crate::dto::nutrition_dto::{FoodImportForm, FoodImportQuery, FoodSearchQuery};
+use crate::errors::Error;
+use crate::services::nutrition_service;
+use actix_multipart::form::MultipartForm;
+use actix_web::web::{Data, Query};
+use actix_web::{web, HttpResponse};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+
+    cfg.service(
+        web::scope("/nutrition")
+            .route("/foods", web::get().to(search_foods))
+            .route("/foods/import", web::post().to(import_foods))
+    );
+}
+pub async fn search_foods(
+    state: Data<AppState>,
+    query: Query<FoodSearchQuery>,
+) -> Result<HttpResponse, Error> {
+    let query = query.into_inner();
+    let foods = nutrition_service::search_foods(&state.db, query.search, query.limit).await?;
+    Ok(HttpResponse::Ok().json(foods))
+}
+pub async fn import_foods(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    query: Query<FoodImportQuery>,
+    MultipartForm(form): MultipartForm<FoodImportForm>,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin, Role::Superuser])?;
+    let report = nutrition_service::import_foods(
+        &state.db,
+        form.file.file.path(),
+        query.format.unwrap_or_default(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(report))
+}
@@ -0,0 +1,93 @@
+use crate::app::state::AppState;
+use crate::domain::user::{AuthenticatedUser, Role};
+use crate::dto::pantry_dto::{PantryExpiringQuery, PantryItemInput, PantryItemsQuery};
+use crate::errors::Error;
+use crate::services::pantry_service;
+use crate::utils::header_extractor::extract_language;
+use actix_web::web::{Data, Json, Path, Query};
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::ops::Deref;
+use uuid::Uuid;
+use validator::Validate;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+
+    cfg.service(
+        web::scope("/pantry")
+            .route("", web::get().to(list))
+            .route("", web::post().to(create))
+            .route("/expiring", web::get().to(expiring))
+            .route("/suggestions", web::get().to(suggestions))
+            .route("/reminders", web::post().to(send_reminders))
+            .route("/{id}", web::put().to(update))
+            .route("/{id}", web::delete().to(delete))
+    );
+}
+pub async fn list(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    query: Query<PantryItemsQuery>,
+) -> Result<HttpResponse, Error> {
+    let items = pantry_service::get_items(&state.db, auth.user.id, query.location).await?;
+    Ok(HttpResponse::Ok().json(items))
+}
+pub async fn create(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    input: Json<PantryItemInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let item = pantry_service::create_item(&state.db, auth.user.id, input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Created().json(item))
+}
+pub async fn update(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<PantryItemInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let item = pantry_service::update_item(&state.db, auth.user.id, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(item))
+}
+pub async fn delete(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    pantry_service::delete_item(&state.db, auth.user.id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+pub async fn expiring(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    query: Query<PantryExpiringQuery>,
+) -> Result<HttpResponse, Error> {
+    query.validate()?;
+    let items = pantry_service::get_expiring(&state.db, auth.user.id, query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(items))
+}
+pub async fn suggestions(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    query: Query<PantryExpiringQuery>,
+) -> Result<HttpResponse, Error> {
+    query.validate()?;
+    let lang_code = extract_language(&req);
+    let suggestions = pantry_service::suggest_for_expiring(&state.db, &state.dict_db, auth.user.id, query.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(suggestions))
+}
+/// Sends the expiry reminders now instead of waiting for the hourly run.
+pub async fn send_reminders(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin, Role::Superuser])?;
+    let result = pantry_service::send_expiry_reminders(&state).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
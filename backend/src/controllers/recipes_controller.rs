@@ -1,9 +1,19 @@
 use crate::app::state::AppState;
 use crate::domain::user::{AuthenticatedUser, Role};
+use crate::dto::archive_dto::{ArchiveExportQuery, ArchiveImportForm, ArchiveImportQuery};
+use crate::dto::collaborator_dto::{RecipeCollaboratorInput, RecipeCollaboratorRoleInput};
 use crate::dto::comment_dto::{CommentDto, CreateCommentDto};
-use crate::dto::recipe_dto::{CreateRecipeInput, EditRecipeInput, GetRecipeQuery, LastRecipesQuery, RecipeFilter, RecipeFilterByPage, RecipePagination, RecipeResponse, RecipeViewDto};
+use crate::dto::dietary_dto::DietaryOverridesInput;
+use crate::dto::facet_dto::RecipeFacetsDto;
+use crate::dto::pantry_dto::{PantrySearchInput, PantrySearchResponse};
+use crate::dto::pdf_dto::{CookbookPdfInput, RecipePdfQuery};
+use crate::dto::recipe_dto::{CreateRecipeInput, EditRecipeInput, GetRecipeQuery, LastRecipesQuery, RecipeFilter, RecipeFormatQuery, RecipeFilterByPage, RecipeResponse, RecipeShareQuery, RecipeViewDto};
 use crate::dto::recipe_rating_dto::RecipeRatingDto;
+use crate::dto::recipe_version_dto::RecipeVersionDiffQuery;
+use crate::dto::share_link_dto::ShareLinkInput;
 use crate::errors::Error;
+use actix_files::NamedFile;
+use actix_multipart::form::MultipartForm;
 use actix_web::web::{Json, Path};
 use actix_web::web::{Data, Query};
 use actix_web::{web, HttpRequest, HttpResponse};
@@ -11,7 +21,7 @@ use std::ops::Deref;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::services::{recipe_service, user_service};
+use crate::services::{archive_service, collaborator_service, cooklang_service, dietary_service, json_ld_service, pantry_service, pdf_service, recipe_service, share_link_service, user_service};
 use crate::utils::header_extractor::extract_language;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -21,17 +31,39 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route("", web::get().to(list))
             .route("/last", web::get().to(get_last))
             .route("/by_page", web::get().to(get_by_page))
+            .route("/facets", web::get().to(facets))
             .route("", web::post().to(create))
+            .route("/import/json-ld", web::post().to(import_json_ld))
+            .route("/import/cooklang", web::post().to(import_cooklang))
+            .route("/import/archive", web::post().to(import_archive))
+            .route("/export/archive", web::get().to(export_archive))
+            .route("/export/pdf", web::post().to(export_cookbook_pdf))
+            .route("/pantry", web::post().to(find_by_pantry))
             .route("/favorites/{id}", web::get().to(get_favorites))
+            .route("/shared/{token}/assets/{path:.*}", web::get().to(get_shared_image))
             .route("/comment/{id}", web::delete().to(delete_comment))
             .route("/comment/{id}", web::put().to(edit_comment))
             .route("/author/{id}", web::get().to(get_by_author))
             .route("/{id}", web::get().to(get))
             .route("/{id}", web::put().to(update))
             .route("/{id}", web::delete().to(delete))
+            .route("/{id}/json-ld", web::get().to(export_json_ld))
+            .route("/{id}/cooklang", web::get().to(export_cooklang))
+            .route("/{id}/pdf", web::get().to(export_pdf))
             .route("/{id}/analytics", web::get().to(analytics))
+            .route("/{id}/labels", web::get().to(get_labels))
+            .route("/{id}/labels", web::put().to(set_labels))
             .route("/{id}/views", web::post().to(track_view))
             .route("/{id}/favorite", web::post().to(favorite))
+            .route("/{id}/fork", web::post().to(fork))
+            .route("/{id}/forks", web::get().to(get_forks))
+            .route("/{id}/share-links", web::get().to(get_share_links))
+            .route("/{id}/share-links", web::post().to(create_share_link))
+            .route("/{id}/share-links/{link_id}", web::delete().to(revoke_share_link))
+            .route("/{id}/collaborators", web::get().to(get_collaborators))
+            .route("/{id}/collaborators", web::post().to(add_collaborator))
+            .route("/{id}/collaborators/{user_id}", web::put().to(set_collaborator_role))
+            .route("/{id}/collaborators/{user_id}", web::delete().to(remove_collaborator))
             .route("/{id}/rating", web::post().to(rate))
             .route("/{id}/rating", web::delete().to(unrate))
             .route("/{id}/rating", web::get().to(get_rating))
@@ -39,6 +71,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route("/{id}/comments", web::post().to(add_comment))
             .route("/{recipe_id}/versions/", web::get().to(get_versions))
             .route("/{recipe_id}/versions/{version_id}", web::get().to(get_version))
+            .route("/{recipe_id}/versions/{version_id}/diff", web::get().to(diff_version))
             .route("/{recipe_id}/versions/{version_id}/restore", web::post().to(restore_version))
     );
 }
@@ -57,21 +90,50 @@ pub async fn list(
     query: Query<RecipeFilter>,
     auth: Option<AuthenticatedUser>,
 ) -> Result<HttpResponse, Error> {
-    if query.scope && auth.is_some() {
-        auth.unwrap().require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
+    if query.scope {
+        recipe_service::authorize_scope(auth.as_ref())?;
     }
     let lang_code = extract_language(&req);
+    let viewer_id = auth.map(|a| a.user.id);
 
-    let recipes = recipe_service::get_all(&state.db, lang_code.deref(), query.into_inner()).await?;
+    let recipes = recipe_service::get_all(&state.db, lang_code.deref(), query.into_inner(), viewer_id).await?;
 
     Ok(HttpResponse::Ok().json(recipes))
 }
+/// Counts per tag, time and step bucket and language for the recipes matching the filters
+#[utoipa::path(
+    get,
+    path = "/recipes/facets",
+    responses(
+        (status = 200, description = "Facet counts of the filtered recipes", body = RecipeFacetsDto)
+    )
+)]
+pub async fn facets(
+    state: Data<AppState>,
+    req: HttpRequest,
+    query: Query<RecipeFilter>,
+    auth: Option<AuthenticatedUser>,
+) -> Result<HttpResponse, Error> {
+    if query.scope {
+        recipe_service::authorize_scope(auth.as_ref())?;
+    }
+    let lang_code = extract_language(&req);
+    let viewer_id = auth.map(|a| a.user.id);
+
+    let facets = recipe_service::get_facets(&state.db, lang_code.deref(), query.into_inner(), viewer_id).await?;
+
+    Ok(HttpResponse::Ok().json(facets))
+}
 /// Get one recipe by ID
 #[utoipa::path(
     get,
     path = "/recipes/{id}",
     params(
-        ("id" = Uuid, Path, description = "Recipe ID")
+        ("id" = Uuid, Path, description = "Recipe ID"),
+        ("include_translations" = Option<bool>, Query, description = "Whether to include all translations"),
+        ("servings" = Option<i32>, Query, description = "Scale ingredient quantities to this number of servings"),
+        ("unit_system" = Option<String>, Query, description = "Render quantities in metric or imperial units, defaults to the user preference"),
+        ("share" = Option<String>, Query, description = "Token of a share link, to read a private recipe without an account")
     ),
     responses(
         (status = 200, description = "Get recipe by ID", body = RecipeViewDto),
@@ -82,16 +144,35 @@ pub async fn get(
     state:Data<AppState>,
     id: Path<Uuid>,
     query: Query<GetRecipeQuery>,
-    req: HttpRequest
+    req: HttpRequest,
+    auth: Option<AuthenticatedUser>,
 ) -> Result<HttpResponse, Error> {
 
     let include_translations = query.include_translations;
 
     let lang_code = extract_language(&req);
 
-    let recipe = recipe_service::get_by_id(&state.db, id.into_inner(),lang_code.deref(),include_translations.unwrap_or_else(|| false)).await?;
+    let recipe_id = id.into_inner();
+    let shared = recipe_service::authorize_read(
+        &state.db,
+        recipe_id,
+        auth.as_ref(),
+        query.share.as_deref(),
+        include_translations.unwrap_or(false),
+    )
+    .await?;
+
+    let recipe = recipe_service::get_by_id(&state.db, recipe_id,lang_code.deref(),include_translations.unwrap_or_else(|| false)).await?;
     match recipe {
         RecipeResponse::View(view_data) => {
+            let unit_system = query
+                .unit_system
+                .or_else(|| auth.and_then(|a| a.user.preferences.unit_system));
+            let mut view_data = recipe_service::adjust_view(&state.db, view_data, query.servings, unit_system).await?;
+            if shared && let Some(token) = &query.share {
+                share_link_service::record_view(&state.db, recipe_id, token).await?;
+                share_link_service::share_images(&mut view_data, token);
+            }
             Ok(HttpResponse::Ok().json(view_data))
         }
         RecipeResponse::Editor(view_data) => {
@@ -140,24 +221,18 @@ pub async fn get_by_page(
     query: Query<RecipeFilterByPage>,
     auth: Option<AuthenticatedUser>,
 ) -> Result<HttpResponse, Error> {
-    if let Some(filters) = &query.filters{
-        if filters.scope && auth.is_some() {
-            auth.unwrap().require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
-        }
+    if let Some(filters) = &query.filters
+        && filters.scope
+    {
+        recipe_service::authorize_scope(auth.as_ref())?;
     }
 
     let lang_code = extract_language(&req);
-    let page = query.page.unwrap_or(1);
-    let per_page = query.per_page.unwrap_or(10);
+    let viewer_id = auth.map(|a| a.user.id);
 
-    let recipes = recipe_service::get_all_by_page(&state.db, lang_code.deref(), query.into_inner().clone()).await?;
+    let recipes = recipe_service::get_all_by_page(&state.db, lang_code.deref(), query.into_inner(), viewer_id).await?;
 
-    Ok(HttpResponse::Ok().json(RecipePagination {
-        data: recipes.clone(),
-        total: recipes.len() as i32,
-        page,
-        per_page,
-    }))
+    Ok(HttpResponse::Ok().json(recipes))
 }
 pub async fn get_by_author(
     state: Data<AppState>,
@@ -185,15 +260,17 @@ pub async fn create(
     auth.require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
     let new_recipe = input.into_inner();
     let lang_code = extract_language(&req);
-    let res: RecipeViewDto = recipe_service::create(&state.db, new_recipe, lang_code.deref()).await?;
+    let res: RecipeViewDto = recipe_service::create(&state.db, &state.dict_db, new_recipe, lang_code.deref()).await?;
 
     Ok(HttpResponse::Ok().json(res))
 }
 pub async fn get_favorites(
     state: Data<AppState>,
+    auth: AuthenticatedUser,
     path: Path<Uuid>,
 ) -> Result<HttpResponse, Error> {
     let user_id = path.into_inner();
+    auth.require_owner_or_roles(user_id, &[Role::Admin, Role::Superuser])?;
     let favorites = user_service::get_favorites(&state.db, user_id).await?;
 
     Ok(HttpResponse::Ok().json(favorites))
@@ -205,17 +282,16 @@ pub async fn update(
     input: Json<EditRecipeInput>,
     path: Path<Uuid>,
 ) -> Result<HttpResponse, Error> {
-    auth.require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
+    let recipe_id = path.into_inner();
+    let edited_as = recipe_service::authorize_edit(&state.db, recipe_id, &auth).await?;
 
     let updated_recipe = input.into_inner();
 
     updated_recipe.validate()?;
 
-    let recipe_id = path.into_inner();
-
     let lang_code = extract_language(&req);
 
-    let result = recipe_service::update(&state.db, updated_recipe, recipe_id, lang_code.deref(), auth.user).await?;
+    let result = recipe_service::update(&state.db, &state.dict_db, updated_recipe, recipe_id, lang_code.deref(), &auth, edited_as).await?;
 
     Ok(HttpResponse::Ok().json(result))
 }
@@ -224,8 +300,8 @@ pub async fn delete(
     path: Path<Uuid>,
     auth: AuthenticatedUser,
 ) -> Result<HttpResponse, Error> {
-    auth.require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
     let recipe_id = path.into_inner();
+    recipe_service::authorize_owner(&state.db, recipe_id, &auth).await?;
     if !recipe_service::delete(&state.db, recipe_id).await?{
         return Ok(HttpResponse::NotFound().finish());
     }
@@ -239,6 +315,26 @@ pub async fn analytics(
     let count = recipe_service::analytics(&state.db, recipe_id).await?;
     Ok(HttpResponse::Ok().json(count))
 }
+pub async fn get_labels(
+    state: Data<AppState>,
+    path: Path<Uuid>,
+    query: Query<RecipeShareQuery>,
+    auth: Option<AuthenticatedUser>,
+) -> Result<HttpResponse, Error> {
+    let recipe_id = path.into_inner();
+    recipe_service::authorize_read(&state.db, recipe_id, auth.as_ref(), query.share.as_deref(), false).await?;
+    let labels = dietary_service::get_labels(&state.db, recipe_id).await?;
+    Ok(HttpResponse::Ok().json(labels))
+}
+pub async fn set_labels(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<DietaryOverridesInput>,
+) -> Result<HttpResponse, Error> {
+    let labels = dietary_service::set_overrides(&state.db, &auth, path.into_inner(), input.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(labels))
+}
 pub async fn track_view(
     state: Data<AppState>,
     path: Path<Uuid>,
@@ -260,6 +356,102 @@ pub async fn favorite(
     let favorited = recipe_service::toggle_favorite(&state, recipe_id, user_id).await?;
     Ok(HttpResponse::Ok().json(favorited))
 }
+pub async fn fork(
+    state: Data<AppState>,
+    req: HttpRequest,
+    path: Path<Uuid>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let fork = recipe_service::fork(&state, path.into_inner(), auth.user, lang_code.deref()).await?;
+    Ok(HttpResponse::Created().json(fork))
+}
+pub async fn get_forks(
+    state: Data<AppState>,
+    req: HttpRequest,
+    path: Path<Uuid>,
+    auth: Option<AuthenticatedUser>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let viewer_id = auth.map(|a| a.user.id);
+    let forks = recipe_service::get_forks(&state.db, path.into_inner(), viewer_id, lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(forks))
+}
+pub async fn get_share_links(
+    state: Data<AppState>,
+    path: Path<Uuid>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    let links = share_link_service::get_links(&state.db, &auth, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(links))
+}
+pub async fn create_share_link(
+    state: Data<AppState>,
+    path: Path<Uuid>,
+    auth: AuthenticatedUser,
+    input: Json<ShareLinkInput>,
+) -> Result<HttpResponse, Error> {
+    let input = input.into_inner();
+    input.validate()?;
+    let link = share_link_service::create_link(&state.db, &auth, path.into_inner(), input).await?;
+    Ok(HttpResponse::Created().json(link))
+}
+pub async fn revoke_share_link(
+    state: Data<AppState>,
+    path: Path<(Uuid, Uuid)>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    let (recipe_id, link_id) = path.into_inner();
+    let link = share_link_service::revoke_link(&state.db, &auth, recipe_id, link_id).await?;
+    Ok(HttpResponse::Ok().json(link))
+}
+pub async fn get_collaborators(
+    state: Data<AppState>,
+    path: Path<Uuid>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    let collaborators = collaborator_service::get_collaborators(&state.db, &auth, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(collaborators))
+}
+pub async fn add_collaborator(
+    state: Data<AppState>,
+    path: Path<Uuid>,
+    auth: AuthenticatedUser,
+    input: Json<RecipeCollaboratorInput>,
+) -> Result<HttpResponse, Error> {
+    let input = input.into_inner();
+    input.validate()?;
+    let collaborators = collaborator_service::add_collaborator(&state.db, &auth, path.into_inner(), input).await?;
+    Ok(HttpResponse::Ok().json(collaborators))
+}
+pub async fn set_collaborator_role(
+    state: Data<AppState>,
+    path: Path<(Uuid, Uuid)>,
+    auth: AuthenticatedUser,
+    input: Json<RecipeCollaboratorRoleInput>,
+) -> Result<HttpResponse, Error> {
+    let (recipe_id, user_id) = path.into_inner();
+    let collaborators = collaborator_service::set_role(&state.db, &auth, recipe_id, user_id, input.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(collaborators))
+}
+pub async fn remove_collaborator(
+    state: Data<AppState>,
+    path: Path<(Uuid, Uuid)>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    let (recipe_id, user_id) = path.into_inner();
+    collaborator_service::remove_collaborator(&state.db, &auth, recipe_id, user_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+pub async fn get_shared_image(
+    state: Data<AppState>,
+    req: HttpRequest,
+    path: Path<(String, String)>,
+) -> Result<HttpResponse, Error> {
+    let (token, image_path) = path.into_inner();
+    let file = share_link_service::find_image(&state.db, &token, &image_path).await?;
+    Ok(NamedFile::open(file)?.into_response(&req))
+}
 pub async fn rate(
     state: Data<AppState>,
     path: Path<Uuid>,
@@ -338,7 +530,7 @@ pub async fn get_versions(
     auth: AuthenticatedUser,
 ) -> Result<HttpResponse, Error> {
     let recipe_id = path.into_inner();
-    auth.require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
+    recipe_service::authorize_edit(&state.db, recipe_id, &auth).await?;
     let versions = recipe_service::get_versions(&state.db, recipe_id).await?;
     Ok(HttpResponse::Ok().json(versions))
 }
@@ -348,15 +540,197 @@ pub async fn get_version(
     auth: AuthenticatedUser,
 ) -> Result<HttpResponse, Error> {
     let (recipe_id, version_id) = path.into_inner();
-    auth.require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
+    recipe_service::authorize_edit(&state.db, recipe_id, &auth).await?;
     let version = recipe_service::get_version(&state.db, recipe_id,version_id).await?;
     Ok(HttpResponse::Ok().json(version))
 }
+pub async fn diff_version(
+    state: Data<AppState>,
+    req: HttpRequest,
+    path: Path<(Uuid, Uuid)>,
+    query: Query<RecipeVersionDiffQuery>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    let (recipe_id, version_id) = path.into_inner();
+    recipe_service::authorize_edit(&state.db, recipe_id, &auth).await?;
+    let lang_code = extract_language(&req);
+    let diff = recipe_service::diff_versions(&state.db, recipe_id, version_id, query.compare_to, lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(diff))
+}
 pub async fn restore_version(
+    state: Data<AppState>,
+    req: HttpRequest,
     path: Path<(Uuid, Uuid)>,
     auth: AuthenticatedUser,
 ) -> Result<HttpResponse, Error> {
     let (recipe_id, version_id) = path.into_inner();
+    let edited_as = recipe_service::authorize_edit(&state.db, recipe_id, &auth).await?;
+    let lang_code = extract_language(&req);
+    let recipe = recipe_service::restore_version(&state.db, &state.dict_db, recipe_id, version_id, lang_code.deref(), &auth, edited_as).await?;
+    Ok(HttpResponse::Ok().json(recipe))
+}
+pub async fn export_json_ld(
+    state: Data<AppState>,
+    req: HttpRequest,
+    path: Path<Uuid>,
+    query: Query<RecipeFormatQuery>,
+    auth: Option<AuthenticatedUser>,
+) -> Result<HttpResponse, Error> {
+    let recipe_id = path.into_inner();
+    recipe_service::authorize_read(&state.db, recipe_id, auth.as_ref(), query.share.as_deref(), false).await?;
+    let lang_code = query.lang.clone().unwrap_or_else(|| extract_language(&req));
+    let document = json_ld_service::export(&state.db, recipe_id, lang_code.deref(), &state.config.frontend_origin).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/ld+json")
+        .json(document))
+}
+pub async fn import_json_ld(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    query: Query<RecipeFormatQuery>,
+    input: Json<serde_json::Value>,
+) -> Result<HttpResponse, Error> {
     auth.require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
-    Ok(HttpResponse::Ok().json({}))
-}
\ No newline at end of file
+    let lang_code = extract_language(&req);
+    let draft = json_ld_service::import(&state.db, &state.dict_db, input.into_inner(), query.lang.as_deref(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(draft))
+}
+pub async fn export_cooklang(
+    state: Data<AppState>,
+    req: HttpRequest,
+    path: Path<Uuid>,
+    query: Query<RecipeFormatQuery>,
+    auth: Option<AuthenticatedUser>,
+) -> Result<HttpResponse, Error> {
+    let recipe_id = path.into_inner();
+    recipe_service::authorize_read(&state.db, recipe_id, auth.as_ref(), query.share.as_deref(), false).await?;
+    let lang_code = query.lang.clone().unwrap_or_else(|| extract_language(&req));
+    let (file_name, document) = cooklang_service::export(&state.db, recipe_id, lang_code.deref()).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", file_name)))
+        .body(document))
+}
+pub async fn import_cooklang(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    query: Query<RecipeFormatQuery>,
+    body: String,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
+    let lang_code = extract_language(&req);
+    let draft = cooklang_service::import(&state.db, &body, query.lang.as_deref(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(draft))
+}
+pub async fn export_archive(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    query: Query<ArchiveExportQuery>,
+) -> Result<HttpResponse, Error> {
+    let (file_name, archive) = archive_service::export(&state.db, auth.user.id, query.include_versions.unwrap_or(false)).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/zip")
+        .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", file_name)))
+        .body(archive))
+}
+pub async fn import_archive(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    query: Query<ArchiveImportQuery>,
+    MultipartForm(form): MultipartForm<ArchiveImportForm>,
+) -> Result<HttpResponse, Error> {
+    auth.require_roles(&[Role::Admin,Role::Moderator,Role::Superuser])?;
+    let lang_code = extract_language(&req);
+    let report = archive_service::import(
+        &state.db,
+        &state.dict_db,
+        form.archive.file.path(),
+        auth.user.id,
+        query.skip_duplicates.unwrap_or(false),
+        lang_code.deref(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+pub async fn export_pdf(
+    state: Data<AppState>,
+    req: HttpRequest,
+    path: Path<Uuid>,
+    query: Query<RecipePdfQuery>,
+    auth: Option<AuthenticatedUser>,
+) -> Result<HttpResponse, Error> {
+    let recipe_id = path.into_inner();
+    let mut query = query.into_inner();
+    recipe_service::authorize_read(&state.db, recipe_id, auth.as_ref(), query.share.as_deref(), false).await?;
+    let lang_code = query.lang.clone().unwrap_or_else(|| extract_language(&req));
+    let user = auth.map(|a| a.user);
+    query.unit_system = query
+        .unit_system
+        .or_else(|| user.as_ref().and_then(|u| u.preferences.unit_system));
+    let (file_name, document) = pdf_service::recipe(
+        &state.db,
+        recipe_id,
+        lang_code.deref(),
+        &query,
+        &state.config.frontend_origin,
+        user.map(|u| u.id),
+    )
+    .await?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header(("Content-Disposition", format!("inline; filename=\"{}\"", file_name)))
+        .body(document))
+}
+pub async fn export_cookbook_pdf(
+    state: Data<AppState>,
+    req: HttpRequest,
+    input: Json<CookbookPdfInput>,
+    auth: Option<AuthenticatedUser>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let mut input = input.into_inner();
+    for recipe_id in &input.recipe_ids {
+        recipe_service::authorize_read(&state.db, *recipe_id, auth.as_ref(), None, false).await?;
+    }
+    let lang_code = input.lang.clone().unwrap_or_else(|| extract_language(&req));
+    let user = auth.map(|a| a.user);
+    input.unit_system = input
+        .unit_system
+        .or_else(|| user.as_ref().and_then(|u| u.preferences.unit_system));
+    let (file_name, document) = pdf_service::cookbook(
+        &state.db,
+        input,
+        lang_code.deref(),
+        &state.config.frontend_origin,
+        user.map(|u| u.id),
+    )
+    .await?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", file_name)))
+        .body(document))
+}
+/// Recipes that can be cooked with the given ingredients, best coverage first
+#[utoipa::path(
+    post,
+    path = "/recipes/pantry",
+    request_body = PantrySearchInput,
+    responses(
+        (status = 200, description = "Recipes ranked by ingredient coverage", body = PantrySearchResponse)
+    )
+)]
+pub async fn find_by_pantry(
+    state: Data<AppState>,
+    req: HttpRequest,
+    input: Json<PantrySearchInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+
+    let results = pantry_service::find_cookable(&state.db, &state.dict_db, input.into_inner(), lang_code.deref()).await?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
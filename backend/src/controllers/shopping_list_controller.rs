@@ -0,0 +1,173 @@
+use crate::app::state::AppState;
+use crate::domain::user::AuthenticatedUser;
+use crate::dto::shopping_list_dto::{
+    ShoppingListInput, ShoppingListItemInput, ShoppingListItemUpdate, ShoppingListMemberInput, ShoppingListRecipeInput,
+};
+use crate::errors::Error;
+use crate::services::shopping_list_service;
+use crate::utils::header_extractor::extract_language;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::ops::Deref;
+use uuid::Uuid;
+use validator::Validate;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+
+    cfg.service(
+        web::scope("/shopping-lists")
+            .route("", web::get().to(list))
+            .route("", web::post().to(create))
+            .route("/{id}", web::get().to(get))
+            .route("/{id}", web::put().to(rename))
+            .route("/{id}", web::delete().to(delete))
+            .route("/{id}/refresh", web::post().to(refresh))
+            .route("/{id}/recipes", web::post().to(add_recipe))
+            .route("/{id}/recipes/{recipe_id}", web::delete().to(remove_recipe))
+            .route("/{id}/items", web::post().to(add_item))
+            .route("/{id}/items/{item_id}", web::put().to(update_item))
+            .route("/{id}/items/{item_id}", web::delete().to(delete_item))
+            .route("/{id}/members", web::post().to(add_member))
+            .route("/{id}/members/{user_id}", web::delete().to(remove_member))
+    );
+}
+pub async fn list(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, Error> {
+    let lists = shopping_list_service::get_all(&state.db, auth.user.id).await?;
+    Ok(HttpResponse::Ok().json(lists))
+}
+pub async fn create(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    input: Json<ShoppingListInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let list = shopping_list_service::create(&state, auth.user.id, input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Created().json(list))
+}
+pub async fn get(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let list = shopping_list_service::get(&state.db, auth.user.id, path.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(list))
+}
+pub async fn rename(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<ShoppingListInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let list = shopping_list_service::rename(&state, auth.user.id, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(list))
+}
+pub async fn delete(
+    state: Data<AppState>,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    shopping_list_service::delete(&state, auth.user.id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+pub async fn refresh(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+) -> Result<HttpResponse, Error> {
+    let lang_code = extract_language(&req);
+    let list = shopping_list_service::refresh(&state, auth.user.id, path.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(list))
+}
+pub async fn add_recipe(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<ShoppingListRecipeInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let list = shopping_list_service::add_recipe(&state, auth.user.id, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(list))
+}
+pub async fn remove_recipe(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, Error> {
+    let (id, recipe_id) = path.into_inner();
+    let lang_code = extract_language(&req);
+    let list = shopping_list_service::remove_recipe(&state, auth.user.id, id, recipe_id, lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(list))
+}
+pub async fn add_item(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<ShoppingListItemInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let list = shopping_list_service::add_item(&state, auth.user.id, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Created().json(list))
+}
+pub async fn update_item(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<(Uuid, Uuid)>,
+    input: Json<ShoppingListItemUpdate>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let (id, item_id) = path.into_inner();
+    let lang_code = extract_language(&req);
+    let list = shopping_list_service::update_item(&state, auth.user.id, id, item_id, input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(list))
+}
+pub async fn delete_item(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, Error> {
+    let (id, item_id) = path.into_inner();
+    let lang_code = extract_language(&req);
+    let list = shopping_list_service::delete_item(&state, auth.user.id, id, item_id, lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(list))
+}
+pub async fn add_member(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<Uuid>,
+    input: Json<ShoppingListMemberInput>,
+) -> Result<HttpResponse, Error> {
+    input.validate()?;
+    let lang_code = extract_language(&req);
+    let list = shopping_list_service::add_member(&state, auth.user.id, path.into_inner(), input.into_inner(), lang_code.deref()).await?;
+    Ok(HttpResponse::Ok().json(list))
+}
+pub async fn remove_member(
+    state: Data<AppState>,
+    req: HttpRequest,
+    auth: AuthenticatedUser,
+    path: Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, Error> {
+    let (id, user_id) = path.into_inner();
+    let lang_code = extract_language(&req);
+    shopping_list_service::remove_member(&state, auth.user.id, id, user_id, lang_code.deref()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,83 @@
+use crate::dto::recipe_dto::RecipeEditorDto;
+use crate::dto::tag_dto::TagDto;
+use crate::dto::unit_dto::UnitDto;
+use actix_multipart::form::MultipartForm;
+use actix_multipart::form::tempfile::TempFile;
+use chrono::{DateTime, FixedOffset, Utc};
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub recipes: Vec<ArchiveManifestEntry>,
+    pub units: Vec<UnitDto>,
+    pub tags: Vec<TagDto>,
+    pub images: Vec<String>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveManifestEntry {
+    pub id: Uuid,
+    pub title: String,
+    pub file: String,
+}
+/// One `recipes/{id}.json` entry: the recipe with all its translations and,
+/// when requested, its version history.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveRecipe {
+    pub recipe: RecipeEditorDto,
+    #[serde(default)]
+    pub versions: Vec<ArchiveVersion>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveVersion {
+    pub id: Uuid,
+    pub data: RecipeEditorDto,
+    pub created_at: DateTime<FixedOffset>,
+}
+#[derive(Debug, Deserialize)]
+pub struct ArchiveExportQuery {
+    pub include_versions: Option<bool>,
+}
+#[derive(Debug, Deserialize)]
+pub struct ArchiveImportQuery {
+    /// Skip recipes whose title already exists instead of importing a second copy.
+    pub skip_duplicates: Option<bool>,
+}
+#[derive(Debug, MultipartForm)]
+pub struct ArchiveImportForm {
+    #[multipart(rename = "archive")]
+    pub archive: TempFile,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveConflictKind {
+    DuplicateTitle,
+    UnknownUnit,
+    MissingImage,
+    InvalidImage,
+    InvalidRecipe,
+    ImportFailed,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveConflictDto {
+    pub kind: ArchiveConflictKind,
+    pub source_id: Option<Uuid>,
+    pub title: Option<String>,
+    pub detail: String,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveImportedDto {
+    pub source_id: Uuid,
+    pub id: Uuid,
+    pub title: String,
+    pub versions: usize,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveImportReport {
+    pub imported: Vec<ArchiveImportedDto>,
+    pub skipped: Vec<Uuid>,
+    pub conflicts: Vec<ArchiveConflictDto>,
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// What a collaborator may do on a recipe besides its author. Both edit it, co-authors
+/// also choose who else does.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecipeCollaboratorRole {
+    CoAuthor,
+    Editor,
+}
+impl RecipeCollaboratorRole {
+    pub const ALL: [RecipeCollaboratorRole; 2] = [RecipeCollaboratorRole::CoAuthor, RecipeCollaboratorRole::Editor];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecipeCollaboratorRole::CoAuthor => "co_author",
+            RecipeCollaboratorRole::Editor => "editor",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.as_str() == value)
+    }
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct RecipeCollaboratorInput {
+    #[validate(email)]
+    pub email: String,
+    pub role: RecipeCollaboratorRole,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct RecipeCollaboratorRoleInput {
+    pub role: RecipeCollaboratorRole,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct RecipeCollaboratorDto {
+    pub user_id: Uuid,
+    pub username: String,
+    pub avatar_url: String,
+    pub role: RecipeCollaboratorRole,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,65 @@
+use crate::dto::recipe_dto::RecipeViewDto;
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct CollectionInput {
+    #[validate(length(min = 1, max = 100))]
+    pub title: String,
+    #[validate(length(max = 2000))]
+    pub description: Option<String>,
+    /// Either the current cover or a freshly uploaded temporary image.
+    pub cover_image_url: Option<String>,
+    #[serde(default)]
+    pub is_public: bool,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct CollectionRecipeInput {
+    pub recipe_id: Uuid,
+}
+/// Every recipe of the collection, in the new order.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct CollectionOrderInput {
+    pub recipe_ids: Vec<Uuid>,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct FavoritesToCollectionInput {
+    #[validate(length(min = 1, max = 100))]
+    pub title: String,
+    #[validate(length(max = 2000))]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub is_public: bool,
+    /// Empties the favorites once they are in the collection.
+    #[serde(default)]
+    pub remove_favorites: bool,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct CollectionSummaryDto {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    /// The chosen cover, or the image of the first recipe when there is none.
+    pub cover_image_url: Option<String>,
+    pub is_public: bool,
+    /// Recipes the viewer can see.
+    pub recipe_count: i64,
+    pub updated_at: DateTime<Utc>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct CollectionDto {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub cover_image_url: Option<String>,
+    pub is_public: bool,
+    /// In collection order, without the private recipes of other users.
+    pub recipes: Vec<RecipeViewDto>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
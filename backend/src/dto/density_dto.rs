@@ -0,0 +1,38 @@
+use entity::ingredient_densities;
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct DensityDto {
+    pub id: Uuid,
+    pub name_en: String,
+    pub name_fr: String,
+    pub grams_per_ml: f64,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct DensityInputDto {
+    #[validate(length(min = 1, max = 100))]
+    pub name_en: String,
+    #[validate(length(min = 1, max = 100))]
+    pub name_fr: String,
+    #[validate(range(exclusive_min = 0.0))]
+    pub grams_per_ml: f64,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DensityStatus {
+    Known,
+    Missing,
+}
+impl From<ingredient_densities::Model> for DensityDto {
+    fn from(value: ingredient_densities::Model) -> Self {
+        Self {
+            id: value.id,
+            name_en: value.name_en,
+            name_fr: value.name_fr,
+            grams_per_ml: value.grams_per_ml,
+        }
+    }
+}
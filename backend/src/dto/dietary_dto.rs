@@ -0,0 +1,146 @@
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Group of ingredients a dietary rule puts an ingredient in.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DietaryCategory {
+    /// Tree nuts and peanuts.
+    Nuts,
+    Gluten,
+    Dairy,
+    Eggs,
+    Meat,
+    Fish,
+    Shellfish,
+    /// Only matters to vegans.
+    Honey,
+}
+impl DietaryCategory {
+    pub const ALL: [DietaryCategory; 8] = [
+        DietaryCategory::Nuts,
+        DietaryCategory::Gluten,
+        DietaryCategory::Dairy,
+        DietaryCategory::Eggs,
+        DietaryCategory::Meat,
+        DietaryCategory::Fish,
+        DietaryCategory::Shellfish,
+        DietaryCategory::Honey,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DietaryCategory::Nuts => "nuts",
+            DietaryCategory::Gluten => "gluten",
+            DietaryCategory::Dairy => "dairy",
+            DietaryCategory::Eggs => "eggs",
+            DietaryCategory::Meat => "meat",
+            DietaryCategory::Fish => "fish",
+            DietaryCategory::Shellfish => "shellfish",
+            DietaryCategory::Honey => "honey",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == value)
+    }
+}
+/// Label of a recipe, derived from its ingredients or set by its author.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum DietaryLabel {
+    ContainsNuts,
+    ContainsGluten,
+    ContainsDairy,
+    ContainsEggs,
+    ContainsMeat,
+    ContainsFish,
+    ContainsShellfish,
+    NutFree,
+    GlutenFree,
+    DairyFree,
+    EggFree,
+    Vegetarian,
+    Vegan,
+}
+impl DietaryLabel {
+    pub const ALL: [DietaryLabel; 13] = [
+        DietaryLabel::ContainsNuts,
+        DietaryLabel::ContainsGluten,
+        DietaryLabel::ContainsDairy,
+        DietaryLabel::ContainsEggs,
+        DietaryLabel::ContainsMeat,
+        DietaryLabel::ContainsFish,
+        DietaryLabel::ContainsShellfish,
+        DietaryLabel::NutFree,
+        DietaryLabel::GlutenFree,
+        DietaryLabel::DairyFree,
+        DietaryLabel::EggFree,
+        DietaryLabel::Vegetarian,
+        DietaryLabel::Vegan,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DietaryLabel::ContainsNuts => "contains-nuts",
+            DietaryLabel::ContainsGluten => "contains-gluten",
+            DietaryLabel::ContainsDairy => "contains-dairy",
+            DietaryLabel::ContainsEggs => "contains-eggs",
+            DietaryLabel::ContainsMeat => "contains-meat",
+            DietaryLabel::ContainsFish => "contains-fish",
+            DietaryLabel::ContainsShellfish => "contains-shellfish",
+            DietaryLabel::NutFree => "nut-free",
+            DietaryLabel::GlutenFree => "gluten-free",
+            DietaryLabel::DairyFree => "dairy-free",
+            DietaryLabel::EggFree => "egg-free",
+            DietaryLabel::Vegetarian => "vegetarian",
+            DietaryLabel::Vegan => "vegan",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.as_str() == value)
+    }
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct DietaryRuleDto {
+    pub id: Uuid,
+    pub category: DietaryCategory,
+    pub lexicon_id: Option<i32>,
+    pub canonical_ingredient_id: Option<Uuid>,
+}
+/// Exactly one of `lexicon_id` and `canonical_ingredient_id`.
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct DietaryRuleInput {
+    pub category: DietaryCategory,
+    pub lexicon_id: Option<i32>,
+    pub canonical_ingredient_id: Option<Uuid>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct DietaryRefreshResultDto {
+    pub rules_created: u64,
+    /// Recipes whose labels were recomputed.
+    pub recipes: u64,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct RecipeDietaryLabelsDto {
+    pub recipe_id: Uuid,
+    /// Labels that apply, once the author overrides are taken into account.
+    pub labels: Vec<DietaryLabel>,
+    /// Labels found from the ingredients.
+    pub derived: Vec<DietaryLabel>,
+    pub overrides: Vec<DietaryLabelOverrideDto>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+pub struct DietaryLabelOverrideDto {
+    pub label: DietaryLabel,
+    /// Forces the label on or off, whatever the ingredients say.
+    pub applies: bool,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct DietaryOverridesInput {
+    /// Replaces every override of the recipe; an empty list goes back to the derived labels.
+    pub overrides: Vec<DietaryLabelOverrideDto>,
+}
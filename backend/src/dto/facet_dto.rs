@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Counts for the recipe browser, each one honouring every other active filter.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct RecipeFacetsDto {
+    /// Recipes matching all active filters.
+    pub total: i64,
+    pub tags: Vec<TagFacetDto>,
+    pub prep_time: Vec<RangeFacetDto>,
+    pub cook_time: Vec<RangeFacetDto>,
+    pub steps: Vec<RangeFacetDto>,
+    pub languages: Vec<LanguageFacetDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct TagFacetDto {
+    pub id: Uuid,
+    pub name: String,
+    pub count: i64,
+}
+
+/// Inclusive bucket, open ended when `max` is unset.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct RangeFacetDto {
+    pub min: i32,
+    pub max: Option<i32>,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct LanguageFacetDto {
+    pub code: String,
+    pub count: i64,
+}
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// What a member may do in a household. Every member sees its recipes, editors
+/// and the owner may also change them.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HouseholdRole {
+    Owner,
+    Editor,
+    Viewer,
+}
+impl HouseholdRole {
+    pub const ALL: [HouseholdRole; 3] = [HouseholdRole::Owner, HouseholdRole::Editor, HouseholdRole::Viewer];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HouseholdRole::Owner => "owner",
+            HouseholdRole::Editor => "editor",
+            HouseholdRole::Viewer => "viewer",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.as_str() == value)
+    }
+
+    pub fn can_edit(&self) -> bool {
+        matches!(self, HouseholdRole::Owner | HouseholdRole::Editor)
+    }
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct HouseholdInput {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct HouseholdInvitationInput {
+    #[validate(email)]
+    pub email: String,
+    /// `editor` or `viewer`; a household has a single owner.
+    pub role: HouseholdRole,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct HouseholdMemberInput {
+    pub role: HouseholdRole,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct HouseholdRecipeInput {
+    pub recipe_id: Uuid,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct HouseholdSummaryDto {
+    pub id: Uuid,
+    pub name: String,
+    pub owner_id: Uuid,
+    /// Role of the current user in the household.
+    pub role: HouseholdRole,
+    /// The owner included.
+    pub member_count: i64,
+    pub recipe_count: i64,
+    pub updated_at: DateTime<Utc>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct HouseholdMemberDto {
+    pub user_id: Uuid,
+    pub username: String,
+    pub avatar_url: String,
+    pub role: HouseholdRole,
+    pub joined_at: DateTime<Utc>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct HouseholdInvitationDto {
+    pub id: Uuid,
+    pub email: String,
+    pub role: HouseholdRole,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct HouseholdDto {
+    pub id: Uuid,
+    pub name: String,
+    pub owner_id: Uuid,
+    /// Role of the current user in the household.
+    pub role: HouseholdRole,
+    /// The owner first, then the members in the order they joined.
+    pub members: Vec<HouseholdMemberDto>,
+    /// Pending invitations, only shown to the owner.
+    pub invitations: Vec<HouseholdInvitationDto>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -1,3 +1,5 @@
+use crate::dto::density_dto::DensityStatus;
+use crate::dto::shopping_list_dto::Aisle;
 use crate::dto::unit_dto::UnitDto;
 use migration::prelude::Decimal;
 use serde::{Deserialize, Serialize};
@@ -27,6 +29,8 @@ pub struct IngredientInput {
     pub quantity: Decimal,
     pub unit_id: Uuid,
     pub position: i32,
+    /// Catalog entry this line is an instance of.
+    pub canonical_ingredient_id: Option<Uuid>,
 }
 #[derive(Debug, Validate, Deserialize, Serialize, ToSchema, Clone)]
 pub struct EditIngredientInput {
@@ -37,6 +41,7 @@ pub struct EditIngredientInput {
     pub quantity: Decimal,
     pub unit_id: Uuid,
     pub position: i32,
+    pub canonical_ingredient_id: Option<Uuid>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct IngredientRecipeViewDto {
@@ -44,15 +49,20 @@ pub struct IngredientRecipeViewDto {
     pub name: String,
     pub unit: UnitDto,
     pub quantity: Decimal,
+    pub display_quantity: Option<String>,
     pub note: Option<String>,
     pub position: i32,
+    pub equivalent: Option<IngredientEquivalentDto>,
+    pub density_status: Option<DensityStatus>,
+    pub canonical_ingredient_id: Option<Uuid>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
-pub struct IngredientViewDto {
-    pub id: Uuid,
-    pub name: String,
+pub struct IngredientEquivalentDto {
+    pub quantity: Decimal,
+    pub display_quantity: String,
+    pub unit: UnitDto,
+    pub grams_per_ml: f64,
 }
-
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct IngredientEditorDto {
     pub id: Uuid,
@@ -62,6 +72,7 @@ pub struct IngredientEditorDto {
     pub unit: UnitDto,
     pub position: i32,
     pub translations: Vec<IngredientTranslationsDto>,
+    pub canonical_ingredient_id: Option<Uuid>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct IngredientTranslationsDto {
@@ -74,4 +85,59 @@ pub struct IngredientTranslationsDto {
 pub struct IngredientList{
     pub search: Option<String>,
     pub limit: i32,
-}
\ No newline at end of file
+}
+/// Catalog entry that recipe ingredient lines point to, whatever they are called.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct CanonicalIngredientDto {
+    pub id: Uuid,
+    /// Name in the requested language, or the first one available.
+    pub name: String,
+    pub lexicon_id: Option<i32>,
+    pub translations: Vec<CanonicalIngredientTranslationDto>,
+    pub aliases: Vec<String>,
+    /// Number of recipes using it.
+    pub usage_count: i64,
+    pub aisle: Option<Aisle>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct CanonicalIngredientTranslationDto {
+    pub language_code: String,
+    pub name: String,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct MergeCanonicalIngredientsInput {
+    /// Duplicates folded into the target, then deleted.
+    #[validate(length(min = 1))]
+    pub source_ids: Vec<Uuid>,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct CanonicalIngredientAisleInput {
+    /// `null` clears it; the ingredient then shows under "other".
+    pub aisle: Option<Aisle>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct CatalogSeedResultDto {
+    /// Catalog entries created from the lexicon.
+    pub created: u64,
+    /// Recipe ingredient lines linked to a catalog entry.
+    pub linked: u64,
+}
+impl From<IngredientEditorDto> for IngredientInput {
+    fn from(ingredient: IngredientEditorDto) -> Self {
+        IngredientInput {
+            translations: ingredient
+                .translations
+                .into_iter()
+                .map(|t| IngredientTranslationInput {
+                    language_code: t.language_code,
+                    data: t.data,
+                    note: t.note,
+                })
+                .collect(),
+            quantity: ingredient.quantity,
+            unit_id: ingredient.unit_id,
+            position: ingredient.position,
+            canonical_ingredient_id: ingredient.canonical_ingredient_id,
+        }
+    }
+}
@@ -69,4 +69,20 @@ impl IngredientGroupViewDto {
             ingredients,
         }
     }
-}
\ No newline at end of file
+}
+impl From<IngredientGroupEditorDto> for IngredientGroupInput {
+    fn from(group: IngredientGroupEditorDto) -> Self {
+        IngredientGroupInput {
+            translations: group
+                .translations
+                .into_iter()
+                .map(|t| IngredientGroupTranslationInput {
+                    language_code: t.language_code,
+                    title: t.title,
+                })
+                .collect(),
+            position: group.position,
+            ingredients: group.ingredients.into_iter().map(IngredientInput::from).collect(),
+        }
+    }
+}
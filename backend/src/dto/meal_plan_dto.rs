@@ -0,0 +1,121 @@
+use chrono::NaiveDate;
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Meal of the day a plan is for, in the order of the day.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum MealSlot {
+    Breakfast,
+    Lunch,
+    Dinner,
+    Snack,
+}
+impl MealSlot {
+    pub const ALL: [MealSlot; 4] = [MealSlot::Breakfast, MealSlot::Lunch, MealSlot::Dinner, MealSlot::Snack];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MealSlot::Breakfast => "breakfast",
+            MealSlot::Lunch => "lunch",
+            MealSlot::Dinner => "dinner",
+            MealSlot::Snack => "snack",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MealSlot::Breakfast => "Breakfast",
+            MealSlot::Lunch => "Lunch",
+            MealSlot::Dinner => "Dinner",
+            MealSlot::Snack => "Snack",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.as_str() == value)
+    }
+}
+/// Why a planned recipe may not be cookable any more.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MealPlanWarning {
+    /// The recipe was made private by its author.
+    RecipePrivate,
+    /// The recipe no longer exists; only its title was kept.
+    RecipeDeleted,
+}
+impl MealPlanWarning {
+    pub fn message(&self) -> &'static str {
+        match self {
+            MealPlanWarning::RecipePrivate => "This recipe is now private",
+            MealPlanWarning::RecipeDeleted => "This recipe has been deleted",
+        }
+    }
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct MealPlanWeekQuery {
+    /// Any day of the week to show, defaults to today.
+    pub week: Option<NaiveDate>,
+}
+/// A plan needs a recipe, a note, or both.
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct MealPlanInput {
+    pub date: NaiveDate,
+    pub slot: MealSlot,
+    pub recipe_id: Option<Uuid>,
+    /// Defaults to the servings of the recipe.
+    #[validate(range(min = 1, max = 1000))]
+    pub servings: Option<i32>,
+    #[validate(length(max = 500))]
+    pub note: Option<String>,
+}
+/// Where a plan is moved or copied to.
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct MealPlanMoveInput {
+    pub date: NaiveDate,
+    pub slot: MealSlot,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct MealPlanCopyWeekInput {
+    /// Any day of the week copied from.
+    pub from_week: NaiveDate,
+    /// Any day of the week copied to.
+    pub to_week: NaiveDate,
+    /// Clears the target week first instead of adding to it.
+    #[serde(default)]
+    pub replace: bool,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct MealPlanDto {
+    pub id: Uuid,
+    pub date: NaiveDate,
+    pub slot: MealSlot,
+    pub recipe_id: Option<Uuid>,
+    pub title: Option<String>,
+    pub image_url: Option<String>,
+    pub servings: i32,
+    pub note: Option<String>,
+    pub warning: Option<MealPlanWarning>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct MealPlanDayDto {
+    pub date: NaiveDate,
+    pub plans: Vec<MealPlanDto>,
+}
+/// One week, Monday to Sunday, with every day present even when nothing is planned.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct MealPlanWeekDto {
+    pub week_start: NaiveDate,
+    pub week_end: NaiveDate,
+    pub days: Vec<MealPlanDayDto>,
+    pub warning_count: usize,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct MealPlanFeedDto {
+    pub token: String,
+    /// Path of the feed under the API, to subscribe to from a calendar app.
+    pub path: String,
+}
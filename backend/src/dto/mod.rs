@@ -19,6 +19,19 @@ pub mod comment_dto;
 pub mod recipe_version_dto;
 pub mod recipe_rating_dto;
 pub mod unit_dto;
+pub mod density_dto;
+pub mod archive_dto;
+pub mod pdf_dto;
 pub(crate) mod recipe_ocr;
 pub mod notification_dto;
 pub mod studio_dto;
+pub mod facet_dto;
+pub mod pantry_dto;
+pub mod nutrition_dto;
+pub mod dietary_dto;
+pub mod shopping_list_dto;
+pub mod meal_plan_dto;
+pub mod collection_dto;
+pub mod share_link_dto;
+pub mod household_dto;
+pub mod collaborator_dto;
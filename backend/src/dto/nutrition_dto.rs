@@ -0,0 +1,156 @@
+use actix_multipart::form::MultipartForm;
+use actix_multipart::form::tempfile::TempFile;
+use entity::foods;
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Layout of a food composition CSV file.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FoodTableFormat {
+    /// ANSES CIQUAL table exported as CSV.
+    Ciqual,
+    /// USDA SR Legacy `ABBREV` file or a FoodData Central export with one column per nutrient.
+    Usda,
+    /// `code, name_en, name_fr, kcal, protein, fat, carbohydrates, fiber, sodium_mg`.
+    #[default]
+    Custom,
+}
+impl FoodTableFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FoodTableFormat::Ciqual => "ciqual",
+            FoodTableFormat::Usda => "usda",
+            FoodTableFormat::Custom => "custom",
+        }
+    }
+}
+#[derive(Debug, Deserialize)]
+pub struct FoodImportQuery {
+    pub format: Option<FoodTableFormat>,
+}
+#[derive(Debug, MultipartForm)]
+pub struct FoodImportForm {
+    #[multipart(rename = "file")]
+    pub file: TempFile,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct FoodImportReportDto {
+    pub source: String,
+    /// Foods created or updated.
+    pub imported: u64,
+    /// Rows without a code or a name.
+    pub skipped: u64,
+}
+#[derive(Debug, Deserialize)]
+pub struct FoodSearchQuery {
+    pub search: Option<String>,
+    pub limit: Option<u64>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct FoodDto {
+    pub id: Uuid,
+    pub source: String,
+    pub source_code: String,
+    pub name_en: Option<String>,
+    pub name_fr: Option<String>,
+    /// Per 100 g.
+    pub per_100g: NutrientsDto,
+}
+/// Amounts of a food or a recipe. Nutrients a food has no value for count as zero.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, Default, PartialEq)]
+pub struct NutrientsDto {
+    pub energy_kcal: f64,
+    pub protein_g: f64,
+    pub fat_g: f64,
+    pub carbohydrates_g: f64,
+    pub fiber_g: f64,
+    pub sodium_mg: f64,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct FoodMappingInput {
+    pub food_id: Uuid,
+    /// Weight of one piece, clove, slice... of the ingredient.
+    #[validate(range(exclusive_min = 0.0))]
+    pub grams_per_unit: Option<f64>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct FoodMappingDto {
+    pub canonical_ingredient_id: Uuid,
+    pub food: FoodDto,
+    pub grams_per_unit: Option<f64>,
+}
+/// Nutrition facts of a recipe, computed from the foods its ingredients are mapped to.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct NutritionDto {
+    pub per_recipe: NutrientsDto,
+    pub per_serving: NutrientsDto,
+    /// Share of the ingredient lines that made it into the totals, from 0 to 1.
+    pub coverage: f64,
+    pub unmatched: Vec<UnmatchedIngredientDto>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct UnmatchedIngredientDto {
+    pub ingredient_id: Uuid,
+    pub name: String,
+    pub reason: UnmatchedReason,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UnmatchedReason {
+    /// Neither the line nor its lexicon entry leads to a food.
+    NoFood,
+    /// The quantity cannot be expressed in grams: a volume without a known density,
+    /// or a count without a unit weight.
+    NoWeight,
+}
+impl NutrientsDto {
+    /// Nutrients of `grams` of `food`.
+    pub fn of(food: &foods::Model, grams: f64) -> Self {
+        let amount = |per_100g: Option<f64>| per_100g.unwrap_or(0.0) * grams / 100.0;
+        Self {
+            energy_kcal: amount(food.energy_kcal),
+            protein_g: amount(food.protein_g),
+            fat_g: amount(food.fat_g),
+            carbohydrates_g: amount(food.carbohydrates_g),
+            fiber_g: amount(food.fiber_g),
+            sodium_mg: amount(food.sodium_mg),
+        }
+    }
+
+    pub fn add(&mut self, other: &NutrientsDto) {
+        self.energy_kcal += other.energy_kcal;
+        self.protein_g += other.protein_g;
+        self.fat_g += other.fat_g;
+        self.carbohydrates_g += other.carbohydrates_g;
+        self.fiber_g += other.fiber_g;
+        self.sodium_mg += other.sodium_mg;
+    }
+
+    /// Divides every amount and rounds it to one decimal.
+    pub fn divided(&self, by: f64) -> Self {
+        let round = |v: f64| (v / by * 10.0).round() / 10.0;
+        Self {
+            energy_kcal: round(self.energy_kcal),
+            protein_g: round(self.protein_g),
+            fat_g: round(self.fat_g),
+            carbohydrates_g: round(self.carbohydrates_g),
+            fiber_g: round(self.fiber_g),
+            sodium_mg: round(self.sodium_mg),
+        }
+    }
+}
+impl From<foods::Model> for FoodDto {
+    fn from(food: foods::Model) -> Self {
+        Self {
+            per_100g: NutrientsDto::of(&food, 100.0),
+            id: food.id,
+            source: food.source,
+            source_code: food.source_code,
+            name_en: food.name_en,
+            name_fr: food.name_fr,
+        }
+    }
+}
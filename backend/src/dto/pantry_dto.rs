@@ -0,0 +1,128 @@
+use crate::dto::recipe_dto::RecipeViewDto;
+use crate::dto::unit_dto::UnitDto;
+use chrono::NaiveDate;
+use migration::prelude::Decimal;
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct PantrySearchInput {
+    /// Ingredients on hand, in English or French.
+    #[validate(length(min = 1, max = 100))]
+    pub ingredients: Vec<String>,
+    /// Leaves salt, oil and the other staples out of the coverage. Defaults to true.
+    pub ignore_staples: Option<bool>,
+    /// Share of the recipe's ingredients that must be on hand, from 0 to 1.
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub min_coverage: Option<f32>,
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<usize>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct PantrySearchResponse {
+    /// Best coverage first.
+    pub results: Vec<PantryMatchDto>,
+    /// Pantry items not found in the lexicon, which could not be matched.
+    pub unrecognized: Vec<String>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct PantryMatchDto {
+    pub recipe: RecipeViewDto,
+    /// `matched / total`, between 0 and 1.
+    pub coverage: f32,
+    pub matched: usize,
+    pub total: usize,
+    /// Ingredients of the recipe that are not on hand, in the requested language.
+    pub missing: Vec<String>,
+}
+/// Where an item of the inventory is kept.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PantryLocation {
+    Pantry,
+    Fridge,
+    Freezer,
+}
+impl PantryLocation {
+    pub const ALL: [PantryLocation; 3] = [PantryLocation::Pantry, PantryLocation::Fridge, PantryLocation::Freezer];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PantryLocation::Pantry => "pantry",
+            PantryLocation::Fridge => "fridge",
+            PantryLocation::Freezer => "freezer",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.as_str() == value)
+    }
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PantryItemStatus {
+    NoDate,
+    Fresh,
+    /// Best before within the next few days.
+    ExpiringSoon,
+    Expired,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct PantryItemsQuery {
+    pub location: Option<PantryLocation>,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct PantryExpiringQuery {
+    /// Days ahead an item counts as expiring, defaults to 3.
+    #[validate(range(min = 0, max = 60))]
+    pub days: Option<i64>,
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<usize>,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct PantryItemInput {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+    pub canonical_ingredient_id: Option<Uuid>,
+    #[validate(range(min = 0.0))]
+    pub quantity: Option<f64>,
+    pub unit_id: Option<Uuid>,
+    pub location: PantryLocation,
+    pub best_before: Option<NaiveDate>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct PantryItemDto {
+    pub id: Uuid,
+    pub name: String,
+    pub canonical_ingredient_id: Option<Uuid>,
+    pub quantity: Option<Decimal>,
+    pub display_quantity: Option<String>,
+    pub unit: Option<UnitDto>,
+    pub location: PantryLocation,
+    pub best_before: Option<NaiveDate>,
+    /// Negative once the best-before date has passed.
+    pub days_left: Option<i64>,
+    pub status: PantryItemStatus,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct PantrySuggestionResponse {
+    /// Items the suggestions are for, soonest first.
+    pub expiring: Vec<PantryItemDto>,
+    /// Recipes using the most expiring items first.
+    pub results: Vec<PantrySuggestionDto>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct PantrySuggestionDto {
+    pub recipe: RecipeViewDto,
+    /// Expiring items the recipe uses.
+    pub uses: Vec<String>,
+    /// Share of the recipe's ingredients in the whole inventory, between 0 and 1.
+    pub coverage: f32,
+    pub missing: Vec<String>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct PantryReminderResult {
+    pub notified: usize,
+}
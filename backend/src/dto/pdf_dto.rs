@@ -0,0 +1,43 @@
+use crate::dto::unit_dto::UnitSystem;
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PdfLayout {
+    /// US letter portrait.
+    #[default]
+    Page,
+    /// 6x4 in landscape index card.
+    Card,
+}
+impl PdfLayout {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PdfLayout::Page => "page",
+            PdfLayout::Card => "card",
+        }
+    }
+}
+#[derive(Debug, Deserialize)]
+pub struct RecipePdfQuery {
+    pub lang: Option<String>,
+    pub servings: Option<i32>,
+    pub unit_system: Option<UnitSystem>,
+    pub layout: Option<PdfLayout>,
+    /// Token of a share link, giving read access to a private recipe.
+    pub share: Option<String>,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema)]
+pub struct CookbookPdfInput {
+    #[validate(length(min = 1, max = 200))]
+    pub title: Option<String>,
+    #[validate(length(min = 1, max = 100))]
+    pub recipe_ids: Vec<Uuid>,
+    pub lang: Option<String>,
+    pub unit_system: Option<UnitSystem>,
+    #[serde(default)]
+    pub layout: PdfLayout,
+}
@@ -1,3 +1,4 @@
+use crate::dto::unit_dto::UnitSystem;
 use serde_derive::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -8,6 +9,12 @@ pub struct UserPreferences {
     pub recipe_favorite_enabled: bool,
     pub recipe_comment_enabled: bool,
     pub comment_reply_enabled: bool,
+    #[serde(default = "enabled")]
+    pub pantry_expiring_enabled: bool,
+    #[serde(default = "enabled")]
+    pub recipe_forked_enabled: bool,
+    #[serde(default)]
+    pub unit_system: Option<UnitSystem>,
 }
 impl Default for UserPreferences {
     fn default() -> Self {
@@ -17,6 +24,14 @@ impl Default for UserPreferences {
             recipe_favorite_enabled: true,
             recipe_comment_enabled: true,
             comment_reply_enabled: true,
+            pantry_expiring_enabled: true,
+            recipe_forked_enabled: true,
+            unit_system: None,
         }
     }
 }
+
+/// Notification preferences added later default to on for existing users.
+fn enabled() -> bool {
+    true
+}
@@ -1,6 +1,9 @@
+use crate::dto::collaborator_dto::RecipeCollaboratorDto;
 use crate::dto::ingredient_group_dto::{EditIngredientGroupInput, IngredientGroupEditorDto, IngredientGroupInput, IngredientGroupViewDto};
 use crate::dto::step_group_dto::{EditStepGroupInput, StepGroupEditorDto, StepGroupInput, StepGroupViewDto};
+use crate::dto::nutrition_dto::NutritionDto;
 use crate::dto::tag_dto::{InputTag, TagDto};
+use crate::dto::unit_dto::UnitSystem;
 use entity::{recipe_translations, recipes};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -39,6 +42,34 @@ pub struct RecipeViewDto {
     pub step_groups: Vec<StepGroupViewDto>,
     pub nb_steps: Option<i32>,
     pub nb_ingredients: Option<i32>,
+    pub scaled_from_servings: Option<i32>,
+    pub search: Option<RecipeSearchMatchDto>,
+    /// Only computed when a single recipe is viewed.
+    pub nutrition: Option<Box<NutritionDto>>,
+    pub forked_from: Option<Uuid>,
+    /// Recipes this one descends from, its parent first. Only computed when a single
+    /// recipe is viewed.
+    pub lineage: Vec<RecipeLineageDto>,
+    /// Only listed in the studio.
+    pub collaborators: Vec<RecipeCollaboratorDto>,
+}
+/// An ancestor of a forked recipe; private ancestors keep only their id.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct RecipeLineageDto {
+    pub id: Uuid,
+    pub title: Option<String>,
+    pub author_id: Option<Uuid>,
+    pub author: Option<String>,
+    pub is_private: bool,
+}
+/// Why a recipe matched a search; matched terms are wrapped in `<mark>` tags.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct RecipeSearchMatchDto {
+    pub rank: f32,
+    pub title: String,
+    pub description: String,
+    pub ingredient: Option<String>,
+    pub step: Option<String>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[derive(Default)]
@@ -126,6 +157,8 @@ pub struct GetAllRecipesByPageQuery {
     pub include_private: bool,
     pub page: Option<i64>,
     pub per_page: Option<i64>,
+    /// `next_cursor` of the previous page; takes precedence over `page`.
+    pub cursor: Option<String>,
     pub lang: Option<String>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema,Clone)]
@@ -136,6 +169,8 @@ pub struct RecipeFilter {
     pub search: Option<String>,
     pub ingredient: Option<Vec<String>>,
     pub tags: Option<String>,
+    /// Comma-separated dietary labels; recipes carrying any of them are left out.
+    pub exclude_labels: Option<String>,
 
     pub min_prep: Option<i32>,
     pub max_prep: Option<i32>,
@@ -148,6 +183,36 @@ pub struct RecipeFilter {
     pub date_from: Option<chrono::NaiveDate>,
     pub date_to: Option<chrono::NaiveDate>,
     pub lang: Option<String>,
+    /// Only recipes translated into this language; `lang` only picks the display language.
+    pub language: Option<String>,
+
+    /// Defaults to `relevance` when searching, `newest` otherwise.
+    pub sort: Option<RecipeSort>,
+    /// Defaults to `asc` for `title`, `desc` for every other sort.
+    pub direction: Option<SortDirection>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecipeSort {
+    /// Search rank, falls back to `newest` without a search.
+    Relevance,
+    Newest,
+    Rating,
+    RatingCount,
+    Views,
+    /// Views over the last 7 days.
+    RecentViews,
+    /// Prep plus cook time.
+    TotalTime,
+    Ingredients,
+    /// Title in the requested language.
+    Title,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    Desc,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema,Clone)]
@@ -155,6 +220,8 @@ pub struct RecipeFilterByPage{
     pub filters: Option<RecipeFilter>,
     pub page:  Option<i32>,
     pub per_page: Option<i32>,
+    /// `next_cursor` of the previous page; takes precedence over `page`.
+    pub cursor: Option<String>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema,Clone)]
 pub struct RecipePagination{
@@ -162,10 +229,27 @@ pub struct RecipePagination{
     pub total: i32,
     pub page: i32,
     pub per_page: i32,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
 }
 #[derive(Deserialize)]
 pub struct GetRecipeQuery {
     pub include_translations: Option<bool>,
+    pub servings: Option<i32>,
+    pub unit_system: Option<UnitSystem>,
+    /// Token of a share link, giving read access to a private recipe.
+    pub share: Option<String>,
+}
+#[derive(Deserialize)]
+pub struct RecipeFormatQuery {
+    pub lang: Option<String>,
+    /// Token of a share link, giving read access to a private recipe.
+    pub share: Option<String>,
+}
+#[derive(Deserialize)]
+pub struct RecipeShareQuery {
+    /// Token of a share link, giving read access to a private recipe.
+    pub share: Option<String>,
 }
 #[derive(Deserialize)]
 pub struct LastRecipesQuery {
@@ -195,7 +279,43 @@ impl From<(recipes::Model, recipe_translations::Model, Option<i32>,Option<i32>)>
             ingredient_groups: vec![],
             step_groups: vec![],
             nb_ingredients,
-            nb_steps
+            nb_steps,
+            scaled_from_servings: None,
+            search: None,
+            nutrition: None,
+            forked_from: recipe.forked_from,
+            lineage: vec![],
+            collaborators: vec![],
+        }
+    }
+}
+impl From<RecipeEditorDto> for CreateRecipeInput {
+    fn from(recipe: RecipeEditorDto) -> Self {
+        CreateRecipeInput {
+            primary_language: recipe.primary_language,
+            translations: recipe
+                .translations
+                .into_iter()
+                .map(|t| RecipeTranslationInput {
+                    language_code: t.language_code,
+                    title: t.title,
+                    description: t.description.unwrap_or_default(),
+                })
+                .collect(),
+            image_url: recipe.image_url,
+            servings: recipe.servings,
+            prep_time_minutes: recipe.prep_time_minutes,
+            cook_time_minutes: recipe.cook_time_minutes,
+            author_id: recipe.author_id,
+            author: recipe.author,
+            is_private: recipe.is_private,
+            tags: recipe
+                .tags
+                .into_iter()
+                .map(|TagDto { name, .. }| InputTag::New { name })
+                .collect(),
+            ingredient_groups: recipe.ingredient_groups.into_iter().map(IngredientGroupInput::from).collect(),
+            step_groups: recipe.step_groups.into_iter().map(StepGroupInput::from).collect(),
         }
     }
 }
@@ -233,6 +353,12 @@ impl RecipeViewDto {
             step_groups: step_groups.clone(),
             nb_ingredients: Some(ingredient_groups.iter().map(|inner_vec| inner_vec.ingredients.len() as i32).sum()),
             nb_steps: Some(step_groups.iter().map(|inner_vec| inner_vec.steps.len() as i32).sum()),
+            scaled_from_servings: None,
+            search: None,
+            nutrition: None,
+            forked_from: recipe.forked_from,
+            lineage: vec![],
+            collaborators: vec![],
         }
     }
 }
@@ -206,6 +206,7 @@ impl OcrConfirmInput {
                             data: ing.main_db_ingredient_id.to_string(),
                             note: Some(ing.source_ocr_lines.join(" ")),
                         }],
+                        canonical_ingredient_id: None,
                     })
                     .collect(),
             })
@@ -1,7 +1,12 @@
+use crate::dto::collaborator_dto::RecipeCollaboratorRole;
 use crate::dto::recipe_dto::RecipeEditorDto;
+use crate::dto::tag_dto::TagDto;
+use crate::dto::unit_dto::UnitDto;
 use crate::dto::user_dto::UserResponseDto;
 use chrono::{DateTime, Utc};
 use entity::recipe_versions;
+use migration::prelude::Decimal;
+use serde_json::Value as JsonValue;
 use serde_derive::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -12,6 +17,8 @@ pub struct RecipeVersionDto {
     pub data: RecipeEditorDto,
     pub recipe_id: Uuid,
     pub edited_by: UserResponseDto,
+    /// Set when the change was made by a collaborator rather than the author.
+    pub edited_as: Option<RecipeCollaboratorRole>,
     pub created_at:DateTime<Utc>,
 }
 impl RecipeVersionDto {
@@ -24,7 +31,68 @@ impl RecipeVersionDto {
             data: recipe_data,
             recipe_id: version.recipe_id,
             edited_by: user,
+            edited_as: version.edited_as.as_deref().and_then(RecipeCollaboratorRole::parse),
             created_at: version.created_at.with_timezone(&Utc),
         }
     }
-}
\ No newline at end of file
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecipeVersionDiffQuery {
+    /// Version to compare against; the current recipe is used when omitted.
+    pub compare_to: Option<Uuid>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+    Moved,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecipeVersionDiffDto {
+    pub recipe_id: Uuid,
+    pub from_version_id: Uuid,
+    pub to_version_id: Option<Uuid>,
+    pub fields: Vec<FieldChangeDto>,
+    pub translations: Vec<TranslationChangeDto>,
+    pub tags_added: Vec<TagDto>,
+    pub tags_removed: Vec<TagDto>,
+    pub ingredients: Vec<IngredientChangeDto>,
+    pub steps: Vec<StepChangeDto>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FieldChangeDto {
+    pub field: String,
+    pub from: JsonValue,
+    pub to: JsonValue,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TranslationChangeDto {
+    pub language_code: String,
+    pub field: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IngredientChangeDto {
+    pub change: ChangeKind,
+    pub name: String,
+    pub group: Option<String>,
+    pub from_quantity: Option<Decimal>,
+    pub to_quantity: Option<Decimal>,
+    pub quantity_delta: Option<Decimal>,
+    pub from_unit: Option<UnitDto>,
+    pub to_unit: Option<UnitDto>,
+    pub translations: Vec<TranslationChangeDto>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StepChangeDto {
+    pub changes: Vec<ChangeKind>,
+    pub group: Option<String>,
+    pub from_position: Option<i32>,
+    pub to_position: Option<i32>,
+    pub from_duration_minutes: Option<i32>,
+    pub to_duration_minutes: Option<i32>,
+    pub translations: Vec<TranslationChangeDto>,
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShareLinkInput {
+    /// Who the link was sent to, as a reminder for the owner.
+    #[validate(length(max = 100))]
+    pub label: Option<String>,
+    /// The link stops working after this moment; it never expires when left empty.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShareLinkDto {
+    pub id: Uuid,
+    pub recipe_id: Uuid,
+    pub token: String,
+    /// Path of the shared recipe under the API.
+    pub path: String,
+    pub label: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Neither revoked nor expired.
+    pub is_active: bool,
+    pub open_count: i64,
+    pub last_opened_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,157 @@
+use crate::dto::unit_dto::UnitDto;
+use chrono::{DateTime, Utc};
+use migration::prelude::Decimal;
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Section of the store an ingredient is found in, in the order a shopper walks them.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Aisle {
+    Produce,
+    Bakery,
+    Meat,
+    Seafood,
+    Dairy,
+    Frozen,
+    Pantry,
+    Spices,
+    Beverages,
+    #[default]
+    Other,
+}
+impl Aisle {
+    pub const ALL: [Aisle; 10] = [
+        Aisle::Produce,
+        Aisle::Bakery,
+        Aisle::Meat,
+        Aisle::Seafood,
+        Aisle::Dairy,
+        Aisle::Frozen,
+        Aisle::Pantry,
+        Aisle::Spices,
+        Aisle::Beverages,
+        Aisle::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Aisle::Produce => "produce",
+            Aisle::Bakery => "bakery",
+            Aisle::Meat => "meat",
+            Aisle::Seafood => "seafood",
+            Aisle::Dairy => "dairy",
+            Aisle::Frozen => "frozen",
+            Aisle::Pantry => "pantry",
+            Aisle::Spices => "spices",
+            Aisle::Beverages => "beverages",
+            Aisle::Other => "other",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.as_str() == value)
+    }
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListInput {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListSummaryDto {
+    pub id: Uuid,
+    pub name: String,
+    pub owner_id: Uuid,
+    pub item_count: i64,
+    pub checked_count: i64,
+    pub updated_at: DateTime<Utc>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListDto {
+    pub id: Uuid,
+    pub name: String,
+    pub owner_id: Uuid,
+    pub members: Vec<ShoppingListMemberDto>,
+    pub recipes: Vec<ShoppingListRecipeDto>,
+    /// Non-empty aisles only, in walking order.
+    pub aisles: Vec<ShoppingListAisleDto>,
+    pub updated_at: DateTime<Utc>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListMemberDto {
+    pub user_id: Uuid,
+    pub username: String,
+    pub avatar_url: String,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListRecipeDto {
+    pub recipe_id: Uuid,
+    pub title: String,
+    pub servings: i32,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListAisleDto {
+    pub aisle: Aisle,
+    pub items: Vec<ShoppingListItemDto>,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListItemDto {
+    pub id: Uuid,
+    pub name: String,
+    pub quantity: Option<Decimal>,
+    pub display_quantity: Option<String>,
+    pub unit: Option<UnitDto>,
+    pub aisle: Aisle,
+    pub checked: bool,
+    /// Merged from the recipes of the list; only its checked state can be changed.
+    pub from_recipes: bool,
+    pub canonical_ingredient_id: Option<Uuid>,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListRecipeInput {
+    pub recipe_id: Uuid,
+    /// Defaults to the servings of the recipe.
+    #[validate(range(min = 1, max = 1000))]
+    pub servings: Option<i32>,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListItemInput {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+    #[validate(range(min = 0.0))]
+    pub quantity: Option<f64>,
+    pub unit_id: Option<Uuid>,
+    pub aisle: Option<Aisle>,
+}
+/// Fields left out are not changed.
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListItemUpdate {
+    pub checked: Option<bool>,
+    #[validate(length(min = 1, max = 200))]
+    pub name: Option<String>,
+    #[validate(range(min = 0.0))]
+    pub quantity: Option<f64>,
+    pub unit_id: Option<Uuid>,
+    pub aisle: Option<Aisle>,
+}
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListMemberInput {
+    #[validate(email)]
+    pub email: String,
+}
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShoppingListEventKind {
+    ShoppingListUpdated,
+    ShoppingListDeleted,
+}
+/// Pushed over the notification websocket to everyone sharing a list when it changes.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ShoppingListEventDto {
+    pub event: ShoppingListEventKind,
+    pub shopping_list_id: Uuid,
+    pub shopping_list: Option<ShoppingListDto>,
+}
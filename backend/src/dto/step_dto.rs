@@ -48,6 +48,7 @@ pub struct StepEditorDto {
     pub image_url: Option<String>,
     pub translations: Vec<StepTranslationsDto>,
     pub position: i32,
+    pub duration_minutes: Option<i32>,
 }
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct StepTranslationsDto {
@@ -55,4 +56,20 @@ pub struct StepTranslationsDto {
     pub language_code: String,
     pub instruction: String,
 }
-
+impl From<StepEditorDto> for StepInput {
+    fn from(step: StepEditorDto) -> Self {
+        StepInput {
+            position: step.position,
+            image_url: step.image_url,
+            translations: step
+                .translations
+                .into_iter()
+                .map(|t| StepTranslationInput {
+                    language_code: t.language_code,
+                    instruction: t.instruction,
+                })
+                .collect(),
+            duration_minutes: step.duration_minutes,
+        }
+    }
+}
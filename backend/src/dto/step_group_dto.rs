@@ -52,4 +52,20 @@ pub struct StepGroupTranslationDto{
     pub id: Uuid,
     pub language_code: String,
     pub title: String,
-}
\ No newline at end of file
+}
+impl From<StepGroupEditorDto> for StepGroupInput {
+    fn from(group: StepGroupEditorDto) -> Self {
+        StepGroupInput {
+            position: group.position,
+            translations: group
+                .translations
+                .into_iter()
+                .map(|t| StepGroupTranslationInput {
+                    language_code: t.language_code,
+                    title: t.title,
+                })
+                .collect(),
+            steps: group.steps.into_iter().map(StepInput::from).collect(),
+        }
+    }
+}
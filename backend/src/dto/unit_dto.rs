@@ -23,10 +23,25 @@ pub struct UnitInputDto {
     pub name_fr: String,
     pub name_en: String,
     pub system: String,
+    pub base_unit_id: Option<Uuid>,
     pub conversion_factor: f64,
     pub is_fraction_allowed: bool,
     pub is_active: bool,
 }
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+}
+impl UnitSystem {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "metric",
+            UnitSystem::Imperial => "imperial",
+        }
+    }
+}
 impl From<ingredient_units::Model> for UnitDto {
     fn from(value: ingredient_units::Model) -> Self {
         Self {
@@ -32,6 +32,9 @@ pub enum Error {
     #[error("Unprocessable entity")]
     UnprocessableEntity(JsonValue),
 
+    #[error("Too many requests")]
+    TooManyRequests(JsonValue),
+
     #[error("Internal server error")]
     InternalServerError(JsonValue),
 
@@ -60,6 +63,7 @@ impl ResponseError for Error {
             Error::Forbidden(_) => StatusCode::FORBIDDEN,
             Error::NotFound(_) => StatusCode::NOT_FOUND,
             Error::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Error::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
             Error::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::EmailAlreadyExists => StatusCode::CONFLICT,
             Error::EmailSend(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -102,6 +106,7 @@ impl ResponseError for Error {
             Error::Forbidden(v) => HttpResponse::build(self.status_code()).json(v),
             Error::NotFound(v) => HttpResponse::build(self.status_code()).json(v),
             Error::UnprocessableEntity(v) => HttpResponse::build(self.status_code()).json(v),
+            Error::TooManyRequests(v) => HttpResponse::build(self.status_code()).json(v),
             Error::EmailAlreadyExists => {
                 let error_log = ErrorLog::new(
                     "EmailAlreadyExists",
@@ -404,6 +409,22 @@ impl From<sqlx::Error> for Error {
     }
 }
 
+/* ----- Zip ----- */
+impl From<zip::result::ZipError> for Error {
+    fn from(err: zip::result::ZipError) -> Self {
+        match err {
+            zip::result::ZipError::Io(e) => Error::from(e),
+            zip::result::ZipError::FileNotFound => Error::BadRequest(json!({
+                "error": "File missing from archive"
+            })),
+            _ => Error::BadRequest(json!({
+                "error": "Invalid archive",
+                "details": err.to_string()
+            })),
+        }
+    }
+}
+
 
         /* -------------------------------------------------------------------------- */
 /*                        ACTIX CONFIG ERROR HANDLERS                         */
@@ -4,11 +4,13 @@ use utoipa::OpenApi;
 #[openapi(
     paths(
         crate::controllers::recipes_controller::list,
+        crate::controllers::recipes_controller::facets,
         crate::controllers::recipes_controller::get,
         // add other controllers here
     ),
     components(schemas(
         crate::dto::recipe_dto::RecipeViewDto,
+        crate::dto::facet_dto::RecipeFacetsDto,
         crate::dto::recipe_dto::CreateRecipeInput,
         crate::dto::recipe_dto::RecipeTranslationInput,
         crate::dto::ingredient_group_dto::IngredientGroupInput,
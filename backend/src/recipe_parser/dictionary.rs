@@ -166,6 +166,184 @@ async fn lookup_lexicon(text: &str, pool: &SqlitePool) -> Result<Option<OcrMatch
     }
 }
 
+/// Pantry basics most cooks never run out of, by English lexicon term.
+const STAPLES: [&str; 9] = [
+    "salt", "pepper", "black pepper", "oil", "olive oil", "canola oil", "water", "sugar", "flour",
+];
+
+/// Lexicon id of the ingredient named in `text`, taking the longest run of words found
+/// in the lexicon, so that "Oignon rouge émincé" resolves to "red onion".
+pub async fn resolve_ingredient(text: &str, pool: &SqlitePool) -> Result<Option<i32>, Error> {
+    let normalized = normalize_ingredient(text);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+
+    for size in (1..=tokens.len().min(5)).rev() {
+        for window in tokens.windows(size) {
+            let chunk = window.join(" ");
+            if is_stop_word(&chunk) { continue; }
+
+            if let Some(id) = find_ingredient_ids(&chunk, pool).await?.first() {
+                return Ok(Some(*id));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Lexicon ids a pantry item stands for: every ingredient it names when it is generic
+/// ("poivron" covers the red, green and yellow ones), plus their varieties, e.g.
+/// "oignon" also covers "oignon rouge".
+pub async fn pantry_ingredient_ids(text: &str, pool: &SqlitePool) -> Result<Vec<i32>, Error> {
+    let mut ids = find_ingredient_ids(&normalize_ingredient(text), pool).await?;
+    if ids.is_empty() {
+        ids.extend(resolve_ingredient(text, pool).await?);
+    }
+    if ids.is_empty() {
+        return Ok(ids);
+    }
+
+    // A variety ends with its base in English and starts with it in French.
+    sqlx::query_scalar::<_, i32>(
+        r#"
+            SELECT DISTINCT v.id
+            FROM lexicon v
+            JOIN lexicon p ON p.id IN (SELECT value FROM json_each(?))
+            WHERE v.category = 'ingredient'
+              AND (
+                  v.id = p.id
+                  OR (v.term_en LIKE '% ' || p.term_en
+                      AND (v.term_fr = p.term_fr OR v.term_fr LIKE p.term_fr || ' %'))
+              )
+        "#
+    )
+        .bind(json!(ids).to_string())
+        .fetch_all(pool)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Database query failed while expanding pantry ingredient",
+            "operation": "pantry_ingredient_ids",
+            "search_term": text,
+            "error": e.to_string(),
+            "stage": "database_query"
+        })))
+}
+
+/// Lexicon ids of the staples, and of any ingredient flagged as common.
+pub async fn staple_ingredient_ids(pool: &SqlitePool) -> Result<Vec<i32>, Error> {
+    sqlx::query_scalar::<_, i32>(
+        r#"
+            SELECT id FROM lexicon
+            WHERE category = 'ingredient'
+              AND (is_common OR term_en IN (SELECT value FROM json_each(?)))
+        "#
+    )
+        .bind(json!(STAPLES).to_string())
+        .fetch_all(pool)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Database query failed while loading staples",
+            "operation": "staple_ingredient_ids",
+            "error": e.to_string(),
+            "stage": "database_query"
+        })))
+}
+
+/// An ingredient of the lexicon with every spelling it is known by.
+pub struct LexiconIngredient {
+    pub id: i32,
+    pub term_en: String,
+    pub term_fr: Option<String>,
+    pub aliases: Vec<String>,
+}
+
+pub async fn lexicon_ingredients(pool: &SqlitePool) -> Result<Vec<LexiconIngredient>, Error> {
+    let rows = sqlx::query(
+        r#"
+            SELECT l.id, l.term_en, l.term_fr, s.raw_text
+            FROM lexicon l
+            LEFT JOIN (
+                SELECT lexicon_id, raw_text FROM aliases
+                UNION
+                SELECT CAST(lexicon_id AS INTEGER), raw_text FROM lexicon_fts
+            ) s ON s.lexicon_id = l.id
+            WHERE l.category = 'ingredient'
+            ORDER BY l.id
+        "#
+    )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Database query failed while loading lexicon ingredients",
+            "operation": "lexicon_ingredients",
+            "error": e.to_string(),
+            "stage": "database_query"
+        })))?;
+
+    let mut ingredients: Vec<LexiconIngredient> = Vec::new();
+    for row in rows {
+        let id = row.get::<i32, _>("id");
+        if ingredients.last().is_none_or(|i| i.id != id) {
+            ingredients.push(LexiconIngredient {
+                id,
+                term_en: row.get("term_en"),
+                term_fr: row.get::<Option<String>, _>("term_fr").filter(|t| !t.trim().is_empty()),
+                aliases: Vec::new(),
+            });
+        }
+        if let (Some(ingredient), Some(alias)) = (ingredients.last_mut(), row.get::<Option<String>, _>("raw_text")) {
+            ingredient.aliases.push(alias);
+        }
+    }
+    Ok(ingredients)
+}
+
+/// Ingredient ids whose alias is exactly `text`, or else starts with it, best match first.
+async fn find_ingredient_ids(text: &str, pool: &SqlitePool) -> Result<Vec<i32>, Error> {
+    if text.chars().count() < 3 {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query(
+        r#"
+            SELECT l.id, f.raw_text = ? AS is_exact
+            FROM lexicon_fts f
+            JOIN lexicon l ON l.id = f.lexicon_id
+            WHERE f.raw_text MATCH ? AND l.category = 'ingredient'
+            ORDER BY is_exact DESC, f.rank ASC
+        "#
+    )
+        .bind(text)
+        .bind(format!("\"{}\"*", text))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Database query failed in find_ingredient_ids",
+            "operation": "find_ingredient_ids",
+            "search_term": text,
+            "error": e.to_string(),
+            "stage": "database_query"
+        })))?;
+
+    let exact = rows.first().is_some_and(|r| r.get::<bool, _>("is_exact"));
+    let mut ids = Vec::new();
+    for row in rows {
+        if exact && !row.get::<bool, _>("is_exact") { break; }
+        let id = row.get::<i32, _>("id");
+        if !ids.contains(&id) { ids.push(id); }
+    }
+    Ok(ids)
+}
+
+/// Lowercase words only, punctuation such as the apostrophe of "d'olive" splits words
+/// the same way the FTS tokenizer does.
+fn normalize_ingredient(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 pub fn parse_numeric(token: &str) -> Result<f32, ()> {
     let unicode_fractions = [
         ('¼', 0.25), ('½', 0.5), ('¾', 0.75), ('⅓', 0.333),
@@ -0,0 +1,455 @@
+use crate::dto::ingredient_dto::{CanonicalIngredientDto, CanonicalIngredientTranslationDto};
+use crate::dto::shopping_list_dto::Aisle;
+use crate::errors::Error;
+use crate::recipe_parser::dictionary::LexiconIngredient;
+use entity::{
+    canonical_ingredient_aliases, canonical_ingredient_foods, canonical_ingredient_translations, canonical_ingredients,
+    dietary_rules, ingredient_groups, ingredients, pantry_items, shopping_list_items,
+};
+use migration::JoinType;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, Order, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, Set, Statement, TransactionTrait,
+};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Number of recipes with a line pointing to the current catalog entry.
+const USAGE_COUNT: &str = r#"(
+    SELECT COUNT(DISTINCT ig.recipe_id)
+    FROM ingredients i
+    JOIN ingredient_groups ig ON ig.id = i.ingredient_group_id
+    WHERE i.canonical_ingredient_id = canonical_ingredients.id
+)"#;
+
+/// Catalog entries named or aliased like `$1`.
+const NAME_MATCHES: &str = r#"(
+    EXISTS (
+        SELECT 1 FROM canonical_ingredient_translations t
+        WHERE t.canonical_ingredient_id = canonical_ingredients.id AND t.name ILIKE $1
+    )
+    OR EXISTS (
+        SELECT 1 FROM canonical_ingredient_aliases a
+        WHERE a.canonical_ingredient_id = canonical_ingredients.id AND a.alias ILIKE $1
+    )
+)"#;
+
+/// Links recipe lines to the catalog entry seeded from the lexicon entry their text resolved to.
+const LINK_FROM_LEXICON: &str = r#"
+    UPDATE ingredients i
+    SET canonical_ingredient_id = c.id
+    FROM ingredient_translations it
+    JOIN ingredient_lexicon_links l ON l.ingredient_translation_id = it.id
+    JOIN canonical_ingredients c ON c.lexicon_id = l.lexicon_id
+    WHERE it.ingredient_id = i.id AND i.canonical_ingredient_id IS NULL
+"#;
+
+/// Most used first.
+pub async fn search(
+    db: &DatabaseConnection,
+    search: Option<&str>,
+    limit: u64,
+    lang_code: &str,
+) -> Result<Vec<CanonicalIngredientDto>, Error> {
+    let mut query = canonical_ingredients::Entity::find()
+        .select_only()
+        .column(canonical_ingredients::Column::Id)
+        .column_as(Expr::cust(USAGE_COUNT), "usage_count");
+
+    if let Some(s) = search.map(str::trim).filter(|s| !s.is_empty()) {
+        query = query.filter(Expr::cust_with_values(NAME_MATCHES, [format!("%{}%", s)]));
+    }
+
+    let rows = query
+        .order_by(Expr::cust("usage_count"), Order::Desc)
+        .order_by_asc(canonical_ingredients::Column::CreatedAt)
+        .order_by_asc(canonical_ingredients::Column::Id)
+        .limit(limit)
+        .into_tuple::<(Uuid, i64)>()
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to search ingredient catalog",
+            "operation": "search",
+            "entity": "canonical_ingredients",
+            "search_term": search.unwrap_or(""),
+            "limit": limit,
+            "error": e.to_string(),
+            "stage": "search_query"
+        })))?;
+
+    load(db, rows, lang_code).await
+}
+
+pub async fn find_by_id(db: &DatabaseConnection, id: Uuid, lang_code: &str) -> Result<CanonicalIngredientDto, Error> {
+    let row = canonical_ingredients::Entity::find_by_id(id)
+        .select_only()
+        .column(canonical_ingredients::Column::Id)
+        .column_as(Expr::cust(USAGE_COUNT), "usage_count")
+        .into_tuple::<(Uuid, i64)>()
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Ingredient not found"})))?;
+
+    load(db, vec![row], lang_code)
+        .await?
+        .pop()
+        .ok_or(Error::NotFound(json!({"error": "Ingredient not found"})))
+}
+
+pub async fn set_aisle(db: &DatabaseConnection, id: Uuid, aisle: Option<Aisle>) -> Result<(), Error> {
+    let ingredient = canonical_ingredients::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Ingredient not found"})))?;
+
+    let mut active: canonical_ingredients::ActiveModel = ingredient.into();
+    active.aisle = Set(aisle.map(|a| a.as_str().to_string()));
+    active.update(db).await?;
+    Ok(())
+}
+
+/// Aisle of each of `ids` that has one.
+pub async fn find_aisles(db: &DatabaseConnection, ids: Vec<Uuid>) -> Result<HashMap<Uuid, Aisle>, Error> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let ingredients = canonical_ingredients::Entity::find()
+        .filter(canonical_ingredients::Column::Id.is_in(ids))
+        .filter(canonical_ingredients::Column::Aisle.is_not_null())
+        .all(db)
+        .await?;
+    Ok(ingredients
+        .into_iter()
+        .filter_map(|i| Some((i.id, Aisle::parse(i.aisle.as_deref()?)?)))
+        .collect())
+}
+
+/// Adds every lexicon ingredient the catalog does not know yet, by lexicon id or by any
+/// of its spellings, so that merged duplicates are not seeded again.
+pub async fn seed_from_lexicon(db: &DatabaseConnection, lexicon: Vec<LexiconIngredient>) -> Result<u64, Error> {
+    let txn = db.begin().await?;
+
+    let seeded: HashSet<i32> = canonical_ingredients::Entity::find()
+        .select_only()
+        .column(canonical_ingredients::Column::LexiconId)
+        .filter(canonical_ingredients::Column::LexiconId.is_not_null())
+        .into_tuple::<i32>()
+        .all(&txn)
+        .await?
+        .into_iter()
+        .collect();
+    let mut known: HashSet<String> = canonical_ingredient_aliases::Entity::find()
+        .select_only()
+        .column(canonical_ingredient_aliases::Column::Alias)
+        .into_tuple::<String>()
+        .all(&txn)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut created = 0;
+    for entry in lexicon {
+        let names: Vec<(&str, &str)> = [("en", Some(entry.term_en.as_str())), ("fr", entry.term_fr.as_deref())]
+            .into_iter()
+            .filter_map(|(lang, name)| name.map(|n| (lang, n.trim())))
+            .filter(|(_, name)| !name.is_empty())
+            .collect();
+        if seeded.contains(&entry.id) || names.iter().any(|(_, name)| known.contains(&name.to_lowercase())) {
+            continue;
+        }
+
+        let ingredient = canonical_ingredients::ActiveModel {
+            lexicon_id: Set(Some(entry.id)),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to insert catalog ingredient",
+            "operation": "seed_from_lexicon",
+            "entity": "canonical_ingredients",
+            "lexicon_id": entry.id,
+            "error": e.to_string(),
+            "stage": "ingredient_insert"
+        })))?;
+
+        for (lang, name) in &names {
+            canonical_ingredient_translations::ActiveModel {
+                canonical_ingredient_id: Set(ingredient.id),
+                language_code: Set(lang.to_string()),
+                name: Set(name.to_string()),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+        }
+
+        let aliases: Vec<String> = names
+            .iter()
+            .map(|(_, name)| name.to_string())
+            .chain(entry.aliases)
+            .map(|alias| alias.trim().to_lowercase())
+            .filter(|alias| !alias.is_empty() && known.insert(alias.clone()))
+            .collect();
+        add_aliases(&txn, ingredient.id, aliases).await?;
+
+        created += 1;
+    }
+
+    txn.commit().await?;
+    Ok(created)
+}
+
+/// Links the recipe lines that are not linked yet through their lexicon entry.
+pub async fn link_from_lexicon(db: &DatabaseConnection) -> Result<u64, Error> {
+    let result = db
+        .execute_raw(Statement::from_string(DbBackend::Postgres, LINK_FROM_LEXICON))
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to link ingredients to the catalog",
+            "operation": "link_from_lexicon",
+            "entity": "ingredients",
+            "error": e.to_string(),
+            "stage": "link_update"
+        })))?;
+    Ok(result.rows_affected())
+}
+
+/// Folds `source_ids` into `target_id`: recipe lines and aliases move over, missing
+/// translations, food and aisle are copied, then the duplicates are deleted.
+/// Folds the duplicates into the target, moving every reference to them over first.
+/// Returns the recipes using the target afterwards, whose labels may have changed.
+pub async fn merge(db: &DatabaseConnection, target_id: Uuid, source_ids: &[Uuid]) -> Result<Vec<Uuid>, Error> {
+    if source_ids.contains(&target_id) {
+        return Err(Error::BadRequest(json!({
+            "message": "An ingredient cannot be merged into itself",
+            "operation": "merge",
+            "entity": "canonical_ingredients",
+            "target_id": target_id.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    let txn = db.begin().await?;
+
+    let target = canonical_ingredients::Entity::find_by_id(target_id)
+        .one(&txn)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Ingredient not found"})))?;
+    let sources = canonical_ingredients::Entity::find()
+        .filter(canonical_ingredients::Column::Id.is_in(source_ids.to_vec()))
+        .all(&txn)
+        .await?;
+    if sources.len() != source_ids.iter().collect::<HashSet<_>>().len() {
+        return Err(Error::NotFound(json!({
+            "message": "Some ingredients to merge do not exist",
+            "operation": "merge",
+            "entity": "canonical_ingredients",
+            "target_id": target_id.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    ingredients::Entity::update_many()
+        .col_expr(ingredients::Column::CanonicalIngredientId, Expr::value(target_id))
+        .filter(ingredients::Column::CanonicalIngredientId.is_in(source_ids.to_vec()))
+        .exec(&txn)
+        .await?;
+
+    pantry_items::Entity::update_many()
+        .col_expr(pantry_items::Column::CanonicalIngredientId, Expr::value(target_id))
+        .filter(pantry_items::Column::CanonicalIngredientId.is_in(source_ids.to_vec()))
+        .exec(&txn)
+        .await?;
+
+    shopping_list_items::Entity::update_many()
+        .col_expr(shopping_list_items::Column::CanonicalIngredientId, Expr::value(target_id))
+        .filter(shopping_list_items::Column::CanonicalIngredientId.is_in(source_ids.to_vec()))
+        .exec(&txn)
+        .await?;
+
+    // Rules of the duplicates move to the target, unless it has one for the category already.
+    let rules: Vec<dietary_rules::ActiveModel> = dietary_rules::Entity::find()
+        .filter(dietary_rules::Column::CanonicalIngredientId.is_in(source_ids.to_vec()))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|rule| dietary_rules::ActiveModel {
+            category: Set(rule.category),
+            canonical_ingredient_id: Set(Some(target_id)),
+            ..Default::default()
+        })
+        .collect();
+    if !rules.is_empty() {
+        dietary_rules::Entity::insert_many(rules)
+            .on_conflict(
+                OnConflict::columns([dietary_rules::Column::Category, dietary_rules::Column::CanonicalIngredientId])
+                    .target_and_where(dietary_rules::Column::CanonicalIngredientId.is_not_null())
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(&txn)
+            .await?;
+    }
+
+    canonical_ingredient_aliases::Entity::update_many()
+        .col_expr(canonical_ingredient_aliases::Column::CanonicalIngredientId, Expr::value(target_id))
+        .filter(canonical_ingredient_aliases::Column::CanonicalIngredientId.is_in(source_ids.to_vec()))
+        .exec(&txn)
+        .await?;
+
+    let target_languages: HashSet<String> = canonical_ingredient_translations::Entity::find()
+        .filter(canonical_ingredient_translations::Column::CanonicalIngredientId.eq(target_id))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|t| t.language_code)
+        .collect();
+    let source_translations = canonical_ingredient_translations::Entity::find()
+        .filter(canonical_ingredient_translations::Column::CanonicalIngredientId.is_in(source_ids.to_vec()))
+        .all(&txn)
+        .await?;
+
+    let mut copied = target_languages;
+    let mut names = Vec::new();
+    for translation in source_translations {
+        names.push(translation.name.to_lowercase());
+        if copied.insert(translation.language_code.clone()) {
+            canonical_ingredient_translations::ActiveModel {
+                canonical_ingredient_id: Set(target_id),
+                language_code: Set(translation.language_code),
+                name: Set(translation.name),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+        }
+    }
+    // Names of the duplicates stay searchable as aliases.
+    add_aliases(&txn, target_id, names).await?;
+
+    // The target keeps its own food, otherwise takes the one of a duplicate.
+    if canonical_ingredient_foods::Entity::find_by_id(target_id).one(&txn).await?.is_none()
+        && let Some(mapping) = canonical_ingredient_foods::Entity::find()
+            .filter(canonical_ingredient_foods::Column::CanonicalIngredientId.is_in(source_ids.to_vec()))
+            .one(&txn)
+            .await?
+    {
+        canonical_ingredient_foods::ActiveModel {
+            canonical_ingredient_id: Set(target_id),
+            food_id: Set(mapping.food_id),
+            grams_per_unit: Set(mapping.grams_per_unit),
+        }
+        .insert(&txn)
+        .await?;
+    }
+
+    canonical_ingredients::Entity::delete_many()
+        .filter(canonical_ingredients::Column::Id.is_in(source_ids.to_vec()))
+        .exec(&txn)
+        .await?;
+
+    let lexicon_id = target.lexicon_id.or_else(|| sources.iter().find_map(|s| s.lexicon_id));
+    let aisle = target.aisle.clone().or_else(|| sources.iter().find_map(|s| s.aisle.clone()));
+    if lexicon_id != target.lexicon_id || aisle != target.aisle {
+        let mut active: canonical_ingredients::ActiveModel = target.into();
+        active.lexicon_id = Set(lexicon_id);
+        active.aisle = Set(aisle);
+        active.update(&txn).await?;
+    }
+
+    let recipe_ids = ingredients::Entity::find()
+        .select_only()
+        .column(ingredient_groups::Column::RecipeId)
+        .distinct()
+        .join(JoinType::InnerJoin, ingredients::Relation::IngredientGroups.def())
+        .filter(ingredients::Column::CanonicalIngredientId.eq(target_id))
+        .into_tuple::<Uuid>()
+        .all(&txn)
+        .await?;
+
+    txn.commit().await?;
+    Ok(recipe_ids)
+}
+
+async fn add_aliases<C: ConnectionTrait>(db: &C, ingredient_id: Uuid, aliases: Vec<String>) -> Result<(), Error> {
+    if aliases.is_empty() {
+        return Ok(());
+    }
+
+    let models = aliases.into_iter().map(|alias| canonical_ingredient_aliases::ActiveModel {
+        canonical_ingredient_id: Set(ingredient_id),
+        alias: Set(alias),
+        ..Default::default()
+    });
+
+    canonical_ingredient_aliases::Entity::insert_many(models)
+        .on_conflict_do_nothing_on([canonical_ingredient_aliases::Column::Alias])
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Attaches translations and aliases to `(id, usage_count)` rows, keeping their order.
+async fn load(
+    db: &DatabaseConnection,
+    rows: Vec<(Uuid, i64)>,
+    lang_code: &str,
+) -> Result<Vec<CanonicalIngredientDto>, Error> {
+    let ids: Vec<Uuid> = rows.iter().map(|(id, _)| *id).collect();
+
+    let translations = canonical_ingredient_translations::Entity::find()
+        .filter(canonical_ingredient_translations::Column::CanonicalIngredientId.is_in(ids.clone()))
+        .order_by_asc(canonical_ingredient_translations::Column::LanguageCode)
+        .all(db)
+        .await?;
+    let aliases = canonical_ingredient_aliases::Entity::find()
+        .filter(canonical_ingredient_aliases::Column::CanonicalIngredientId.is_in(ids.clone()))
+        .order_by_asc(canonical_ingredient_aliases::Column::Alias)
+        .all(db)
+        .await?;
+    let models = canonical_ingredients::Entity::find()
+        .filter(canonical_ingredients::Column::Id.is_in(ids))
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(id, usage_count)| {
+            let own: Vec<&canonical_ingredient_translations::Model> =
+                translations.iter().filter(|t| t.canonical_ingredient_id == id).collect();
+            let name = own
+                .iter()
+                .find(|t| t.language_code == lang_code)
+                .or_else(|| own.iter().find(|t| t.language_code == "en"))
+                .or_else(|| own.first())
+                .map(|t| t.name.clone())
+                .unwrap_or_default();
+
+            let model = models.iter().find(|m| m.id == id);
+            CanonicalIngredientDto {
+                id,
+                name,
+                lexicon_id: model.and_then(|m| m.lexicon_id),
+                translations: own
+                    .into_iter()
+                    .map(|t| CanonicalIngredientTranslationDto {
+                        language_code: t.language_code.clone(),
+                        name: t.name.clone(),
+                    })
+                    .collect(),
+                aliases: aliases
+                    .iter()
+                    .filter(|a| a.canonical_ingredient_id == id)
+                    .map(|a| a.alias.clone())
+                    .collect(),
+                usage_count,
+                aisle: model.and_then(|m| m.aisle.as_deref()).and_then(Aisle::parse),
+            }
+        })
+        .collect())
+}
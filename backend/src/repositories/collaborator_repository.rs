@@ -0,0 +1,128 @@
+use chrono::Utc;
+use entity::{recipe_collaborators, users};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde_json::json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::dto::collaborator_dto::{RecipeCollaboratorDto, RecipeCollaboratorRole};
+use crate::errors::Error;
+
+/// Role of the user on the recipe, `None` when they do not collaborate on it.
+pub async fn find_role(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<RecipeCollaboratorRole>, Error> {
+    let collaborator = recipe_collaborators::Entity::find_by_id((recipe_id, user_id)).one(db).await?;
+    Ok(collaborator.and_then(|c| RecipeCollaboratorRole::parse(&c.role)))
+}
+
+/// Collaborators of the recipe in the order they were added.
+pub async fn find_by_recipe(db: &DatabaseConnection, recipe_id: Uuid) -> Result<Vec<RecipeCollaboratorDto>, Error> {
+    Ok(find_by_recipes(db, &[recipe_id]).await?.remove(&recipe_id).unwrap_or_default())
+}
+
+/// Collaborators of each recipe, keyed by recipe; recipes without any are left out.
+pub async fn find_by_recipes(
+    db: &DatabaseConnection,
+    recipe_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<RecipeCollaboratorDto>>, Error> {
+    let collaborators = recipe_collaborators::Entity::find()
+        .filter(recipe_collaborators::Column::RecipeId.is_in(recipe_ids.to_vec()))
+        .order_by_asc(recipe_collaborators::Column::CreatedAt)
+        .find_also_related(users::Entity)
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to fetch recipe collaborators",
+            "operation": "find_by_recipes",
+            "entity": "recipe_collaborators",
+            "error": e.to_string(),
+            "stage": "select"
+        })))?;
+
+    let mut by_recipe: HashMap<Uuid, Vec<RecipeCollaboratorDto>> = HashMap::new();
+    for (collaborator, user) in collaborators {
+        let (Some(user), Some(role)) = (user, RecipeCollaboratorRole::parse(&collaborator.role)) else {
+            continue;
+        };
+        by_recipe.entry(collaborator.recipe_id).or_default().push(RecipeCollaboratorDto {
+            user_id: user.id,
+            username: user.username,
+            avatar_url: user.avatar_url,
+            role,
+            created_at: collaborator.created_at.with_timezone(&Utc),
+        });
+    }
+    Ok(by_recipe)
+}
+
+pub async fn find_user_by_email(db: &DatabaseConnection, email: &str) -> Result<users::Model, Error> {
+    users::Entity::find()
+        .filter(users::Column::Email.eq(email.trim()))
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "User not found"})))
+}
+
+/// Adds the user to the recipe; someone already collaborating on it takes the new role.
+pub async fn save(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    user_id: Uuid,
+    role: RecipeCollaboratorRole,
+    added_by: Uuid,
+) -> Result<(), Error> {
+    recipe_collaborators::Entity::insert(recipe_collaborators::ActiveModel {
+        recipe_id: Set(recipe_id),
+        user_id: Set(user_id),
+        role: Set(role.as_str().to_string()),
+        added_by: Set(Some(added_by)),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([recipe_collaborators::Column::RecipeId, recipe_collaborators::Column::UserId])
+            .update_column(recipe_collaborators::Column::Role)
+            .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to save recipe collaborator",
+        "operation": "save",
+        "entity": "recipe_collaborators",
+        "recipe_id": recipe_id.to_string(),
+        "user_id": user_id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+    Ok(())
+}
+
+pub async fn set_role(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    user_id: Uuid,
+    role: RecipeCollaboratorRole,
+) -> Result<(), Error> {
+    let result = recipe_collaborators::Entity::update_many()
+        .col_expr(recipe_collaborators::Column::Role, Expr::value(role.as_str()))
+        .filter(recipe_collaborators::Column::RecipeId.eq(recipe_id))
+        .filter(recipe_collaborators::Column::UserId.eq(user_id))
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(Error::NotFound(json!({"error": "Collaborator not found"})));
+    }
+    Ok(())
+}
+
+pub async fn remove(db: &DatabaseConnection, recipe_id: Uuid, user_id: Uuid) -> Result<(), Error> {
+    let result = recipe_collaborators::Entity::delete_by_id((recipe_id, user_id)).exec(db).await?;
+    if result.rows_affected == 0 {
+        return Err(Error::NotFound(json!({"error": "Collaborator not found"})));
+    }
+    Ok(())
+}
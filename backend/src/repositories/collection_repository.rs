@@ -0,0 +1,296 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use entity::{collection_recipes, collections, favorites, recipes};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+    FromQueryResult, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set, Statement, TransactionTrait,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::dto::collection_dto::{CollectionInput, CollectionSummaryDto};
+use crate::errors::Error;
+use crate::repositories::recipe_repository;
+
+/// Collections of `$1` that `$2` may see, last changed first. Only the recipes `$2` may
+/// see count, and a collection without a cover shows its first one. `{visible}` stands
+/// for the visibility of recipe `r`.
+const OWNER_COLLECTIONS: &str = r#"
+    SELECT
+        c.id,
+        c.owner_id,
+        c.title,
+        c.description,
+        COALESCE(c.cover_image_url, (
+            SELECT r.image_url
+            FROM collection_recipes cr JOIN recipes r ON r.id = cr.recipe_id
+            WHERE cr.collection_id = c.id AND {visible}
+            ORDER BY cr.position
+            LIMIT 1
+        )) AS cover_image_url,
+        c.is_public,
+        c.updated_at,
+        (
+            SELECT count(*)
+            FROM collection_recipes cr JOIN recipes r ON r.id = cr.recipe_id
+            WHERE cr.collection_id = c.id AND {visible}
+        ) AS recipe_count
+    FROM collections c
+    WHERE c.owner_id = $1 AND (c.is_public OR c.owner_id = $2)
+    ORDER BY c.updated_at DESC
+"#;
+
+/// Puts every favorite of `$2` in collection `$1`, oldest favorite first.
+const COPY_FAVORITES: &str = r#"
+    INSERT INTO collection_recipes (collection_id, recipe_id, position)
+    SELECT $1, f.recipe_id, (row_number() OVER (ORDER BY f.created_at, f.recipe_id) - 1)::int
+    FROM favorites f
+    WHERE f.user_id = $2
+"#;
+
+/// Appends recipe `$2` to collection `$1` unless it is already in it.
+const APPEND_RECIPE: &str = r#"
+    INSERT INTO collection_recipes (collection_id, recipe_id, position)
+    SELECT $1, $2, COALESCE(max(position) + 1, 0)
+    FROM collection_recipes
+    WHERE collection_id = $1
+    ON CONFLICT (collection_id, recipe_id) DO NOTHING
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct CollectionSummaryRow {
+    id: Uuid,
+    owner_id: Uuid,
+    title: String,
+    description: Option<String>,
+    cover_image_url: Option<String>,
+    is_public: bool,
+    updated_at: DateTime<FixedOffset>,
+    recipe_count: i64,
+}
+
+pub async fn find_by_owner(
+    db: &DatabaseConnection,
+    owner_id: Uuid,
+    viewer_id: Option<Uuid>,
+) -> Result<Vec<CollectionSummaryDto>, Error> {
+    let rows = CollectionSummaryRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        OWNER_COLLECTIONS.replace("{visible}", &recipe_repository::visible_sql("r", "$2")),
+        [owner_id.into(), viewer_id.into()],
+    ))
+    .all(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to fetch collections",
+        "operation": "find_by_owner",
+        "entity": "collections",
+        "owner_id": owner_id.to_string(),
+        "error": e.to_string(),
+        "stage": "select"
+    })))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| CollectionSummaryDto {
+            id: r.id,
+            owner_id: r.owner_id,
+            title: r.title,
+            description: r.description,
+            cover_image_url: r.cover_image_url,
+            is_public: r.is_public,
+            recipe_count: r.recipe_count,
+            updated_at: r.updated_at.with_timezone(&Utc),
+        })
+        .collect())
+}
+
+pub async fn find_by_id(db: &DatabaseConnection, id: Uuid) -> Result<collections::Model, Error> {
+    collections::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Collection not found"})))
+}
+
+pub async fn find_recipe(db: &DatabaseConnection, recipe_id: Uuid) -> Result<Option<recipes::Model>, Error> {
+    let recipe = recipes::Entity::find_by_id(recipe_id).one(db).await?;
+    Ok(recipe)
+}
+
+/// Recipes of the collection `viewer_id` may see, in order.
+pub async fn find_recipes(
+    db: &DatabaseConnection,
+    id: Uuid,
+    viewer_id: Option<Uuid>,
+) -> Result<Vec<recipes::Model>, Error> {
+    let recipes = recipes::Entity::find()
+        .join(JoinType::InnerJoin, recipes::Relation::CollectionRecipes.def())
+        .filter(collection_recipes::Column::CollectionId.eq(id))
+        .filter(recipe_repository::visible_condition(viewer_id))
+        .order_by_asc(collection_recipes::Column::Position)
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to fetch collection recipes",
+            "operation": "find_recipes",
+            "entity": "collection_recipes",
+            "collection_id": id.to_string(),
+            "error": e.to_string(),
+            "stage": "select"
+        })))?;
+    Ok(recipes)
+}
+
+/// Every recipe id of the collection in order, hidden ones included.
+pub async fn find_recipe_ids(db: &DatabaseConnection, id: Uuid) -> Result<Vec<Uuid>, Error> {
+    let ids = collection_recipes::Entity::find()
+        .select_only()
+        .column(collection_recipes::Column::RecipeId)
+        .filter(collection_recipes::Column::CollectionId.eq(id))
+        .order_by_asc(collection_recipes::Column::Position)
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(ids)
+}
+
+pub async fn create(db: &DatabaseConnection, owner_id: Uuid, input: CollectionInput) -> Result<collections::Model, Error> {
+    let collection = collections::ActiveModel {
+        owner_id: Set(owner_id),
+        title: Set(input.title.trim().to_string()),
+        description: Set(input.description),
+        cover_image_url: Set(input.cover_image_url),
+        is_public: Set(input.is_public),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to insert collection",
+        "operation": "create",
+        "entity": "collections",
+        "owner_id": owner_id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+    Ok(collection)
+}
+
+pub async fn update(
+    db: &DatabaseConnection,
+    collection: collections::Model,
+    input: CollectionInput,
+) -> Result<collections::Model, Error> {
+    let mut active: collections::ActiveModel = collection.into();
+    active.title = Set(input.title.trim().to_string());
+    active.description = Set(input.description);
+    active.cover_image_url = Set(input.cover_image_url);
+    active.is_public = Set(input.is_public);
+    active.updated_at = Set(Utc::now().into());
+    let collection = active.update(db).await?;
+    Ok(collection)
+}
+
+/// Marks the collection as changed, for the order of the collections.
+pub async fn touch<C: ConnectionTrait>(db: &C, id: Uuid) -> Result<(), Error> {
+    collections::Entity::update_many()
+        .col_expr(collections::Column::UpdatedAt, Expr::current_timestamp())
+        .filter(collections::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), Error> {
+    collections::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// Adds the recipe at the end of the collection; a recipe already in it keeps its place.
+pub async fn add_recipe(db: &DatabaseConnection, id: Uuid, recipe_id: Uuid) -> Result<(), Error> {
+    db.execute_raw(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        APPEND_RECIPE,
+        [id.into(), recipe_id.into()],
+    ))
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to add recipe to collection",
+        "operation": "add_recipe",
+        "entity": "collection_recipes",
+        "collection_id": id.to_string(),
+        "recipe_id": recipe_id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+    touch(db, id).await
+}
+
+pub async fn remove_recipe(db: &DatabaseConnection, id: Uuid, recipe_id: Uuid) -> Result<(), Error> {
+    let result = collection_recipes::Entity::delete_by_id((id, recipe_id)).exec(db).await?;
+    if result.rows_affected == 0 {
+        return Err(Error::NotFound(json!({"error": "Recipe not in collection"})));
+    }
+    touch(db, id).await
+}
+
+/// Numbers the recipes in the order given, which must hold every recipe of the collection.
+pub async fn reorder(db: &DatabaseConnection, id: Uuid, recipe_ids: &[Uuid]) -> Result<(), Error> {
+    let txn = db.begin().await?;
+    for (position, recipe_id) in recipe_ids.iter().enumerate() {
+        collection_recipes::Entity::update_many()
+            .col_expr(collection_recipes::Column::Position, Expr::value(position as i32))
+            .filter(collection_recipes::Column::CollectionId.eq(id))
+            .filter(collection_recipes::Column::RecipeId.eq(*recipe_id))
+            .exec(&txn)
+            .await?;
+    }
+    touch(&txn, id).await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Creates a collection holding the favorites of the owner, emptying them on request.
+pub async fn create_from_favorites(
+    db: &DatabaseConnection,
+    owner_id: Uuid,
+    input: CollectionInput,
+    remove_favorites: bool,
+) -> Result<collections::Model, Error> {
+    let txn = db.begin().await?;
+    let collection = collections::ActiveModel {
+        owner_id: Set(owner_id),
+        title: Set(input.title.trim().to_string()),
+        description: Set(input.description),
+        cover_image_url: Set(input.cover_image_url),
+        is_public: Set(input.is_public),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    txn.execute_raw(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        COPY_FAVORITES,
+        [collection.id.into(), owner_id.into()],
+    ))
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to copy favorites to collection",
+        "operation": "create_from_favorites",
+        "entity": "collection_recipes",
+        "owner_id": owner_id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+
+    if remove_favorites {
+        favorites::Entity::delete_many()
+            .filter(favorites::Column::UserId.eq(owner_id))
+            .exec(&txn)
+            .await?;
+    }
+    txn.commit().await?;
+
+    Ok(collection)
+}
@@ -0,0 +1,83 @@
+use entity::ingredient_densities::{ActiveModel, Column, Entity as IngredientDensities};
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, QueryOrder, Set};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::dto::density_dto::{DensityDto, DensityInputDto};
+use crate::errors::Error;
+
+pub async fn get_all(db: &DatabaseConnection) -> Result<Vec<DensityDto>, Error> {
+    let densities = IngredientDensities::find()
+        .order_by_asc(Column::NameEn)
+        .all(db)
+        .await?;
+
+    Ok(densities.into_iter().map(DensityDto::from).collect())
+}
+
+pub async fn find_by_id(db: &DatabaseConnection, id: Uuid) -> Result<DensityDto, Error> {
+    let model = IngredientDensities::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Density not found"})))?;
+
+    Ok(DensityDto::from(model))
+}
+
+pub async fn create(db: &DatabaseConnection, input: DensityInputDto) -> Result<DensityDto, Error> {
+    let model = ActiveModel {
+        name_en: Set(input.name_en.trim().to_lowercase()),
+        name_fr: Set(input.name_fr.trim().to_lowercase()),
+        grams_per_ml: Set(input.grams_per_ml),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| Error::BadRequest(json!({
+        "message": "Failed to insert ingredient density",
+        "operation": "create",
+        "entity": "ingredient_densities",
+        "name_en": &input.name_en,
+        "error": e.to_string(),
+        "stage": "density_insert"
+    })))?;
+
+    Ok(DensityDto::from(model))
+}
+
+pub async fn update(
+    db: &DatabaseConnection,
+    id: Uuid,
+    input: DensityInputDto,
+) -> Result<DensityDto, Error> {
+    let existing = IngredientDensities::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Density not found"})))?;
+
+    let mut active_model: ActiveModel = existing.into();
+    active_model.name_en = Set(input.name_en.trim().to_lowercase());
+    active_model.name_fr = Set(input.name_fr.trim().to_lowercase());
+    active_model.grams_per_ml = Set(input.grams_per_ml);
+
+    let model = active_model.update(db).await.map_err(|e| Error::BadRequest(json!({
+        "message": "Failed to update ingredient density",
+        "operation": "update",
+        "entity": "ingredient_densities",
+        "density_id": id.to_string(),
+        "error": e.to_string(),
+        "stage": "density_update"
+    })))?;
+
+    Ok(DensityDto::from(model))
+}
+
+pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), Error> {
+    let result = IngredientDensities::delete_by_id(id).exec(db).await?;
+
+    if result.rows_affected == 0 {
+        return Err(Error::NotFound(json!({"error": "Density not found"})));
+    }
+
+    Ok(())
+}
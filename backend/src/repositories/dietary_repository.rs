@@ -0,0 +1,315 @@
+use entity::dietary_rules::{ActiveModel, Column, Entity as DietaryRules, Model};
+use entity::{recipe_dietary_labels, recipes};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult,
+    QueryFilter, QueryOrder, QuerySelect, Set, Statement,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::dto::dietary_dto::{
+    DietaryCategory, DietaryLabel, DietaryLabelOverrideDto, DietaryRuleDto, DietaryRuleInput, RecipeDietaryLabelsDto,
+};
+use crate::errors::Error;
+use crate::utils::dietary::{self, LineCategories};
+
+/// Every line of recipe `$1` with the categories of the rules matching it. A line is
+/// known by its catalog entry, the lexicon entries its translations resolved to, and
+/// the catalog entries seeded from those; a rule on any of them applies. A link
+/// resolved from an older text of the line is ignored.
+const RECIPE_LINE_CATEGORIES: &str = r#"
+    WITH lines AS (
+        SELECT i.id, i.canonical_ingredient_id
+        FROM ingredient_groups ig
+        JOIN ingredients i ON i.ingredient_group_id = ig.id
+        WHERE ig.recipe_id = $1
+    ),
+    lexicon_ids AS (
+        SELECT lines.id AS ingredient_id, l.lexicon_id
+        FROM lines
+        JOIN ingredient_translations it ON it.ingredient_id = lines.id
+        JOIN ingredient_lexicon_links l ON l.ingredient_translation_id = it.id
+        WHERE l.lexicon_id IS NOT NULL AND l.source_text = it.data
+        UNION
+        SELECT lines.id, c.lexicon_id
+        FROM lines
+        JOIN canonical_ingredients c ON c.id = lines.canonical_ingredient_id
+        WHERE c.lexicon_id IS NOT NULL
+    ),
+    catalog_ids AS (
+        SELECT id AS ingredient_id, canonical_ingredient_id
+        FROM lines
+        WHERE canonical_ingredient_id IS NOT NULL
+        UNION
+        SELECT x.ingredient_id, c.id
+        FROM lexicon_ids x
+        JOIN canonical_ingredients c ON c.lexicon_id = x.lexicon_id
+    )
+    SELECT
+        lines.id AS ingredient_id,
+        EXISTS (SELECT 1 FROM lexicon_ids x WHERE x.ingredient_id = lines.id)
+            OR EXISTS (SELECT 1 FROM catalog_ids x WHERE x.ingredient_id = lines.id) AS identified,
+        r.category
+    FROM lines
+    LEFT JOIN dietary_rules r
+        ON r.lexicon_id IN (SELECT x.lexicon_id FROM lexicon_ids x WHERE x.ingredient_id = lines.id)
+        OR r.canonical_ingredient_id IN (SELECT x.canonical_ingredient_id FROM catalog_ids x WHERE x.ingredient_id = lines.id)
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct LineCategoryRow {
+    ingredient_id: Uuid,
+    identified: bool,
+    category: Option<String>,
+}
+
+pub async fn find_rules(db: &DatabaseConnection) -> Result<Vec<DietaryRuleDto>, Error> {
+    let rules = DietaryRules::find()
+        .order_by_asc(Column::Category)
+        .order_by_asc(Column::LexiconId)
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?;
+    Ok(rules.into_iter().filter_map(to_dto).collect())
+}
+
+pub async fn create_rule(db: &DatabaseConnection, input: DietaryRuleInput) -> Result<DietaryRuleDto, Error> {
+    if input.lexicon_id.is_some() == input.canonical_ingredient_id.is_some() {
+        return Err(Error::BadRequest(json!({
+            "message": "A rule targets either a lexicon entry or a catalog ingredient",
+            "operation": "create_rule",
+            "entity": "dietary_rules",
+            "stage": "validation"
+        })));
+    }
+
+    let model = ActiveModel {
+        category: Set(input.category.as_str().to_string()),
+        lexicon_id: Set(input.lexicon_id),
+        canonical_ingredient_id: Set(input.canonical_ingredient_id),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| Error::BadRequest(json!({
+        "message": "Failed to insert dietary rule",
+        "operation": "create_rule",
+        "entity": "dietary_rules",
+        "category": input.category.as_str(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+
+    to_dto(model).ok_or(Error::InternalServerError(json!({"error": "Unknown dietary category"})))
+}
+
+pub async fn delete_rule(db: &DatabaseConnection, id: Uuid) -> Result<(), Error> {
+    let result = DietaryRules::delete_by_id(id).exec(db).await?;
+    if result.rows_affected == 0 {
+        return Err(Error::NotFound(json!({"error": "Dietary rule not found"})));
+    }
+    Ok(())
+}
+
+/// Adds the `(category, lexicon id)` rules not known yet.
+pub async fn add_lexicon_rules(db: &DatabaseConnection, rules: Vec<(DietaryCategory, i32)>) -> Result<u64, Error> {
+    if rules.is_empty() {
+        return Ok(0);
+    }
+
+    let models = rules.into_iter().map(|(category, lexicon_id)| ActiveModel {
+        category: Set(category.as_str().to_string()),
+        lexicon_id: Set(Some(lexicon_id)),
+        ..Default::default()
+    });
+
+    let created = DietaryRules::insert_many(models)
+        .on_conflict(
+            OnConflict::columns([Column::Category, Column::LexiconId])
+                .target_and_where(Column::LexiconId.is_not_null())
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to insert dietary rules",
+            "operation": "add_lexicon_rules",
+            "entity": "dietary_rules",
+            "error": e.to_string(),
+            "stage": "insert"
+        })))?;
+    Ok(created)
+}
+
+/// Recomputes the derived labels of a recipe. Overrides are kept, even when the label
+/// they force is no longer derived.
+pub async fn refresh_recipe<C: ConnectionTrait>(db: &C, recipe_id: Uuid) -> Result<(), Error> {
+    let rows = LineCategoryRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        RECIPE_LINE_CATEGORIES,
+        [recipe_id.into()],
+    ))
+    .all(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to match recipe ingredients against dietary rules",
+        "operation": "refresh_recipe",
+        "entity": "dietary_rules",
+        "recipe_id": recipe_id.to_string(),
+        "error": e.to_string(),
+        "stage": "select"
+    })))?;
+
+    let mut lines: HashMap<Uuid, LineCategories> = HashMap::new();
+    for row in rows {
+        let line = lines.entry(row.ingredient_id).or_default();
+        line.identified = row.identified;
+        if let Some(category) = row.category.as_deref().and_then(DietaryCategory::parse) {
+            line.categories.insert(category);
+        }
+    }
+    let lines: Vec<LineCategories> = lines.into_values().collect();
+    let labels = dietary::derive_labels(&lines);
+
+    recipe_dietary_labels::Entity::delete_many()
+        .filter(recipe_dietary_labels::Column::RecipeId.eq(recipe_id))
+        .filter(recipe_dietary_labels::Column::Override.is_null())
+        .exec(db)
+        .await?;
+    recipe_dietary_labels::Entity::update_many()
+        .col_expr(recipe_dietary_labels::Column::Derived, Expr::value(false))
+        .filter(recipe_dietary_labels::Column::RecipeId.eq(recipe_id))
+        .exec(db)
+        .await?;
+
+    if labels.is_empty() {
+        return Ok(());
+    }
+    let models = labels.iter().map(|label| recipe_dietary_labels::ActiveModel {
+        recipe_id: Set(recipe_id),
+        label: Set(label.as_str().to_string()),
+        derived: Set(true),
+        r#override: Set(None),
+    });
+    recipe_dietary_labels::Entity::insert_many(models)
+        .on_conflict(
+            OnConflict::columns([recipe_dietary_labels::Column::RecipeId, recipe_dietary_labels::Column::Label])
+                .update_column(recipe_dietary_labels::Column::Derived)
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to save dietary labels",
+            "operation": "refresh_recipe",
+            "entity": "recipe_dietary_labels",
+            "recipe_id": recipe_id.to_string(),
+            "error": e.to_string(),
+            "stage": "upsert"
+        })))?;
+    Ok(())
+}
+
+/// Recomputes the labels of every recipe, after the rules changed.
+pub async fn refresh_all(db: &DatabaseConnection) -> Result<u64, Error> {
+    let recipe_ids: Vec<Uuid> = recipes::Entity::find()
+        .select_only()
+        .column(recipes::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    for recipe_id in &recipe_ids {
+        refresh_recipe(db, *recipe_id).await?;
+    }
+    Ok(recipe_ids.len() as u64)
+}
+
+pub async fn find_by_recipe(db: &DatabaseConnection, recipe_id: Uuid) -> Result<RecipeDietaryLabelsDto, Error> {
+    let rows = recipe_dietary_labels::Entity::find()
+        .filter(recipe_dietary_labels::Column::RecipeId.eq(recipe_id))
+        .all(db)
+        .await?;
+
+    let mut dto = RecipeDietaryLabelsDto {
+        recipe_id,
+        labels: Vec::new(),
+        derived: Vec::new(),
+        overrides: Vec::new(),
+    };
+    for row in rows {
+        let Some(label) = DietaryLabel::parse(&row.label) else {
+            continue;
+        };
+        if row.r#override.unwrap_or(row.derived) {
+            dto.labels.push(label);
+        }
+        if row.derived {
+            dto.derived.push(label);
+        }
+        if let Some(applies) = row.r#override {
+            dto.overrides.push(DietaryLabelOverrideDto { label, applies });
+        }
+    }
+    dto.labels.sort();
+    dto.derived.sort();
+    dto.overrides.sort_by_key(|o| o.label);
+    Ok(dto)
+}
+
+/// Replaces the overrides of a recipe.
+pub async fn save_overrides<C: ConnectionTrait>(
+    db: &C,
+    recipe_id: Uuid,
+    overrides: &[DietaryLabelOverrideDto],
+) -> Result<(), Error> {
+    recipe_dietary_labels::Entity::delete_many()
+        .filter(recipe_dietary_labels::Column::RecipeId.eq(recipe_id))
+        .filter(recipe_dietary_labels::Column::Derived.eq(false))
+        .exec(db)
+        .await?;
+    recipe_dietary_labels::Entity::update_many()
+        .col_expr(recipe_dietary_labels::Column::Override, Expr::value(Option::<bool>::None))
+        .filter(recipe_dietary_labels::Column::RecipeId.eq(recipe_id))
+        .exec(db)
+        .await?;
+
+    if overrides.is_empty() {
+        return Ok(());
+    }
+    let models = overrides.iter().map(|o| recipe_dietary_labels::ActiveModel {
+        recipe_id: Set(recipe_id),
+        label: Set(o.label.as_str().to_string()),
+        derived: Set(false),
+        r#override: Set(Some(o.applies)),
+    });
+    recipe_dietary_labels::Entity::insert_many(models)
+        .on_conflict(
+            OnConflict::columns([recipe_dietary_labels::Column::RecipeId, recipe_dietary_labels::Column::Label])
+                .update_column(recipe_dietary_labels::Column::Override)
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to save dietary label overrides",
+            "operation": "save_overrides",
+            "entity": "recipe_dietary_labels",
+            "recipe_id": recipe_id.to_string(),
+            "error": e.to_string(),
+            "stage": "upsert"
+        })))?;
+    Ok(())
+}
+
+fn to_dto(rule: Model) -> Option<DietaryRuleDto> {
+    Some(DietaryRuleDto {
+        id: rule.id,
+        category: DietaryCategory::parse(&rule.category)?,
+        lexicon_id: rule.lexicon_id,
+        canonical_ingredient_id: rule.canonical_ingredient_id,
+    })
+}
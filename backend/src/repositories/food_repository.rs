@@ -0,0 +1,235 @@
+use entity::{canonical_ingredient_foods, canonical_ingredients};
+use entity::foods::{ActiveModel, Column, Entity as Foods, Model};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult, Order, QueryFilter, QueryOrder, QuerySelect,
+    Set, Statement,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::dto::nutrition_dto::{FoodDto, FoodMappingDto, FoodMappingInput};
+use crate::errors::Error;
+use crate::utils::food_composition::FoodRecord;
+
+/// Rows per insert, well under the bind parameter limit of Postgres.
+const IMPORT_BATCH: usize = 1000;
+
+/// Foods named like `$1`, in either language.
+const NAME_MATCHES: &str = "(foods.name_en ILIKE $1 OR foods.name_fr ILIKE $1)";
+
+/// Food of each line of recipe `$1`, through the catalog entry of the line or, when the
+/// line has none, through the entry seeded from the lexicon entry its text resolved to.
+const RECIPE_FOODS: &str = r#"
+    SELECT DISTINCT ON (i.id) i.id AS ingredient_id, m.food_id, m.grams_per_unit
+    FROM ingredient_groups ig
+    JOIN ingredients i ON i.ingredient_group_id = ig.id
+    JOIN canonical_ingredients c ON c.id = i.canonical_ingredient_id
+        OR (i.canonical_ingredient_id IS NULL AND c.lexicon_id IN (
+            SELECT l.lexicon_id
+            FROM ingredient_translations it
+            JOIN ingredient_lexicon_links l ON l.ingredient_translation_id = it.id
+            WHERE it.ingredient_id = i.id
+        ))
+    JOIN canonical_ingredient_foods m ON m.canonical_ingredient_id = c.id
+    WHERE ig.recipe_id = $1
+    ORDER BY i.id, c.id
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct IngredientFoodRow {
+    ingredient_id: Uuid,
+    food_id: Uuid,
+    grams_per_unit: Option<f64>,
+}
+
+/// Food mapped to a recipe line, with the weight of one unit when it is counted.
+#[derive(Debug, Clone)]
+pub struct IngredientFood {
+    pub food: Model,
+    pub grams_per_unit: Option<f64>,
+}
+
+/// Creates the foods of `source` or updates those already imported, matched on their code.
+pub async fn upsert(db: &DatabaseConnection, source: &str, records: Vec<FoodRecord>) -> Result<u64, Error> {
+    // A code appearing twice would make the upsert touch the same row twice; the last one wins.
+    let mut by_code: HashMap<String, FoodRecord> = HashMap::new();
+    for record in records {
+        by_code.insert(record.source_code.clone(), record);
+    }
+    let records: Vec<FoodRecord> = by_code.into_values().collect();
+
+    let mut imported = 0;
+    for batch in records.chunks(IMPORT_BATCH) {
+        let models = batch.iter().cloned().map(|record| ActiveModel {
+            source: Set(source.to_string()),
+            source_code: Set(record.source_code),
+            name_en: Set(record.name_en),
+            name_fr: Set(record.name_fr),
+            energy_kcal: Set(record.energy_kcal),
+            protein_g: Set(record.protein_g),
+            fat_g: Set(record.fat_g),
+            carbohydrates_g: Set(record.carbohydrates_g),
+            fiber_g: Set(record.fiber_g),
+            sodium_mg: Set(record.sodium_mg),
+            updated_at: Set(chrono::Utc::now().into()),
+            ..Default::default()
+        });
+
+        imported += Foods::insert_many(models)
+            .on_conflict(
+                OnConflict::columns([Column::Source, Column::SourceCode])
+                    .update_columns([
+                        Column::NameEn,
+                        Column::NameFr,
+                        Column::EnergyKcal,
+                        Column::ProteinG,
+                        Column::FatG,
+                        Column::CarbohydratesG,
+                        Column::FiberG,
+                        Column::SodiumMg,
+                        Column::UpdatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await
+            .map_err(|e| Error::InternalServerError(json!({
+                "message": "Failed to import foods",
+                "operation": "upsert",
+                "entity": "foods",
+                "source": source,
+                "error": e.to_string(),
+                "stage": "upsert"
+            })))?;
+    }
+    Ok(imported)
+}
+
+/// Shortest names first, so that "Onion, raw" comes before "Onion rings, breaded, fried".
+pub async fn search(db: &DatabaseConnection, search: Option<&str>, limit: u64) -> Result<Vec<FoodDto>, Error> {
+    let mut query = Foods::find();
+    if let Some(s) = search.map(str::trim).filter(|s| !s.is_empty()) {
+        query = query.filter(Expr::cust_with_values(NAME_MATCHES, [format!("%{}%", s)]));
+    }
+
+    let foods = query
+        .order_by(Expr::cust("length(coalesce(foods.name_en, foods.name_fr))"), Order::Asc)
+        .order_by_asc(Column::Id)
+        .limit(limit)
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to search foods",
+            "operation": "search",
+            "entity": "foods",
+            "search_term": search.unwrap_or(""),
+            "limit": limit,
+            "error": e.to_string(),
+            "stage": "search_query"
+        })))?;
+
+    Ok(foods.into_iter().map(FoodDto::from).collect())
+}
+
+pub async fn find_mapping(db: &DatabaseConnection, canonical_ingredient_id: Uuid) -> Result<FoodMappingDto, Error> {
+    let (mapping, food) = canonical_ingredient_foods::Entity::find_by_id(canonical_ingredient_id)
+        .find_also_related(Foods)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Food mapping not found"})))?;
+    let food = food.ok_or(Error::NotFound(json!({"error": "Food not found"})))?;
+
+    Ok(FoodMappingDto {
+        canonical_ingredient_id,
+        food: FoodDto::from(food),
+        grams_per_unit: mapping.grams_per_unit,
+    })
+}
+
+/// Points a catalog entry to a food, replacing its previous one.
+pub async fn save_mapping(
+    db: &DatabaseConnection,
+    canonical_ingredient_id: Uuid,
+    input: FoodMappingInput,
+) -> Result<FoodMappingDto, Error> {
+    canonical_ingredients::Entity::find_by_id(canonical_ingredient_id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Ingredient not found"})))?;
+    Foods::find_by_id(input.food_id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Food not found"})))?;
+
+    let model = canonical_ingredient_foods::ActiveModel {
+        canonical_ingredient_id: Set(canonical_ingredient_id),
+        food_id: Set(input.food_id),
+        grams_per_unit: Set(input.grams_per_unit),
+    };
+    canonical_ingredient_foods::Entity::insert(model)
+        .on_conflict(
+            OnConflict::column(canonical_ingredient_foods::Column::CanonicalIngredientId)
+                .update_columns([
+                    canonical_ingredient_foods::Column::FoodId,
+                    canonical_ingredient_foods::Column::GramsPerUnit,
+                ])
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to save food mapping",
+            "operation": "save_mapping",
+            "entity": "canonical_ingredient_foods",
+            "canonical_ingredient_id": canonical_ingredient_id.to_string(),
+            "food_id": input.food_id.to_string(),
+            "error": e.to_string(),
+            "stage": "upsert"
+        })))?;
+
+    find_mapping(db, canonical_ingredient_id).await
+}
+
+pub async fn delete_mapping(db: &DatabaseConnection, canonical_ingredient_id: Uuid) -> Result<(), Error> {
+    let result = canonical_ingredient_foods::Entity::delete_by_id(canonical_ingredient_id)
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(Error::NotFound(json!({"error": "Food mapping not found"})));
+    }
+    Ok(())
+}
+
+/// Foods of the lines of a recipe that lead to one, keyed by ingredient id.
+pub async fn find_by_recipe(db: &DatabaseConnection, recipe_id: Uuid) -> Result<HashMap<Uuid, IngredientFood>, Error> {
+    let rows = IngredientFoodRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        RECIPE_FOODS,
+        [recipe_id.into()],
+    ))
+    .all(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to fetch the foods of the recipe",
+        "operation": "find_by_recipe",
+        "entity": "canonical_ingredient_foods",
+        "recipe_id": recipe_id.to_string(),
+        "error": e.to_string(),
+        "stage": "select"
+    })))?;
+
+    let foods = Foods::find()
+        .filter(Column::Id.is_in(rows.iter().map(|r| r.food_id).collect::<Vec<_>>()))
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let food = foods.iter().find(|f| f.id == row.food_id)?.clone();
+            Some((row.ingredient_id, IngredientFood { food, grams_per_unit: row.grams_per_unit }))
+        })
+        .collect())
+}
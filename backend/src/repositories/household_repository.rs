@@ -0,0 +1,329 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use entity::{household_invitations, household_members, households, recipes, users};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult, PaginatorTrait,
+    QueryFilter, QueryOrder, Set, Statement, TransactionTrait,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::dto::household_dto::{HouseholdInvitationDto, HouseholdMemberDto, HouseholdRole, HouseholdSummaryDto};
+use crate::errors::Error;
+
+/// Households `$1` owns or belongs to, with the role held in each.
+const USER_HOUSEHOLDS: &str = r#"
+    SELECT
+        h.id,
+        h.name,
+        h.owner_id,
+        CASE WHEN h.owner_id = $1 THEN 'owner' ELSE m.role END AS role,
+        (SELECT count(*) + 1 FROM household_members hm WHERE hm.household_id = h.id) AS member_count,
+        (SELECT count(*) FROM recipes r WHERE r.household_id = h.id) AS recipe_count,
+        h.updated_at
+    FROM households h
+    LEFT JOIN household_members m ON m.household_id = h.id AND m.user_id = $1
+    WHERE h.owner_id = $1 OR m.user_id IS NOT NULL
+    ORDER BY h.name
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct HouseholdSummaryRow {
+    id: Uuid,
+    name: String,
+    owner_id: Uuid,
+    role: String,
+    member_count: i64,
+    recipe_count: i64,
+    updated_at: DateTime<FixedOffset>,
+}
+
+pub async fn find_for_user(db: &DatabaseConnection, user_id: Uuid) -> Result<Vec<HouseholdSummaryDto>, Error> {
+    let rows = HouseholdSummaryRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        USER_HOUSEHOLDS,
+        [user_id.into()],
+    ))
+    .all(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to fetch households",
+        "operation": "find_for_user",
+        "entity": "households",
+        "user_id": user_id.to_string(),
+        "error": e.to_string(),
+        "stage": "select"
+    })))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| HouseholdSummaryDto {
+            id: r.id,
+            name: r.name,
+            owner_id: r.owner_id,
+            role: HouseholdRole::parse(&r.role).unwrap_or(HouseholdRole::Viewer),
+            member_count: r.member_count,
+            recipe_count: r.recipe_count,
+            updated_at: r.updated_at.with_timezone(&Utc),
+        })
+        .collect())
+}
+
+pub async fn find_by_id(db: &DatabaseConnection, id: Uuid) -> Result<households::Model, Error> {
+    households::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Household not found"})))
+}
+
+/// Role of the user in the household, `None` when they are not part of it.
+pub async fn find_role(db: &DatabaseConnection, id: Uuid, user_id: Uuid) -> Result<Option<HouseholdRole>, Error> {
+    let household = households::Entity::find_by_id(id).one(db).await?;
+    match household {
+        Some(household) if household.owner_id == user_id => Ok(Some(HouseholdRole::Owner)),
+        Some(_) => {
+            let member = household_members::Entity::find_by_id((id, user_id)).one(db).await?;
+            Ok(member.and_then(|m| HouseholdRole::parse(&m.role)))
+        }
+        None => Ok(None),
+    }
+}
+
+pub async fn create(db: &DatabaseConnection, owner_id: Uuid, name: String) -> Result<households::Model, Error> {
+    let household = households::ActiveModel {
+        owner_id: Set(owner_id),
+        name: Set(name.trim().to_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to insert household",
+        "operation": "create",
+        "entity": "households",
+        "owner_id": owner_id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+    Ok(household)
+}
+
+pub async fn rename(db: &DatabaseConnection, household: households::Model, name: String) -> Result<households::Model, Error> {
+    let mut active: households::ActiveModel = household.into();
+    active.name = Set(name.trim().to_string());
+    active.updated_at = Set(Utc::now().into());
+    let household = active.update(db).await?;
+    Ok(household)
+}
+
+pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), Error> {
+    households::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// The owner first, then the members in the order they joined.
+pub async fn find_members(db: &DatabaseConnection, household: &households::Model) -> Result<Vec<HouseholdMemberDto>, Error> {
+    let mut dtos = Vec::new();
+    if let Some(owner) = users::Entity::find_by_id(household.owner_id).one(db).await? {
+        dtos.push(HouseholdMemberDto {
+            user_id: owner.id,
+            username: owner.username,
+            avatar_url: owner.avatar_url,
+            role: HouseholdRole::Owner,
+            joined_at: household.created_at.with_timezone(&Utc),
+        });
+    }
+
+    let members = household_members::Entity::find()
+        .filter(household_members::Column::HouseholdId.eq(household.id))
+        .order_by_asc(household_members::Column::CreatedAt)
+        .find_also_related(users::Entity)
+        .all(db)
+        .await?;
+    dtos.extend(members.into_iter().filter_map(|(member, user)| {
+        let user = user?;
+        Some(HouseholdMemberDto {
+            user_id: user.id,
+            username: user.username,
+            avatar_url: user.avatar_url,
+            role: HouseholdRole::parse(&member.role)?,
+            joined_at: member.created_at.with_timezone(&Utc),
+        })
+    }));
+    Ok(dtos)
+}
+
+pub async fn set_member_role(db: &DatabaseConnection, id: Uuid, user_id: Uuid, role: HouseholdRole) -> Result<(), Error> {
+    let result = household_members::Entity::update_many()
+        .col_expr(household_members::Column::Role, Expr::value(role.as_str()))
+        .filter(household_members::Column::HouseholdId.eq(id))
+        .filter(household_members::Column::UserId.eq(user_id))
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(Error::NotFound(json!({"error": "Member not found"})));
+    }
+    Ok(())
+}
+
+pub async fn remove_member(db: &DatabaseConnection, id: Uuid, user_id: Uuid) -> Result<(), Error> {
+    let result = household_members::Entity::delete_by_id((id, user_id)).exec(db).await?;
+    if result.rows_affected == 0 {
+        return Err(Error::NotFound(json!({"error": "Member not found"})));
+    }
+    Ok(())
+}
+
+/// Invitations still waiting for an answer, newest first.
+pub async fn find_invitations(db: &DatabaseConnection, id: Uuid) -> Result<Vec<HouseholdInvitationDto>, Error> {
+    let invitations = household_invitations::Entity::find()
+        .filter(household_invitations::Column::HouseholdId.eq(id))
+        .filter(household_invitations::Column::AcceptedAt.is_null())
+        .filter(household_invitations::Column::ExpiresAt.gt(Utc::now()))
+        .order_by_desc(household_invitations::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    Ok(invitations
+        .into_iter()
+        .filter_map(|i| {
+            Some(HouseholdInvitationDto {
+                id: i.id,
+                role: HouseholdRole::parse(&i.role)?,
+                email: i.email,
+                expires_at: i.expires_at.with_timezone(&Utc),
+                created_at: i.created_at.with_timezone(&Utc),
+            })
+        })
+        .collect())
+}
+
+pub async fn create_invitation(
+    db: &DatabaseConnection,
+    id: Uuid,
+    invited_by: Uuid,
+    email: &str,
+    role: HouseholdRole,
+    expires_at: DateTime<Utc>,
+) -> Result<household_invitations::Model, Error> {
+    let invitation = household_invitations::ActiveModel {
+        household_id: Set(id),
+        invited_by: Set(invited_by),
+        email: Set(email.trim().to_lowercase()),
+        role: Set(role.as_str().to_string()),
+        token: Set(Uuid::new_v4()),
+        expires_at: Set(expires_at.into()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to insert household invitation",
+        "operation": "create_invitation",
+        "entity": "household_invitations",
+        "household_id": id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+    Ok(invitation)
+}
+
+/// The invitation behind `token`, unless it was already accepted or has expired.
+pub async fn find_pending_invitation(
+    db: &DatabaseConnection,
+    token: Uuid,
+) -> Result<household_invitations::Model, Error> {
+    household_invitations::Entity::find()
+        .filter(household_invitations::Column::Token.eq(token))
+        .filter(household_invitations::Column::AcceptedAt.is_null())
+        .filter(household_invitations::Column::ExpiresAt.gt(Utc::now()))
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Invitation not found"})))
+}
+
+/// Makes the user a member with the role of the invitation; someone already in the
+/// household takes that role instead.
+pub async fn accept_invitation(
+    db: &DatabaseConnection,
+    invitation: household_invitations::Model,
+    user_id: Uuid,
+) -> Result<(), Error> {
+    let txn = db.begin().await?;
+    household_members::Entity::insert(household_members::ActiveModel {
+        household_id: Set(invitation.household_id),
+        user_id: Set(user_id),
+        role: Set(invitation.role.clone()),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([household_members::Column::HouseholdId, household_members::Column::UserId])
+            .update_column(household_members::Column::Role)
+            .to_owned(),
+    )
+    .exec_without_returning(&txn)
+    .await?;
+
+    let mut active: household_invitations::ActiveModel = invitation.into();
+    active.accepted_at = Set(Some(Utc::now().into()));
+    active.update(&txn).await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Invitations sent for the household since `since`, revoked ones included.
+pub async fn count_invitations_since(db: &DatabaseConnection, id: Uuid, since: DateTime<Utc>) -> Result<u64, Error> {
+    let count = household_invitations::Entity::find()
+        .filter(household_invitations::Column::HouseholdId.eq(id))
+        .filter(household_invitations::Column::CreatedAt.gte(since))
+        .count(db)
+        .await?;
+    Ok(count)
+}
+
+/// Expires a pending invitation; it is kept so that it still counts towards the
+/// invitations sent by the household.
+pub async fn revoke_invitation(db: &DatabaseConnection, id: Uuid, invitation_id: Uuid) -> Result<(), Error> {
+    let result = household_invitations::Entity::update_many()
+        .col_expr(household_invitations::Column::ExpiresAt, Expr::value(Utc::now()))
+        .filter(household_invitations::Column::Id.eq(invitation_id))
+        .filter(household_invitations::Column::HouseholdId.eq(id))
+        .filter(household_invitations::Column::AcceptedAt.is_null())
+        .filter(household_invitations::Column::ExpiresAt.gt(Utc::now()))
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(Error::NotFound(json!({"error": "Invitation not found"})));
+    }
+    Ok(())
+}
+
+pub async fn find_recipes(db: &DatabaseConnection, id: Uuid) -> Result<Vec<recipes::Model>, Error> {
+    let recipes = recipes::Entity::find()
+        .filter(recipes::Column::HouseholdId.eq(id))
+        .order_by_desc(recipes::Column::UpdatedAt)
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to fetch household recipes",
+            "operation": "find_recipes",
+            "entity": "recipes",
+            "household_id": id.to_string(),
+            "error": e.to_string(),
+            "stage": "select"
+        })))?;
+    Ok(recipes)
+}
+
+pub async fn set_recipe_household(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    household_id: Option<Uuid>,
+) -> Result<(), Error> {
+    recipes::Entity::update_many()
+        .col_expr(recipes::Column::HouseholdId, Expr::value(household_id))
+        .filter(recipes::Column::Id.eq(recipe_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
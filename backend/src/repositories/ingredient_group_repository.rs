@@ -136,9 +136,13 @@ pub async fn find_by_recipe(
                 id: ing.id,
                 name,
                 quantity: ing.quantity,
+                display_quantity: None,
                 note,
                 unit,
                 position: ing.position,
+                equivalent: None,
+                density_status: None,
+                canonical_ingredient_id: ing.canonical_ingredient_id,
             });
     }
 
@@ -226,6 +230,7 @@ pub async fn find_all_by_recipe(
                 unit_id: ing.unit_id,
                 unit,
                 translations: translations_dto,
+                canonical_ingredient_id: ing.canonical_ingredient_id,
             });
     }
 
@@ -338,6 +343,7 @@ pub async fn update(
                     am.quantity = Set(ing_in.quantity);
                     am.unit_id = Set(ing_in.unit_id);
                     am.position = Set(ing_in.position);
+                    am.canonical_ingredient_id = Set(ing_in.canonical_ingredient_id);
                     am.update(txn).await?.id
                 }
                 None => {
@@ -346,6 +352,7 @@ pub async fn update(
                         quantity: Set(ing_in.quantity),
                         unit_id: Set(ing_in.unit_id),
                         position: Set(ing_in.position),
+                        canonical_ingredient_id: Set(ing_in.canonical_ingredient_id),
                         ..Default::default()
                     }
                     .insert(txn)
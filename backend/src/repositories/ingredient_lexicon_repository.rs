@@ -0,0 +1,217 @@
+use entity::ingredient_lexicon_links::{ActiveModel, Column, Entity as IngredientLexiconLinks};
+use entity::{ingredient_groups, ingredient_translations, ingredients};
+use migration::JoinType;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult, QueryFilter, QuerySelect, RelationTrait, Set, Statement};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::errors::Error;
+
+/// One ingredient line of a public recipe, with the lexicon entry of its text.
+#[derive(Debug, FromQueryResult)]
+pub struct RecipeIngredientLink {
+    pub recipe_id: Uuid,
+    pub ingredient_id: Uuid,
+    pub language_code: String,
+    pub data: String,
+    pub lexicon_id: Option<i32>,
+}
+
+/// How many of its distinct ingredients a public recipe has on hand.
+#[derive(Debug, FromQueryResult)]
+pub struct RecipeCoverage {
+    pub recipe_id: Uuid,
+    pub matched: i64,
+    pub total: i64,
+}
+
+/// Ingredient lines of the public recipes `$1`.
+const PUBLIC_RECIPE_INGREDIENTS: &str = r#"
+    SELECT ig.recipe_id, i.id AS ingredient_id, it.language_code, it.data, l.lexicon_id
+    FROM recipes r
+    JOIN ingredient_groups ig ON ig.recipe_id = r.id
+    JOIN ingredients i ON i.ingredient_group_id = ig.id
+    JOIN ingredient_translations it ON it.ingredient_id = i.id
+    LEFT JOIN ingredient_lexicon_links l ON l.ingredient_translation_id = it.id
+    WHERE NOT r.is_private AND r.id = ANY($1)
+    ORDER BY ig.recipe_id, ig.position, i.position
+"#;
+
+/// Public recipes listing one of the lexicon entries `$1`.
+const PUBLIC_RECIPES_USING: &str = r#"
+    SELECT DISTINCT ig.recipe_id
+    FROM recipes r
+    JOIN ingredient_groups ig ON ig.recipe_id = r.id
+    JOIN ingredients i ON i.ingredient_group_id = ig.id
+    JOIN ingredient_translations it ON it.ingredient_id = i.id
+    JOIN ingredient_lexicon_links l ON l.ingredient_translation_id = it.id
+    WHERE NOT r.is_private AND l.lexicon_id = ANY($1)
+"#;
+
+/// Public recipes scored against the lexicon entries on hand `$1`, staples `$2` left
+/// out. An ingredient counts once per lexicon entry, and every line without one counts
+/// as missing. Keeps a coverage of at least `$3`, best first, up to `$4` recipes.
+const PUBLIC_RECIPE_COVERAGE: &str = r#"
+    WITH lines AS (
+        SELECT ig.recipe_id, i.id, MIN(l.lexicon_id) AS lexicon_id
+        FROM recipes r
+        JOIN ingredient_groups ig ON ig.recipe_id = r.id
+        JOIN ingredients i ON i.ingredient_group_id = ig.id
+        JOIN ingredient_translations it ON it.ingredient_id = i.id
+        LEFT JOIN ingredient_lexicon_links l ON l.ingredient_translation_id = it.id
+        WHERE NOT r.is_private
+        GROUP BY ig.recipe_id, i.id
+    ),
+    scores AS (
+        SELECT
+            recipe_id,
+            COUNT(DISTINCT lexicon_id) FILTER (WHERE lexicon_id = ANY($1)) AS matched,
+            COUNT(DISTINCT lexicon_id) + COUNT(*) FILTER (WHERE lexicon_id IS NULL) AS total
+        FROM lines
+        WHERE lexicon_id IS NULL OR lexicon_id <> ALL($2)
+        GROUP BY recipe_id
+    )
+    SELECT recipe_id, matched, total
+    FROM scores
+    WHERE matched > 0 AND matched::real / total >= $3
+    ORDER BY matched::real / total DESC, total - matched, matched DESC, recipe_id
+    LIMIT $4
+"#;
+
+/// Ingredient translations never linked, or edited since: `(translation id, text)`.
+/// Limited to the lines of `recipe_id` when given.
+pub async fn find_stale(db: &DatabaseConnection, recipe_id: Option<Uuid>) -> Result<Vec<(Uuid, String)>, Error> {
+    let mut query = ingredient_translations::Entity::find()
+        .select_only()
+        .column(ingredient_translations::Column::Id)
+        .column(ingredient_translations::Column::Data)
+        .join(JoinType::LeftJoin, ingredient_translations::Relation::IngredientLexiconLinks.def())
+        .filter(
+            Condition::any()
+                .add(Column::IngredientTranslationId.is_null())
+                .add(Expr::cust("ingredient_lexicon_links.source_text <> ingredient_translations.data")),
+        );
+    if let Some(recipe_id) = recipe_id {
+        query = query
+            .join(JoinType::InnerJoin, ingredient_translations::Relation::Ingredients.def())
+            .join(JoinType::InnerJoin, ingredients::Relation::IngredientGroups.def())
+            .filter(ingredient_groups::Column::RecipeId.eq(recipe_id));
+    }
+
+    query
+        .into_tuple::<(Uuid, String)>()
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to fetch unlinked ingredient translations",
+            "operation": "find_stale",
+            "entity": "ingredient_lexicon_links",
+            "recipe_id": recipe_id.map(|id| id.to_string()),
+            "error": e.to_string(),
+            "stage": "select"
+        })))
+}
+
+/// Stores the lexicon entry resolved for each translation, replacing older links.
+pub async fn save_links(
+    db: &DatabaseConnection,
+    links: Vec<(Uuid, Option<i32>, String)>,
+) -> Result<(), Error> {
+    if links.is_empty() {
+        return Ok(());
+    }
+
+    let models = links.into_iter().map(|(translation_id, lexicon_id, source_text)| ActiveModel {
+        ingredient_translation_id: Set(translation_id),
+        lexicon_id: Set(lexicon_id),
+        source_text: Set(source_text),
+    });
+
+    IngredientLexiconLinks::insert_many(models)
+        .on_conflict(
+            OnConflict::column(Column::IngredientTranslationId)
+                .update_columns([Column::LexiconId, Column::SourceText])
+                .to_owned(),
+        )
+        .exec(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to save ingredient lexicon links",
+            "operation": "save_links",
+            "entity": "ingredient_lexicon_links",
+            "error": e.to_string(),
+            "stage": "upsert"
+        })))?;
+
+    Ok(())
+}
+
+/// Every translated ingredient line of the given public recipes, grouped by recipe.
+pub async fn find_public_recipe_ingredients(
+    db: &DatabaseConnection,
+    recipe_ids: &[Uuid],
+) -> Result<Vec<RecipeIngredientLink>, Error> {
+    RecipeIngredientLink::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        PUBLIC_RECIPE_INGREDIENTS,
+        [recipe_ids.to_vec().into()],
+    ))
+    .all(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to fetch recipe ingredients",
+        "operation": "find_public_recipe_ingredients",
+        "entity": "ingredient_lexicon_links",
+        "error": e.to_string(),
+        "stage": "select"
+    })))
+}
+
+/// Ids of the public recipes listing one of `lexicon_ids`.
+pub async fn find_public_recipes_using(db: &DatabaseConnection, lexicon_ids: &[i32]) -> Result<Vec<Uuid>, Error> {
+    #[derive(FromQueryResult)]
+    struct RecipeId {
+        recipe_id: Uuid,
+    }
+
+    let rows = RecipeId::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        PUBLIC_RECIPES_USING,
+        [lexicon_ids.to_vec().into()],
+    ))
+    .all(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to fetch recipes using lexicon entries",
+        "operation": "find_public_recipes_using",
+        "entity": "ingredient_lexicon_links",
+        "error": e.to_string(),
+        "stage": "select"
+    })))?;
+    Ok(rows.into_iter().map(|r| r.recipe_id).collect())
+}
+
+/// Best covered public recipes for the lexicon entries on hand, scored in the database.
+pub async fn find_public_recipe_coverage(
+    db: &DatabaseConnection,
+    on_hand: &[i32],
+    staples: &[i32],
+    min_coverage: f32,
+    limit: u64,
+) -> Result<Vec<RecipeCoverage>, Error> {
+    RecipeCoverage::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        PUBLIC_RECIPE_COVERAGE,
+        [on_hand.to_vec().into(), staples.to_vec().into(), min_coverage.into(), (limit as i64).into()],
+    ))
+    .all(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to score recipes against the pantry",
+        "operation": "find_public_recipe_coverage",
+        "entity": "ingredient_lexicon_links",
+        "error": e.to_string(),
+        "stage": "select"
+    })))
+}
@@ -0,0 +1,199 @@
+use chrono::{NaiveDate, Utc};
+use entity::{meal_plan_feeds, meal_plans, recipes};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, QueryFilter, QueryOrder,
+    Set, Statement, TransactionTrait,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::dto::meal_plan_dto::{MealPlanInput, MealSlot};
+use crate::errors::Error;
+
+/// Copies the plans of `$1` dated `$2` to `$3` forward by `$4` days.
+const COPY_RANGE: &str = r#"
+    INSERT INTO meal_plans (user_id, plan_date, slot, recipe_id, recipe_title, servings, note)
+    SELECT user_id, plan_date + $4, slot, recipe_id, recipe_title, servings, note
+    FROM meal_plans
+    WHERE user_id = $1 AND plan_date BETWEEN $2 AND $3
+    ORDER BY plan_date, created_at
+"#;
+
+/// Plans of the user between both dates included, with their recipe when it still exists.
+pub async fn find_range(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<(meal_plans::Model, Option<recipes::Model>)>, Error> {
+    let plans = meal_plans::Entity::find()
+        .filter(meal_plans::Column::UserId.eq(user_id))
+        .filter(meal_plans::Column::PlanDate.between(start, end))
+        .order_by_asc(meal_plans::Column::PlanDate)
+        .order_by_asc(meal_plans::Column::CreatedAt)
+        .find_also_related(recipes::Entity)
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to fetch meal plans",
+            "operation": "find_range",
+            "entity": "meal_plans",
+            "user_id": user_id.to_string(),
+            "error": e.to_string(),
+            "stage": "select"
+        })))?;
+    Ok(plans)
+}
+
+pub async fn find_by_id(db: &DatabaseConnection, id: Uuid) -> Result<meal_plans::Model, Error> {
+    meal_plans::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Meal plan not found"})))
+}
+
+pub async fn find_recipe(db: &DatabaseConnection, recipe_id: Uuid) -> Result<Option<recipes::Model>, Error> {
+    let recipe = recipes::Entity::find_by_id(recipe_id).one(db).await?;
+    Ok(recipe)
+}
+
+pub async fn create(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    input: MealPlanInput,
+    recipe_title: Option<String>,
+    servings: i32,
+) -> Result<meal_plans::Model, Error> {
+    let plan = meal_plans::ActiveModel {
+        user_id: Set(user_id),
+        plan_date: Set(input.date),
+        slot: Set(input.slot.as_str().to_string()),
+        recipe_id: Set(input.recipe_id),
+        recipe_title: Set(recipe_title),
+        servings: Set(servings),
+        note: Set(input.note),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to insert meal plan",
+        "operation": "create",
+        "entity": "meal_plans",
+        "user_id": user_id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+    Ok(plan)
+}
+
+pub async fn update(
+    db: &DatabaseConnection,
+    plan: meal_plans::Model,
+    input: MealPlanInput,
+    recipe_title: Option<String>,
+    servings: i32,
+) -> Result<meal_plans::Model, Error> {
+    let mut active: meal_plans::ActiveModel = plan.into();
+    active.plan_date = Set(input.date);
+    active.slot = Set(input.slot.as_str().to_string());
+    active.recipe_id = Set(input.recipe_id);
+    active.recipe_title = Set(recipe_title);
+    active.servings = Set(servings);
+    active.note = Set(input.note);
+    active.updated_at = Set(Utc::now().into());
+    let plan = active.update(db).await?;
+    Ok(plan)
+}
+
+pub async fn move_to(
+    db: &DatabaseConnection,
+    plan: meal_plans::Model,
+    date: NaiveDate,
+    slot: MealSlot,
+) -> Result<meal_plans::Model, Error> {
+    let mut active: meal_plans::ActiveModel = plan.into();
+    active.plan_date = Set(date);
+    active.slot = Set(slot.as_str().to_string());
+    active.updated_at = Set(Utc::now().into());
+    let plan = active.update(db).await?;
+    Ok(plan)
+}
+
+pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), Error> {
+    meal_plans::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// Copies the seven days from `from_start` to the seven days from `to_start`,
+/// clearing those first when `replace` is set. Returns how many plans were copied.
+pub async fn copy_week(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    from_start: NaiveDate,
+    to_start: NaiveDate,
+    replace: bool,
+) -> Result<u64, Error> {
+    let from_end = from_start + chrono::Duration::days(6);
+    let to_end = to_start + chrono::Duration::days(6);
+    let offset = (to_start - from_start).num_days() as i32;
+
+    let txn = db.begin().await?;
+    if replace {
+        meal_plans::Entity::delete_many()
+            .filter(meal_plans::Column::UserId.eq(user_id))
+            .filter(meal_plans::Column::PlanDate.between(to_start, to_end))
+            .exec(&txn)
+            .await?;
+    }
+
+    let result = txn
+        .execute_raw(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            COPY_RANGE,
+            [user_id.into(), from_start.into(), from_end.into(), offset.into()],
+        ))
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to copy meal plans",
+            "operation": "copy_week",
+            "entity": "meal_plans",
+            "user_id": user_id.to_string(),
+            "error": e.to_string(),
+            "stage": "insert"
+        })))?;
+    txn.commit().await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn find_feed_by_token(db: &DatabaseConnection, token: &str) -> Result<meal_plan_feeds::Model, Error> {
+    meal_plan_feeds::Entity::find()
+        .filter(meal_plan_feeds::Column::Token.eq(token))
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Calendar feed not found"})))
+}
+
+/// Sets the token of the user's feed, replacing the previous one.
+pub async fn save_feed(db: &DatabaseConnection, user_id: Uuid, token: String) -> Result<(), Error> {
+    meal_plan_feeds::Entity::insert(meal_plan_feeds::ActiveModel {
+        user_id: Set(user_id),
+        token: Set(token),
+        created_at: Set(Utc::now().into()),
+    })
+    .on_conflict(
+        OnConflict::column(meal_plan_feeds::Column::UserId)
+            .update_columns([meal_plan_feeds::Column::Token, meal_plan_feeds::Column::CreatedAt])
+            .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await?;
+    Ok(())
+}
+
+pub async fn delete_feed(db: &DatabaseConnection, user_id: Uuid) -> Result<(), Error> {
+    meal_plan_feeds::Entity::delete_by_id(user_id).exec(db).await?;
+    Ok(())
+}
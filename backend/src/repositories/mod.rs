@@ -12,6 +12,18 @@ pub mod step_repository;
 pub mod language_repository;
 pub mod recipe_version_repository;
 pub mod unit_repository;
+pub mod density_repository;
 pub mod notification_template_repository;
 pub mod notification_repository;
+pub mod ingredient_lexicon_repository;
+pub mod canonical_ingredient_repository;
 
+pub mod food_repository;
+pub mod dietary_repository;
+pub mod shopping_list_repository;
+pub mod meal_plan_repository;
+pub mod pantry_repository;
+pub mod collection_repository;
+pub mod share_link_repository;
+pub mod household_repository;
+pub mod collaborator_repository;
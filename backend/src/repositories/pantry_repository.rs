@@ -0,0 +1,136 @@
+use chrono::{NaiveDate, Utc};
+use entity::pantry_items;
+use sea_orm::sea_query::{Expr, NullOrdering};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::dto::pantry_dto::{PantryItemInput, PantryLocation};
+use crate::errors::Error;
+
+/// Items of the user, soonest best-before first and undated ones last.
+pub async fn find_by_user(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    location: Option<PantryLocation>,
+) -> Result<Vec<pantry_items::Model>, Error> {
+    let mut query = pantry_items::Entity::find().filter(pantry_items::Column::UserId.eq(user_id));
+    if let Some(location) = location {
+        query = query.filter(pantry_items::Column::Location.eq(location.as_str()));
+    }
+
+    let items = query
+        .order_by_with_nulls(pantry_items::Column::BestBefore, sea_orm::Order::Asc, NullOrdering::Last)
+        .order_by_asc(pantry_items::Column::Name)
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to fetch pantry items",
+            "operation": "find_by_user",
+            "entity": "pantry_items",
+            "user_id": user_id.to_string(),
+            "error": e.to_string(),
+            "stage": "select"
+        })))?;
+    Ok(items)
+}
+
+/// Items of the user best before `until` at the latest, expired ones included.
+pub async fn find_expiring(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    until: NaiveDate,
+) -> Result<Vec<pantry_items::Model>, Error> {
+    let items = pantry_items::Entity::find()
+        .filter(pantry_items::Column::UserId.eq(user_id))
+        .filter(pantry_items::Column::BestBefore.lte(until))
+        .order_by_asc(pantry_items::Column::BestBefore)
+        .order_by_asc(pantry_items::Column::Name)
+        .all(db)
+        .await?;
+    Ok(items)
+}
+
+/// Items of every user coming to their best-before date between both dates whose
+/// owner was not told yet.
+pub async fn find_due_reminders(
+    db: &DatabaseConnection,
+    from: NaiveDate,
+    until: NaiveDate,
+) -> Result<Vec<pantry_items::Model>, Error> {
+    let items = pantry_items::Entity::find()
+        .filter(pantry_items::Column::BestBefore.between(from, until))
+        .filter(pantry_items::Column::ExpiryNotifiedAt.is_null())
+        .order_by_asc(pantry_items::Column::UserId)
+        .order_by_asc(pantry_items::Column::BestBefore)
+        .all(db)
+        .await?;
+    Ok(items)
+}
+
+pub async fn mark_notified(db: &DatabaseConnection, id: Uuid) -> Result<(), Error> {
+    pantry_items::Entity::update_many()
+        .col_expr(pantry_items::Column::ExpiryNotifiedAt, Expr::current_timestamp())
+        .filter(pantry_items::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+pub async fn find_by_id(db: &DatabaseConnection, id: Uuid) -> Result<pantry_items::Model, Error> {
+    pantry_items::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Pantry item not found"})))
+}
+
+pub async fn create(db: &DatabaseConnection, user_id: Uuid, input: PantryItemInput) -> Result<pantry_items::Model, Error> {
+    let item = pantry_items::ActiveModel {
+        user_id: Set(user_id),
+        name: Set(input.name.trim().to_string()),
+        canonical_ingredient_id: Set(input.canonical_ingredient_id),
+        quantity: Set(input.quantity),
+        unit_id: Set(input.unit_id),
+        location: Set(input.location.as_str().to_string()),
+        best_before: Set(input.best_before),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to insert pantry item",
+        "operation": "create",
+        "entity": "pantry_items",
+        "user_id": user_id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+    Ok(item)
+}
+
+/// A new best-before date makes the item due for a reminder again.
+pub async fn update(
+    db: &DatabaseConnection,
+    item: pantry_items::Model,
+    input: PantryItemInput,
+) -> Result<pantry_items::Model, Error> {
+    let date_changed = item.best_before != input.best_before;
+    let mut active: pantry_items::ActiveModel = item.into();
+    active.name = Set(input.name.trim().to_string());
+    active.canonical_ingredient_id = Set(input.canonical_ingredient_id);
+    active.quantity = Set(input.quantity);
+    active.unit_id = Set(input.unit_id);
+    active.location = Set(input.location.as_str().to_string());
+    active.best_before = Set(input.best_before);
+    if date_changed {
+        active.expiry_notified_at = Set(None);
+    }
+    active.updated_at = Set(Utc::now().into());
+    let item = active.update(db).await?;
+    Ok(item)
+}
+
+pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), Error> {
+    pantry_items::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
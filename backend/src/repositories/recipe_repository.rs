@@ -1,24 +1,39 @@
+use crate::dto::collaborator_dto::RecipeCollaboratorRole;
 use crate::dto::comment_dto::{CommentDto, CreateCommentDto};
-use crate::dto::ingredient_group_dto::IngredientGroupViewDto;
-use crate::dto::recipe_dto::{CreateRecipeInput, EditRecipeInput, GetAllRecipesByPageQuery, RecipeFilter, RecipeFilterByPage, RecipeViewDto};
+use crate::dto::dietary_dto::DietaryLabel;
+use crate::dto::facet_dto::{LanguageFacetDto, RangeFacetDto, RecipeFacetsDto, TagFacetDto};
+use crate::dto::ingredient_group_dto::{IngredientGroupInput, IngredientGroupViewDto};
+use crate::dto::recipe_dto::{CreateRecipeInput, EditRecipeInput, GetAllRecipesByPageQuery, RecipeEditorDto, RecipeFilter, RecipeFilterByPage, RecipeSearchMatchDto, RecipeSort, RecipeViewDto, SortDirection};
 use crate::dto::recipe_rating_dto::RecipeRatingDto;
-use crate::dto::step_group_dto::StepGroupViewDto;
+use crate::dto::step_group_dto::{StepGroupInput, StepGroupViewDto};
 use crate::dto::tag_dto::{InputTag, TagDto};
 use crate::errors::Error;
-use crate::repositories::{ingredient_group_repository, step_group_repository, tag_repository};
+use crate::utils::pagination::{RecipeCursor, SortValue};
+use crate::repositories::{ingredient_group_repository, recipe_version_repository, step_group_repository, tag_repository};
 use chrono::Utc;
-use entity::{favorites, ingredient_groups, ingredient_translations, ingredients, recipe_analytics, recipe_comments, recipe_ratings, recipe_tags, recipe_translations, recipes, step_groups, steps, users};
+use entity::{favorites, household_members, households, ingredient_groups, ingredients, recipe_analytics, recipe_collaborators, recipe_comments, recipe_prints, recipe_ratings, recipe_tags, recipe_translations, recipes, step_groups, steps, tags, users};
 use futures_util::TryFutureExt;
 use migration::JoinType;
-use sea_orm::{ActiveModelTrait, ColumnTrait, DeleteResult, FromQueryResult, PaginatorTrait, SelectExt, Set, TransactionError, TransactionTrait};
+use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, DbBackend, DbErr, DeleteResult, FromQueryResult, PaginatorTrait, Select, SelectExt, Set, Statement, TransactionError, TransactionTrait, Value};
 use sea_orm::{DatabaseConnection, EntityTrait};
-use sea_orm::{ExprTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait};
+use sea_orm::{Order, QueryFilter, QueryOrder, QuerySelect, RelationTrait};
 use serde_json::json;
 use std::collections::HashMap;
 use std::ops::Deref;
-use sea_orm::sea_query::Expr;
+use sea_orm::sea_query::{Expr, Query, SelectStatement};
 use uuid::Uuid;
 
+/// Ancestors of recipe `$1`, its parent first. The depth cap only guards against a
+/// corrupted chain; forks always point at an older recipe.
+const LINEAGE: &str = r#"
+    WITH RECURSIVE lineage AS (
+        SELECT r.forked_from AS id, 1 AS depth FROM recipes r WHERE r.id = $1
+        UNION ALL
+        SELECT p.forked_from, l.depth + 1 FROM lineage l JOIN recipes p ON p.id = l.id WHERE l.depth < 50
+    )
+    SELECT r.* FROM lineage l JOIN recipes r ON r.id = l.id ORDER BY l.depth
+"#;
+
 pub async fn find_all(db: &DatabaseConnection) -> Result<Vec<recipes::Model>, Error> {
     recipes::Entity::find()
         .all(db)
@@ -36,62 +51,17 @@ pub async fn find_by_query(
     db: &DatabaseConnection,
     filter: RecipeFilter,
     lang_code: &str,
+    viewer_id: Option<Uuid>,
 ) -> Result<Option<Vec<recipes::Model>>, Error> {
     let mut query = recipes::Entity::find();
 
     if !filter.scope {
-        query = query.filter(recipes::Column::IsPrivate.eq(false));
-    }
-
-    if let Some(s) = &filter.search {
-        if !s.trim().is_empty() {
-            let pattern = format!("%{}%", s);
-            query = query
-                .join(
-                    JoinType::LeftJoin,
-                    recipes::Relation::RecipeTranslations.def(),
-                )
-                .filter(
-                    recipe_translations::Column::LanguageCode.eq(lang_code).and(
-                        recipe_translations::Column::Title
-                            .like(&pattern)
-                            .or(recipe_translations::Column::Description.like(&pattern)),
-                    ),
-                );
-        }
-    }
-
-    if let Some(ingredients_list) = &filter.ingredient {
-        if !ingredients_list.is_empty() {
-            query = query
-                .join(
-                    JoinType::InnerJoin,
-                    recipes::Relation::IngredientGroups.def(),
-                )
-                .join(
-                    JoinType::InnerJoin,
-                    ingredient_groups::Relation::Ingredients.def(),
-                )
-                .join(
-                    JoinType::InnerJoin,
-                    ingredient_translations::Relation::Ingredients.def(),
-                )
-                .filter(ingredient_translations::Column::LanguageCode.eq(lang_code));
-
-            let mut condition = sea_orm::Condition::any();
-            for ing in ingredients_list {
-                if !ing.trim().is_empty() {
-                    condition = condition
-                        .add(ingredient_translations::Column::Data.like(format!("%{}%", ing)));
-                }
-            }
-            query = query.filter(condition);
-        }
+        query = query.filter(visible_condition(viewer_id));
     }
+    query = query.filter(filter_condition(&filter, lang_code, None)?);
 
-    query = query
-        .group_by(recipes::Column::Id)
-        .order_by_desc(recipes::Column::CreatedAt);
+    let order = RecipeOrder::new(&filter, lang_code);
+    query = order.apply(query);
 
     let results = query
         .all(db)
@@ -115,6 +85,335 @@ pub async fn find_by_query(
     }
 }
 
+/// Recipes whose title, description, ingredients or steps in `$1` match the search `$2`.
+const SEARCH_FILTER: &str = r#"recipes.id IN (
+    SELECT d.recipe_id FROM recipe_search_documents d
+    WHERE d.language_code = $1
+      AND d.document @@ websearch_to_tsquery(search_config($1), $2)
+)"#;
+
+/// Cover density rank of the whole recipe document, so terms found close together score higher.
+const SEARCH_RANK: &str = r#"(
+    SELECT ts_rank_cd(d.document, websearch_to_tsquery(search_config($1), $2))
+    FROM recipe_search_documents d
+    WHERE d.recipe_id = recipes.id AND d.language_code = $1
+)"#;
+
+/// Snippets for every recipe of `$1`. A single ingredient or step usually holds only some of the
+/// search terms, so the first one where `ts_headline` marked anything is kept rather than a strict
+/// `@@` match. Texts are escaped first, leaving `<mark>` as the only markup.
+const SEARCH_MATCH: &str = r#"
+SELECT
+    d.recipe_id,
+    ts_rank_cd(d.document, q.query) AS rank,
+    ts_headline(search_config($2), html_escape(rt.title), q.query,
+        'HighlightAll=true, StartSel=<mark>, StopSel=</mark>') AS title,
+    ts_headline(search_config($2), html_escape(rt.description), q.query,
+        'MaxFragments=2, MaxWords=25, MinWords=10, StartSel=<mark>, StopSel=</mark>') AS description,
+    (
+        SELECT h.snippet
+        FROM ingredient_groups ig
+        JOIN ingredients i ON i.ingredient_group_id = ig.id
+        JOIN ingredient_translations it ON it.ingredient_id = i.id AND it.language_code = $2
+        CROSS JOIN LATERAL ts_headline(search_config($2), html_escape(it.data), q.query,
+            'HighlightAll=true, StartSel=<mark>, StopSel=</mark>') AS h(snippet)
+        WHERE ig.recipe_id = d.recipe_id AND h.snippet LIKE '%<mark>%'
+        ORDER BY ig.position, i.position
+        LIMIT 1
+    ) AS ingredient,
+    (
+        SELECT h.snippet
+        FROM step_groups sg
+        JOIN steps s ON s.step_group_id = sg.id
+        JOIN step_translations st ON st.step_id = s.id AND st.language_code = $2
+        CROSS JOIN LATERAL ts_headline(search_config($2), html_escape(st.instruction), q.query,
+            'MaxFragments=1, MaxWords=25, MinWords=10, StartSel=<mark>, StopSel=</mark>') AS h(snippet)
+        WHERE sg.recipe_id = d.recipe_id AND h.snippet LIKE '%<mark>%'
+        ORDER BY sg.position, s.position
+        LIMIT 1
+    ) AS step
+FROM recipe_search_documents d
+JOIN recipe_translations rt ON rt.recipe_id = d.recipe_id AND rt.language_code = d.language_code
+CROSS JOIN websearch_to_tsquery(search_config($2), $3) AS q(query)
+WHERE d.recipe_id = ANY($1) AND d.language_code = $2
+"#;
+
+/// Public recipes, plus the private ones `viewer_id` wrote, collaborates on or shares
+/// through a household.
+pub fn visible_condition(viewer_id: Option<Uuid>) -> Condition {
+    let mut visible = Condition::any().add(recipes::Column::IsPrivate.eq(false));
+    if let Some(viewer_id) = viewer_id {
+        visible = visible
+            .add(recipes::Column::AuthorId.eq(viewer_id))
+            .add(
+                recipes::Column::HouseholdId.in_subquery(
+                    Query::select()
+                        .column(households::Column::Id)
+                        .from(households::Entity)
+                        .and_where(households::Column::OwnerId.eq(viewer_id))
+                        .to_owned(),
+                ),
+            )
+            .add(
+                recipes::Column::HouseholdId.in_subquery(
+                    Query::select()
+                        .column(household_members::Column::HouseholdId)
+                        .from(household_members::Entity)
+                        .and_where(household_members::Column::UserId.eq(viewer_id))
+                        .to_owned(),
+                ),
+            )
+            .add(recipes::Column::Id.in_subquery(collaborations(viewer_id)));
+    }
+    visible
+}
+
+/// [`visible_condition`] for raw queries: whether the recipe aliased `alias` is visible
+/// to the viewer bound as `viewer`.
+pub fn visible_sql(alias: &str, viewer: &str) -> String {
+    format!(
+        "(NOT {r}.is_private OR {r}.author_id = {v} \
+         OR {r}.household_id IN (SELECT id FROM households WHERE owner_id = {v}) \
+         OR {r}.household_id IN (SELECT household_id FROM household_members WHERE user_id = {v}) \
+         OR {r}.id IN (SELECT recipe_id FROM recipe_collaborators WHERE user_id = {v}))",
+        r = alias,
+        v = viewer,
+    )
+}
+
+pub async fn is_visible(db: &DatabaseConnection, id: Uuid, viewer_id: Option<Uuid>) -> Result<bool, Error> {
+    let count = recipes::Entity::find()
+        .filter(recipes::Column::Id.eq(id))
+        .filter(visible_condition(viewer_id))
+        .count(db)
+        .await?;
+    Ok(count > 0)
+}
+
+/// Whether the viewer may see `image_url`: files no recipe shows are public, those of
+/// recipes only when one of them is visible to the viewer.
+pub async fn is_image_visible(db: &DatabaseConnection, image_url: &str, viewer_id: Option<Uuid>) -> Result<bool, Error> {
+    let shown_on = recipes::Entity::find().filter(image_condition(image_url));
+    if shown_on.clone().count(db).await? == 0 {
+        return Ok(true);
+    }
+    let count = shown_on.filter(visible_condition(viewer_id)).count(db).await?;
+    Ok(count > 0)
+}
+
+/// Recipes showing `image_url`, as their picture or on one of their steps.
+fn image_condition(image_url: &str) -> Condition {
+    use sea_orm::ExprTrait;
+
+    Condition::any()
+        .add(recipes::Column::ImageUrl.eq(image_url))
+        .add(
+            recipes::Column::Id.in_subquery(
+                Query::select()
+                    .column((step_groups::Entity, step_groups::Column::RecipeId))
+                    .from(step_groups::Entity)
+                    .inner_join(
+                        steps::Entity,
+                        Expr::col((steps::Entity, steps::Column::StepGroupId))
+                            .equals((step_groups::Entity, step_groups::Column::Id)),
+                    )
+                    .and_where(steps::Column::ImageUrl.eq(image_url))
+                    .to_owned(),
+            ),
+        )
+}
+
+/// Recipes `user_id` collaborates on.
+fn collaborations(user_id: Uuid) -> SelectStatement {
+    Query::select()
+        .column(recipe_collaborators::Column::RecipeId)
+        .from(recipe_collaborators::Entity)
+        .and_where(recipe_collaborators::Column::UserId.eq(user_id))
+        .to_owned()
+}
+
+/// Recipes `user_id` wrote or collaborates on, as listed in their studio.
+fn studio_condition(user_id: Uuid) -> Condition {
+    Condition::any()
+        .add(recipes::Column::AuthorId.eq(user_id))
+        .add(recipes::Column::Id.in_subquery(collaborations(user_id)))
+}
+
+fn search_filter(lang_code: &str, search: &str) -> Expr {
+    Expr::cust_with_values(SEARCH_FILTER, [lang_code, search.trim()])
+}
+
+/// Number of steps of the current recipe, across all step groups.
+const STEP_COUNT: &str = "(SELECT COUNT(*) FROM step_groups sg JOIN steps s ON s.step_group_id = sg.id WHERE sg.recipe_id = recipes.id)";
+
+/// A facet of the recipe browser whose own filter is left out when counting it,
+/// so that every other choice of that facet still shows what it would return.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RecipeFacet {
+    PrepTime,
+    CookTime,
+    Steps,
+    Language,
+}
+
+/// Every `RecipeFilter` criterion except `scope`, whose meaning differs between listings.
+/// Built from subqueries only so that each recipe comes out once.
+fn filter_condition(filter: &RecipeFilter, lang_code: &str, except: Option<RecipeFacet>) -> Result<Condition, Error> {
+    let mut condition = Condition::all();
+    let applies = |facet: RecipeFacet| except != Some(facet);
+
+    if let Some(s) = filter.search.as_deref().filter(|s| !s.trim().is_empty()) {
+        condition = condition.add(search_filter(lang_code, s));
+    }
+
+    let ingredients: Vec<&String> = filter.ingredient.iter().flatten().filter(|i| !i.trim().is_empty()).collect();
+    if !ingredients.is_empty() {
+        let patterns: Vec<String> = (0..ingredients.len()).map(|i| format!("it.data LIKE ${}", i + 2)).collect();
+        let mut values = vec![Value::from(lang_code)];
+        values.extend(ingredients.iter().map(|i| Value::from(format!("%{}%", i))));
+        condition = condition.add(Expr::cust_with_values(
+            format!(
+                "recipes.id IN (
+                    SELECT ig.recipe_id FROM ingredient_groups ig
+                    JOIN ingredients i ON i.ingredient_group_id = ig.id
+                    JOIN ingredient_translations it ON it.ingredient_id = i.id
+                    WHERE it.language_code = $1 AND ({})
+                )",
+                patterns.join(" OR ")
+            ),
+            values,
+        ));
+    }
+
+    // Comma separated tag ids, a recipe must carry all of them.
+    for tag in filter.tags.iter().flat_map(|t| t.split(',')).map(str::trim).filter(|t| !t.is_empty()) {
+        let tag_id = Uuid::parse_str(tag).map_err(|_| Error::BadRequest(json!({
+            "message": "Invalid tag id in filter",
+            "operation": "filter_recipes",
+            "entity": "tags",
+            "tag_id": tag,
+            "stage": "validation"
+        })))?;
+        condition = condition.add(
+            recipes::Column::Id.in_subquery(
+                Query::select()
+                    .column(recipe_tags::Column::RecipeId)
+                    .from(recipe_tags::Entity)
+                    .and_where(recipe_tags::Column::TagId.eq(tag_id))
+                    .to_owned(),
+            ),
+        );
+    }
+
+    let mut excluded = Vec::new();
+    for label in filter.exclude_labels.iter().flat_map(|l| l.split(',')).map(str::trim).filter(|l| !l.is_empty()) {
+        let label = DietaryLabel::parse(label).ok_or_else(|| Error::BadRequest(json!({
+            "message": "Invalid dietary label in filter",
+            "operation": "filter_recipes",
+            "entity": "recipe_dietary_labels",
+            "label": label,
+            "stage": "validation"
+        })))?;
+        excluded.push(Value::from(label.as_str()));
+    }
+    if !excluded.is_empty() {
+        let placeholders: Vec<String> = (1..=excluded.len()).map(|i| format!("${}", i)).collect();
+        condition = condition.add(Expr::cust_with_values(
+            format!(
+                "recipes.id NOT IN (
+                    SELECT recipe_id FROM recipe_dietary_labels
+                    WHERE label IN ({}) AND coalesce(override, derived)
+                )",
+                placeholders.join(", ")
+            ),
+            excluded,
+        ));
+    }
+
+    if applies(RecipeFacet::PrepTime) {
+        if let Some(min) = filter.min_prep { condition = condition.add(recipes::Column::PrepTimeMinutes.gte(min)); }
+        if let Some(max) = filter.max_prep { condition = condition.add(recipes::Column::PrepTimeMinutes.lte(max)); }
+    }
+
+    if applies(RecipeFacet::CookTime) {
+        if let Some(min) = filter.min_cook { condition = condition.add(recipes::Column::CookTimeMinutes.gte(min)); }
+        if let Some(max) = filter.max_cook { condition = condition.add(recipes::Column::CookTimeMinutes.lte(max)); }
+    }
+
+    if applies(RecipeFacet::Steps) {
+        if let Some(min) = filter.min_steps { condition = condition.add(Expr::cust_with_values(format!("{STEP_COUNT} >= $1"), [min])); }
+        if let Some(max) = filter.max_steps { condition = condition.add(Expr::cust_with_values(format!("{STEP_COUNT} <= $1"), [max])); }
+    }
+
+    if let Some(from) = filter.date_from { condition = condition.add(recipes::Column::UpdatedAt.gte(from)); }
+    if let Some(to) = filter.date_to { condition = condition.add(recipes::Column::UpdatedAt.lte(to)); }
+
+    if applies(RecipeFacet::Language)
+        && let Some(language) = filter.language.as_deref().filter(|l| !l.is_empty())
+    {
+        condition = condition.add(
+            recipes::Column::Id.in_subquery(
+                Query::select()
+                    .column(recipe_translations::Column::RecipeId)
+                    .from(recipe_translations::Entity)
+                    .and_where(recipe_translations::Column::LanguageCode.eq(language))
+                    .to_owned(),
+            ),
+        );
+    }
+
+    Ok(condition)
+}
+
+/// Relevance and highlighted snippets explaining why each of `recipe_ids` matched `search`,
+/// in a single query for a whole page of results.
+pub async fn find_search_matches(
+    db: &DatabaseConnection,
+    recipe_ids: &[Uuid],
+    lang_code: &str,
+    search: &str,
+) -> Result<HashMap<Uuid, RecipeSearchMatchDto>, Error> {
+    #[derive(FromQueryResult)]
+    struct SearchMatch {
+        recipe_id: Uuid,
+        rank: f32,
+        title: String,
+        description: String,
+        ingredient: Option<String>,
+        step: Option<String>,
+    }
+
+    let statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        SEARCH_MATCH,
+        [recipe_ids.to_vec().into(), lang_code.into(), search.trim().into()],
+    );
+
+    let matches = SearchMatch::find_by_statement(statement)
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to build search highlights",
+            "operation": "find_search_matches",
+            "entity": "recipe_search_documents",
+            "language_code": lang_code,
+            "search_term": search,
+            "error": e.to_string(),
+            "stage": "highlight_query"
+        })))?;
+
+    Ok(matches
+        .into_iter()
+        .map(|m| (m.recipe_id, RecipeSearchMatchDto {
+            rank: m.rank,
+            title: m.title,
+            description: m.description,
+            ingredient: m.ingredient,
+            step: m.step,
+        }))
+        .collect())
+}
+
 pub async fn find_by_id(db: &DatabaseConnection, id: Uuid) -> Result<recipes::Model, Error> {
     recipes::Entity::find_by_id(id)
         .one(db)
@@ -173,10 +472,66 @@ pub async fn find_by_author(
         .map_err(Error::from)
 }
 
+pub async fn find_lineage(db: &DatabaseConnection, id: Uuid) -> Result<Vec<recipes::Model>, Error> {
+    recipes::Entity::find()
+        .from_raw_sql(Statement::from_sql_and_values(DbBackend::Postgres, LINEAGE, [id.into()]))
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to fetch recipe lineage",
+            "operation": "find_lineage",
+            "entity": "recipes",
+            "recipe_id": id.to_string(),
+            "error": e.to_string(),
+            "stage": "select"
+        })))
+}
+
+/// Direct forks of the recipe `viewer_id` may see, newest first.
+pub async fn find_forks(
+    db: &DatabaseConnection,
+    id: Uuid,
+    viewer_id: Option<Uuid>,
+) -> Result<Vec<recipes::Model>, Error> {
+    recipes::Entity::find()
+        .filter(recipes::Column::ForkedFrom.eq(id))
+        .filter(visible_condition(viewer_id))
+        .order_by_desc(recipes::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(Error::from)
+}
+
+/// Points the recipe and its steps at their own copies of the images, once a fork is made.
+pub async fn set_image_urls(
+    db: &DatabaseConnection,
+    id: Uuid,
+    image_url: String,
+    step_image_urls: Vec<(Uuid, String)>,
+) -> Result<(), Error> {
+    let txn = db.begin().await?;
+    recipes::Entity::update_many()
+        .col_expr(recipes::Column::ImageUrl, Expr::value(image_url))
+        .filter(recipes::Column::Id.eq(id))
+        .exec(&txn)
+        .await?;
+    for (step_id, image_url) in step_image_urls {
+        steps::Entity::update_many()
+            .col_expr(steps::Column::ImageUrl, Expr::value(image_url))
+            .filter(steps::Column::Id.eq(step_id))
+            .exec(&txn)
+            .await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+/// `forked_from` links a fork to its source in the same transaction.
 pub async fn create(
     db: &DatabaseConnection,
     new_recipe: CreateRecipeInput,
     preferred_language: &str,
+    forked_from: Option<Uuid>,
 ) -> Result<RecipeViewDto, TransactionError<Error>> {
     let pref_lang = preferred_language.to_string();
     db.transaction::<_, RecipeViewDto, Error>(|txn| {
@@ -190,6 +545,7 @@ pub async fn create(
                 cook_time_minutes: Set(new_recipe.cook_time_minutes),
                 is_private: Set(new_recipe.is_private),
                 original_language_code: Set(new_recipe.primary_language.clone()),
+                forked_from: Set(forked_from),
                 ..Default::default()
             }
                 .insert(txn)
@@ -354,7 +710,7 @@ pub async fn find_latest_work(
     user_id: Uuid,
 ) -> Result<Vec<recipes::Model>, Error> {
     recipes::Entity::find()
-        .filter(recipes::Column::AuthorId.eq(user_id))
+        .filter(studio_condition(user_id))
         .order_by_desc(recipes::Column::CreatedAt)
         .limit(limit as u64)
         .all(db)
@@ -416,65 +772,26 @@ pub async fn get_by_author_and_filter(
     db: &DatabaseConnection,
     user_id: Uuid,
     query_params: GetAllRecipesByPageQuery,
-    lang_code: &String,
-) -> Result<Vec<recipes::Model>, Error> {
+    lang_code: &str,
+) -> Result<(Vec<recipes::Model>, u64, Option<String>), Error> {
     let mut query = recipes::Entity::find()
-        .filter(recipes::Column::AuthorId.eq(user_id));
+        .filter(studio_condition(user_id));
 
-    let page = query_params.page.unwrap_or(1) as u64;
-    let per_page = query_params.per_page.unwrap_or(10) as u64;
+    let page = query_params.page.unwrap_or(1).max(1) as u64;
+    let per_page = query_params.per_page.unwrap_or(10).max(1) as u64;
+    let cursor = query_params.cursor.as_deref().map(RecipeCursor::decode).transpose()?;
+    let mut order = RecipeOrder::default();
 
     if let Some(filter) = query_params.filters {
-
-        if let Some(s) = &filter.search {
-            if !s.trim().is_empty() {
-                let pattern = format!("%{}%", s);
-                query = query
-                    .join(JoinType::LeftJoin, recipes::Relation::RecipeTranslations.def())
-                    .filter(
-                        recipe_translations::Column::LanguageCode.eq(lang_code).and(
-                            recipe_translations::Column::Title.like(&pattern)
-                                .or(recipe_translations::Column::Description.like(&pattern)),
-                        ),
-                    );
-            }
-        }
-
-        if let Some(min) = filter.min_prep { query = query.filter(recipes::Column::PrepTimeMinutes.gte(min)); }
-        if let Some(max) = filter.max_prep { query = query.filter(recipes::Column::PrepTimeMinutes.lte(max)); }
-
-        if let Some(min) = filter.min_cook { query = query.filter(recipes::Column::CookTimeMinutes.gte(min)); }
-        if let Some(max) = filter.max_cook { query = query.filter(recipes::Column::CookTimeMinutes.lte(max)); }
-
-        if let Some(from) = filter.date_from { query = query.filter(recipes::Column::UpdatedAt.gte(from)); }
-        if let Some(to) = filter.date_to { query = query.filter(recipes::Column::UpdatedAt.lte(to)); }
-
-        if let Some(ingredients_list) = &filter.ingredient {
-            if !ingredients_list.is_empty() {
-                query = query
-                    .join(JoinType::InnerJoin, recipes::Relation::IngredientGroups.def())
-                    .join(JoinType::InnerJoin, ingredient_groups::Relation::Ingredients.def())
-                    .join(JoinType::InnerJoin, ingredient_translations::Relation::Ingredients.def())
-                    .filter(ingredient_translations::Column::LanguageCode.eq(lang_code));
-
-                let mut condition = sea_orm::Condition::any();
-                for ing in ingredients_list {
-                    if !ing.trim().is_empty() {
-                        condition = condition.add(ingredient_translations::Column::Data.like(format!("%{}%", ing)));
-                    }
-                }
-                query = query.filter(condition);
-            }
-        }
+        order = RecipeOrder::new(&filter, lang_code);
+        query = query.filter(filter_condition(&filter, lang_code, None)?);
     }
 
-    let paginator = query
-        .group_by(recipes::Column::Id)
-        .order_by_desc(recipes::Column::CreatedAt)
-        .paginate(db, per_page);
+    if let Some(cursor) = &cursor {
+        order.check_cursor(cursor)?;
+    }
 
-    let results = paginator
-        .fetch_page(page - 1)
+    fetch_page(db, query, page, per_page, cursor, &order)
         .await
         .map_err(|e| Error::InternalServerError(json!({
             "message": "Failed to fetch filtered recipes for author",
@@ -483,79 +800,40 @@ pub async fn get_by_author_and_filter(
             "page": page,
             "error": e.to_string(),
             "stage": "database_query"
-        })))?;
-
-    Ok(results)
+        })))
 }
 pub async fn find_by_query_by_page(
     db: &DatabaseConnection,
     filter: RecipeFilterByPage,
     lang_code: &str,
-) -> Result<Option<Vec<recipes::Model>>, Error> {
+    viewer_id: Option<Uuid>,
+) -> Result<(Vec<recipes::Model>, u64, Option<String>), Error> {
     // Extract pagination info BEFORE filter is moved
-    let page = filter.page.unwrap_or(1);
-    let per_page = filter.per_page.unwrap_or(10);
+    let page = filter.page.unwrap_or(1).max(1);
+    let per_page = filter.per_page.unwrap_or(10).max(1);
     let has_filters = filter.filters.is_some();
+    let cursor = filter.cursor.as_deref().map(RecipeCursor::decode).transpose()?;
+    let mut order = RecipeOrder::default();
 
     let mut query = recipes::Entity::find();
 
     if let Some(filter) = filter.filters {
-        query = query.filter(recipes::Column::IsPrivate.eq(!filter.scope));
-
-        if let Some(s) = &filter.search {
-            if !s.trim().is_empty() {
-                let pattern = format!("%{}%", s);
-                query = query
-                    .join(
-                        JoinType::LeftJoin,
-                        recipes::Relation::RecipeTranslations.def(),
-                    )
-                    .filter(
-                        recipe_translations::Column::LanguageCode.eq(lang_code).and(
-                            recipe_translations::Column::Title
-                                .like(&pattern)
-                                .or(recipe_translations::Column::Description.like(&pattern)),
-                        ),
-                    );
-            }
-        }
-
-        if let Some(ingredients_list) = &filter.ingredient {
-            if !ingredients_list.is_empty() {
-                query = query
-                    .join(
-                        JoinType::InnerJoin,
-                        recipes::Relation::IngredientGroups.def(),
-                    )
-                    .join(
-                        JoinType::InnerJoin,
-                        ingredient_groups::Relation::Ingredients.def(),
-                    )
-                    .join(
-                        JoinType::InnerJoin,
-                        ingredient_translations::Relation::Ingredients.def(),
-                    )
-                    .filter(ingredient_translations::Column::LanguageCode.eq(lang_code));
-
-                let mut condition = sea_orm::Condition::any();
-                for ing in ingredients_list {
-                    if !ing.trim().is_empty() {
-                        condition = condition
-                            .add(ingredient_translations::Column::Data.like(format!("%{}%", ing)));
-                    }
-                }
-                query = query.filter(condition);
-            }
+        if filter.scope {
+            query = query.filter(recipes::Column::IsPrivate.eq(true));
+        } else {
+            query = query.filter(visible_condition(viewer_id));
         }
+        order = RecipeOrder::new(&filter, lang_code);
+        query = query.filter(filter_condition(&filter, lang_code, None)?);
+    } else {
+        query = query.filter(visible_condition(viewer_id));
     }
 
-    query = query
-        .group_by(recipes::Column::Id)
-        .order_by_desc(recipes::Column::CreatedAt);
+    if let Some(cursor) = &cursor {
+        order.check_cursor(cursor)?;
+    }
 
-    let paginator = query.paginate(db, per_page as u64);
-    let results = paginator
-        .fetch_page((page - 1) as u64)
+    fetch_page(db, query, page as u64, per_page as u64, cursor, &order)
         .await
         .map_err(|e| Error::InternalServerError(json!({
             "message": "Failed to fetch paginated recipes",
@@ -567,12 +845,334 @@ pub async fn find_by_query_by_page(
             "has_filters": has_filters,
             "error": e.to_string(),
             "stage": "pagination_query"
-        })))?;
+        })))
+}
 
-    if results.is_empty() {
-        Ok(None)
-    } else {
-        Ok(Some(results))
+/// Minutes, inclusive, the last bucket being open ended.
+const TIME_BUCKETS: [(i32, Option<i32>); 4] = [(0, Some(15)), (16, Some(30)), (31, Some(60)), (61, None)];
+const STEP_BUCKETS: [(i32, Option<i32>); 4] = [(0, Some(5)), (6, Some(10)), (11, Some(15)), (16, None)];
+
+/// Facet counts for the recipes `find_by_query` returns. A facet ignores its own filter,
+/// tags excepted since selecting more of them narrows the results.
+pub async fn get_facets(
+    db: &DatabaseConnection,
+    filter: &RecipeFilter,
+    lang_code: &str,
+    viewer_id: Option<Uuid>,
+) -> Result<RecipeFacetsDto, Error> {
+    let matching = |except: Option<RecipeFacet>| -> Result<Condition, Error> {
+        let mut condition = filter_condition(filter, lang_code, except)?;
+        if !filter.scope {
+            condition = condition.add(visible_condition(viewer_id));
+        }
+        Ok(condition)
+    };
+    let matching_ids = |except: Option<RecipeFacet>| -> Result<SelectStatement, Error> {
+        Ok(Query::select()
+            .column((recipes::Entity, recipes::Column::Id))
+            .from(recipes::Entity)
+            .cond_where(matching(except)?)
+            .to_owned())
+    };
+    let db_error = |facet: &str, e: DbErr| Error::InternalServerError(json!({
+        "message": "Failed to count recipe facets",
+        "operation": "get_facets",
+        "entity": "recipes",
+        "facet": facet,
+        "language_code": lang_code,
+        "error": e.to_string(),
+        "stage": "facet_query"
+    }));
+
+    let total = recipes::Entity::find()
+        .filter(matching(None)?)
+        .count(db)
+        .await
+        .map_err(|e| db_error("total", e))?;
+
+    let tags = tags::Entity::find()
+        .select_only()
+        .column(tags::Column::Id)
+        .column(tags::Column::Name)
+        .column_as(Expr::cust("COUNT(*)"), "count")
+        .join(JoinType::InnerJoin, tags::Relation::RecipeTags.def())
+        .filter(recipe_tags::Column::RecipeId.in_subquery(matching_ids(None)?))
+        .group_by(tags::Column::Id)
+        .group_by(tags::Column::Name)
+        .order_by(Expr::cust("count"), Order::Desc)
+        .order_by_asc(tags::Column::Name)
+        .into_tuple::<(Uuid, String, i64)>()
+        .all(db)
+        .await
+        .map_err(|e| db_error("tags", e))?
+        .into_iter()
+        .map(|(id, name, count)| TagFacetDto { id, name, count })
+        .collect();
+
+    let prep_time = count_buckets(db, "recipes.prep_time_minutes", &TIME_BUCKETS, matching(Some(RecipeFacet::PrepTime))?)
+        .await
+        .map_err(|e| db_error("prep_time", e))?;
+    let cook_time = count_buckets(db, "recipes.cook_time_minutes", &TIME_BUCKETS, matching(Some(RecipeFacet::CookTime))?)
+        .await
+        .map_err(|e| db_error("cook_time", e))?;
+    let steps = count_buckets(db, STEP_COUNT, &STEP_BUCKETS, matching(Some(RecipeFacet::Steps))?)
+        .await
+        .map_err(|e| db_error("steps", e))?;
+
+    let languages = recipe_translations::Entity::find()
+        .select_only()
+        .column(recipe_translations::Column::LanguageCode)
+        .column_as(Expr::cust("COUNT(DISTINCT recipe_translations.recipe_id)"), "count")
+        .filter(recipe_translations::Column::RecipeId.in_subquery(matching_ids(Some(RecipeFacet::Language))?))
+        .group_by(recipe_translations::Column::LanguageCode)
+        .order_by(Expr::cust("count"), Order::Desc)
+        .order_by_asc(recipe_translations::Column::LanguageCode)
+        .into_tuple::<(String, i64)>()
+        .all(db)
+        .await
+        .map_err(|e| db_error("languages", e))?
+        .into_iter()
+        .map(|(code, count)| LanguageFacetDto { code, count })
+        .collect();
+
+    Ok(RecipeFacetsDto {
+        total: total as i64,
+        tags,
+        prep_time,
+        cook_time,
+        steps,
+        languages,
+    })
+}
+
+/// Counts the recipes matching `condition` per bucket of `value`, empty buckets included.
+async fn count_buckets(
+    db: &DatabaseConnection,
+    value: &str,
+    buckets: &[(i32, Option<i32>)],
+    condition: Condition,
+) -> Result<Vec<RangeFacetDto>, DbErr> {
+    let cases: Vec<String> = buckets
+        .iter()
+        .enumerate()
+        .map(|(i, (min, max))| match max {
+            Some(max) => format!("WHEN {value} BETWEEN {min} AND {max} THEN {i}"),
+            None => format!("WHEN {value} >= {min} THEN {i}"),
+        })
+        .collect();
+
+    let counts: HashMap<i32, i64> = recipes::Entity::find()
+        .select_only()
+        .column_as(Expr::cust(format!("CASE {} END", cases.join(" "))), "bucket")
+        .column_as(Expr::cust("COUNT(*)"), "count")
+        .filter(condition)
+        .group_by(Expr::cust("bucket"))
+        .into_tuple::<(Option<i32>, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(bucket, count)| bucket.map(|b| (b, count)))
+        .collect();
+
+    Ok(buckets
+        .iter()
+        .enumerate()
+        .map(|(i, (min, max))| RangeFacetDto {
+            min: *min,
+            max: *max,
+            count: counts.get(&(i as i32)).copied().unwrap_or(0),
+        })
+        .collect())
+}
+
+/// Fetches one page of `query` along with the total number of matching recipes.
+/// With a `cursor` the page starts right after it instead of at `page`, so recipes
+/// published in the meantime don't shift the listing.
+async fn fetch_page(
+    db: &DatabaseConnection,
+    query: Select<recipes::Entity>,
+    page: u64,
+    per_page: u64,
+    cursor: Option<RecipeCursor>,
+    order: &RecipeOrder,
+) -> Result<(Vec<recipes::Model>, u64, Option<String>), DbErr> {
+    let total = query.clone().paginate(db, per_page).num_items().await?;
+
+    let query = match cursor {
+        Some(cursor) => query.filter(order.after(&cursor)),
+        None => query.offset((page - 1) * per_page),
+    };
+
+    let mut results = order.apply(query).limit(per_page + 1).all(db).await?;
+    if results.len() as u64 <= per_page {
+        return Ok((results, total, None));
+    }
+    results.truncate(per_page as usize);
+
+    let Some(last) = results.last() else {
+        return Ok((results, total, None));
+    };
+    let next_cursor = RecipeCursor {
+        key: order.key_of(db, last.id).await?,
+        created_at: last.created_at.unwrap_or_default(),
+        id: last.id,
+    };
+
+    Ok((results, total, Some(next_cursor.encode())))
+}
+
+/// Listing order: an optional sort key computed in SQL, then `created_at, id` so that
+/// every recipe has a unique position a cursor can point at.
+struct RecipeOrder {
+    key: Option<SortKey>,
+    direction: Order,
+}
+
+impl Default for RecipeOrder {
+    fn default() -> Self {
+        Self { key: None, direction: Order::Desc }
+    }
+}
+
+struct SortKey {
+    expr: Expr,
+    is_text: bool,
+}
+
+impl RecipeOrder {
+    fn new(filter: &RecipeFilter, lang_code: &str) -> Self {
+        let search = filter.search.as_deref().map(str::trim).filter(|s| !s.is_empty());
+        let sort = filter.sort.unwrap_or(RecipeSort::Relevance);
+        let direction = match filter.direction {
+            Some(SortDirection::Asc) => Order::Asc,
+            Some(SortDirection::Desc) => Order::Desc,
+            None if sort == RecipeSort::Title => Order::Asc,
+            None => Order::Desc,
+        };
+
+        let number = |sql: &'static str| Some(SortKey { expr: Expr::cust(sql), is_text: false });
+        let key = match sort {
+            RecipeSort::Relevance => search.map(|s| SortKey {
+                expr: Expr::cust_with_values(format!("{SEARCH_RANK}::float8"), [lang_code, s]),
+                is_text: false,
+            }),
+            RecipeSort::Newest => None,
+            RecipeSort::Rating => number(
+                "COALESCE((SELECT AVG(rr.rating) FROM recipe_ratings rr WHERE rr.recipe_id = recipes.id), 0)::float8",
+            ),
+            RecipeSort::RatingCount => number(
+                "(SELECT COUNT(*) FROM recipe_ratings rr WHERE rr.recipe_id = recipes.id)::float8",
+            ),
+            RecipeSort::Views => number(
+                "(SELECT COUNT(*) FROM recipe_analytics ra WHERE ra.recipe_id = recipes.id)::float8",
+            ),
+            RecipeSort::RecentViews => number(
+                "(SELECT COUNT(*) FROM recipe_analytics ra
+                  WHERE ra.recipe_id = recipes.id AND ra.viewed_at >= now() - interval '7 days')::float8",
+            ),
+            RecipeSort::TotalTime => number(
+                "(recipes.prep_time_minutes + recipes.cook_time_minutes)::float8",
+            ),
+            RecipeSort::Ingredients => number(
+                "(SELECT COUNT(*) FROM ingredient_groups ig
+                  JOIN ingredients i ON i.ingredient_group_id = ig.id
+                  WHERE ig.recipe_id = recipes.id)::float8",
+            ),
+            // Recipes missing the requested language are listed under their original title,
+            // the same fallback the listing itself uses.
+            RecipeSort::Title => Some(SortKey {
+                expr: Expr::cust_with_values(
+                    "lower(COALESCE(
+                        (SELECT rt.title FROM recipe_translations rt
+                         WHERE rt.recipe_id = recipes.id AND rt.language_code = $1),
+                        (SELECT rt.title FROM recipe_translations rt
+                         WHERE rt.recipe_id = recipes.id AND rt.language_code = recipes.original_language_code),
+                        ''))",
+                    [lang_code],
+                ),
+                is_text: true,
+            }),
+        };
+
+        Self { key, direction }
+    }
+
+    /// Newest first breaks ties whatever the direction of the sort key; without a key,
+    /// the direction applies to the creation date itself.
+    fn tiebreak(&self) -> Order {
+        match self.key {
+            Some(_) => Order::Desc,
+            None => self.direction.clone(),
+        }
+    }
+
+    fn apply(&self, query: Select<recipes::Entity>) -> Select<recipes::Entity> {
+        let query = match &self.key {
+            Some(key) => query.order_by(key.expr.clone(), self.direction.clone()),
+            None => query,
+        };
+        query
+            .order_by(recipes::Column::CreatedAt, self.tiebreak())
+            .order_by(recipes::Column::Id, self.tiebreak())
+    }
+
+    /// Recipes listed after `cursor`.
+    fn after(&self, cursor: &RecipeCursor) -> Expr {
+        use sea_orm::ExprTrait;
+
+        let operator = |order: Order| if order == Order::Asc { ">" } else { "<" };
+        let tiebreak = Expr::cust_with_values(
+            format!("(recipes.created_at, recipes.id) {} ($1, $2)", operator(self.tiebreak())),
+            [Value::from(cursor.created_at), Value::from(cursor.id)],
+        );
+
+        let (Some(key), Some(value)) = (&self.key, &cursor.key) else {
+            return tiebreak;
+        };
+        let value = match value {
+            SortValue::Number(n) => Value::from(*n),
+            SortValue::Text(t) => Value::from(t.clone()),
+        };
+        let beyond = match self.direction {
+            Order::Asc => key.expr.clone().gt(value.clone()),
+            _ => key.expr.clone().lt(value.clone()),
+        };
+        beyond.or(key.expr.clone().eq(value).and(tiebreak))
+    }
+
+    fn check_cursor(&self, cursor: &RecipeCursor) -> Result<(), Error> {
+        let matches = match (&self.key, &cursor.key) {
+            (None, None) => true,
+            (Some(key), Some(SortValue::Text(_))) => key.is_text,
+            (Some(key), Some(SortValue::Number(_))) => !key.is_text,
+            _ => false,
+        };
+        if matches {
+            return Ok(());
+        }
+        Err(Error::BadRequest(json!({
+            "message": "Pagination cursor does not match the requested sort",
+            "operation": "check_cursor",
+            "entity": "recipes",
+            "stage": "validation"
+        })))
+    }
+
+    /// Sort key value of `recipe_id`, to resume the listing after it.
+    async fn key_of(&self, db: &DatabaseConnection, recipe_id: Uuid) -> Result<Option<SortValue>, DbErr> {
+        let Some(key) = &self.key else {
+            return Ok(None);
+        };
+        let query = recipes::Entity::find_by_id(recipe_id)
+            .select_only()
+            .column_as(key.expr.clone(), "key");
+
+        let value = if key.is_text {
+            query.into_tuple::<Option<String>>().one(db).await?.flatten().map(SortValue::Text)
+        } else {
+            query.into_tuple::<Option<f64>>().one(db).await?.flatten().map(SortValue::Number)
+        };
+        Ok(value)
     }
 }
 
@@ -609,11 +1209,18 @@ pub async fn get_favorites(
         })))
 }
 
+/// `is_private` is only applied with `may_change_visibility`, the stored value is kept otherwise.
+/// `original` is saved as a version in the same transaction.
+#[allow(clippy::too_many_arguments)]
 pub async fn update(
     db: &DatabaseConnection,
     updated_recipe: EditRecipeInput,
     recipe_id: Uuid,
     lang_code: &str,
+    may_change_visibility: bool,
+    original: RecipeEditorDto,
+    user_id: Uuid,
+    edited_as: Option<RecipeCollaboratorRole>,
 ) -> Result<(), TransactionError<Error>> {
     let lang_code_owned = lang_code.to_string();
 
@@ -659,7 +1266,7 @@ pub async fn update(
                 active_model.cook_time_minutes = Set(updated_recipe.cook_time_minutes);
                 base_changed = true;
             }
-            if original_recipe.is_private != updated_recipe.is_private {
+            if may_change_visibility && original_recipe.is_private != updated_recipe.is_private {
                 active_model.is_private = Set(updated_recipe.is_private);
                 base_changed = true;
             }
@@ -861,6 +1468,8 @@ pub async fn update(
                     }
                 })?;
 
+            recipe_version_repository::create(txn, original, user_id, edited_as).await?;
+
             Ok(())
         })
     })
@@ -871,6 +1480,210 @@ pub async fn update(
         })
 }
 
+pub async fn restore(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    snapshot: RecipeEditorDto,
+    current: RecipeEditorDto,
+    user_id: Uuid,
+    edited_as: Option<RecipeCollaboratorRole>,
+    may_change_visibility: bool,
+) -> Result<(), TransactionError<Error>> {
+    db.transaction::<_, (), Error>(|txn| {
+        Box::pin(async move {
+            let original_recipe = recipes::Entity::find_by_id(recipe_id)
+                .one(txn)
+                .await
+                .map_err(|e| Error::InternalServerError(json!({
+                    "message": "Failed to fetch recipe for restore",
+                    "operation": "restore",
+                    "entity": "recipes",
+                    "recipe_id": recipe_id.to_string(),
+                    "error": e.to_string(),
+                    "stage": "fetch_original"
+                })))?
+                .ok_or_else(|| Error::NotFound(json!({
+                    "message": "Recipe not found for restore",
+                    "operation": "restore",
+                    "entity": "recipes",
+                    "recipe_id": recipe_id.to_string(),
+                    "stage": "validation"
+                })))?;
+
+            let mut active_model: recipes::ActiveModel = original_recipe.into();
+            active_model.image_url = Set(snapshot.image_url.clone());
+            active_model.servings = Set(snapshot.servings);
+            active_model.prep_time_minutes = Set(snapshot.prep_time_minutes);
+            active_model.cook_time_minutes = Set(snapshot.cook_time_minutes);
+            if may_change_visibility {
+                active_model.is_private = Set(snapshot.is_private);
+            }
+            active_model.original_language_code = Set(snapshot.primary_language.clone());
+            active_model
+                .update(txn)
+                .await
+                .map_err(|e| Error::InternalServerError(json!({
+                    "message": "Failed to restore recipe base fields",
+                    "operation": "restore",
+                    "entity": "recipes",
+                    "recipe_id": recipe_id.to_string(),
+                    "error": e.to_string(),
+                    "stage": "base_update"
+                })))?;
+
+            recipe_translations::Entity::delete_many()
+                .filter(recipe_translations::Column::RecipeId.eq(recipe_id))
+                .exec(txn)
+                .await
+                .map_err(|e| Error::InternalServerError(json!({
+                    "message": "Failed to delete current translations",
+                    "operation": "restore",
+                    "entity": "recipe_translations",
+                    "recipe_id": recipe_id.to_string(),
+                    "error": e.to_string(),
+                    "stage": "delete_translations"
+                })))?;
+
+            for (trans_idx, trans) in snapshot.translations.iter().enumerate() {
+                recipe_translations::ActiveModel {
+                    recipe_id: Set(recipe_id),
+                    language_code: Set(trans.language_code.clone()),
+                    title: Set(trans.title.clone()),
+                    description: Set(trans.description.clone().unwrap_or_default()),
+                    ..Default::default()
+                }
+                    .insert(txn)
+                    .await
+                    .map_err(|e| Error::InternalServerError(json!({
+                    "message": "Failed to restore translation",
+                    "operation": "restore",
+                    "entity": "recipe_translations",
+                    "recipe_id": recipe_id.to_string(),
+                    "language_code": &trans.language_code,
+                    "translation_index": trans_idx,
+                    "error": e.to_string(),
+                    "stage": "translation_insert"
+                })))?;
+            }
+
+            recipe_tags::Entity::delete_many()
+                .filter(recipe_tags::Column::RecipeId.eq(recipe_id))
+                .exec(txn)
+                .await
+                .map_err(|e| Error::InternalServerError(json!({
+                    "message": "Failed to delete current tags",
+                    "operation": "restore",
+                    "entity": "recipe_tags",
+                    "recipe_id": recipe_id.to_string(),
+                    "error": e.to_string(),
+                    "stage": "delete_tags"
+                })))?;
+
+            // Tags may have been renamed or deleted since the snapshot, so relink them by name.
+            let snapshot_tags: Vec<InputTag> = snapshot
+                .tags
+                .iter()
+                .map(|t| InputTag::New { name: t.name.clone() })
+                .collect();
+
+            tag_repository::find_or_create_tags(txn, snapshot_tags, recipe_id)
+                .await
+                .map_err(|e| {
+                    log::error!("Failed to restore tags for recipe {}: {:?}", recipe_id, e);
+                    match e {
+                        Error::InternalServerError(mut ctx) => {
+                            ctx["recipe_id"] = json!(recipe_id.to_string());
+                            ctx["stage"] = json!("tag_restore");
+                            Error::InternalServerError(ctx)
+                        }
+                        other => other,
+                    }
+                })?;
+
+            ingredient_groups::Entity::delete_many()
+                .filter(ingredient_groups::Column::RecipeId.eq(recipe_id))
+                .exec(txn)
+                .await
+                .map_err(|e| Error::InternalServerError(json!({
+                    "message": "Failed to delete current ingredient groups",
+                    "operation": "restore",
+                    "entity": "ingredient_groups",
+                    "recipe_id": recipe_id.to_string(),
+                    "error": e.to_string(),
+                    "stage": "delete_ingredient_groups"
+                })))?;
+
+            ingredient_group_repository::create_multiple(
+                txn,
+                recipe_id,
+                snapshot
+                    .ingredient_groups
+                    .into_iter()
+                    .map(IngredientGroupInput::from)
+                    .collect(),
+                &snapshot.primary_language,
+            )
+                .await
+                .map_err(|e| {
+                    log::error!("Failed to restore ingredient groups for recipe {}: {:?}", recipe_id, e);
+                    match e {
+                        Error::InternalServerError(mut ctx) => {
+                            ctx["recipe_id"] = json!(recipe_id.to_string());
+                            ctx["stage"] = json!("ingredient_group_restore");
+                            Error::InternalServerError(ctx)
+                        }
+                        other => other,
+                    }
+                })?;
+
+            step_groups::Entity::delete_many()
+                .filter(step_groups::Column::RecipeId.eq(recipe_id))
+                .exec(txn)
+                .await
+                .map_err(|e| Error::InternalServerError(json!({
+                    "message": "Failed to delete current step groups",
+                    "operation": "restore",
+                    "entity": "step_groups",
+                    "recipe_id": recipe_id.to_string(),
+                    "error": e.to_string(),
+                    "stage": "delete_step_groups"
+                })))?;
+
+            step_group_repository::create_multiple(
+                txn,
+                recipe_id,
+                snapshot
+                    .step_groups
+                    .into_iter()
+                    .map(StepGroupInput::from)
+                    .collect(),
+                &snapshot.primary_language,
+            )
+                .await
+                .map_err(|e| {
+                    log::error!("Failed to restore step groups for recipe {}: {:?}", recipe_id, e);
+                    match e {
+                        Error::InternalServerError(mut ctx) => {
+                            ctx["recipe_id"] = json!(recipe_id.to_string());
+                            ctx["stage"] = json!("step_group_restore");
+                            Error::InternalServerError(ctx)
+                        }
+                        other => other,
+                    }
+                })?;
+
+            recipe_version_repository::create(txn, current, user_id, edited_as).await?;
+
+            Ok(())
+        })
+    })
+        .await
+        .map_err(|e| {
+            log::error!("Transaction failed in restore recipe {}: {:?}", recipe_id, e);
+            e
+        })
+}
+
 pub async fn get_analytics(db: &DatabaseConnection, recipe_id: Uuid) -> Result<u64, Error> {
     recipe_analytics::Entity::find()
         .filter(recipe_analytics::Column::RecipeId.eq(recipe_id))
@@ -911,6 +1724,33 @@ pub async fn add_view(
     Ok(())
 }
 
+pub async fn add_print(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    user_id: Option<Uuid>,
+    layout: &str,
+) -> Result<(), Error> {
+    recipe_prints::ActiveModel {
+        recipe_id: Set(recipe_id),
+        user_id: Set(user_id),
+        layout: Set(layout.to_string()),
+        ..Default::default()
+    }
+        .insert(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to record recipe print",
+        "operation": "add_print",
+        "entity": "recipe_prints",
+        "recipe_id": recipe_id.to_string(),
+        "user_id": user_id.map(|u| u.to_string()),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+
+    Ok(())
+}
+
 pub async fn toogle_favorite(
     db: &DatabaseConnection,
     recipe_id: Uuid,
@@ -1419,6 +2259,18 @@ pub async fn get_total_views(
         .map_err(|e| Error::InternalServerError(json!({ "error": e.to_string() })))
 }
 
+pub async fn get_total_prints(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+) -> Result<i64, Error> {
+    recipe_prints::Entity::find()
+        .filter(recipe_prints::Column::RecipeId.eq(recipe_id))
+        .count(db)
+        .await
+        .map(|c| c as i64)
+        .map_err(|e| Error::InternalServerError(json!({ "error": e.to_string() })))
+}
+
 pub async fn get_views_last_7_days(
     db: &DatabaseConnection,
     recipe_id: Uuid,
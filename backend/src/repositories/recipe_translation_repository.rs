@@ -4,6 +4,7 @@ use entity::recipe_translations;
 use sea_orm::DatabaseConnection;
 use sea_orm::{ColumnTrait, DbErr};
 use sea_orm::{EntityTrait, QueryFilter};
+use sea_orm::sea_query::{Expr, Func};
 use uuid::Uuid;
 use entity::*;
 
@@ -14,6 +15,18 @@ pub async fn find_all(db: &DatabaseConnection) -> Result<Vec<recipe_translations
         .map_err(Error::from)
 }
 
+/// Titles are compared trimmed and case insensitively.
+pub async fn exists_with_title(db: &DatabaseConnection, language_code: &str, title: &str) -> Result<bool, Error> {
+    use sea_orm::ExprTrait;
+
+    let found = recipe_translations::Entity::find()
+        .filter(recipe_translations::Column::LanguageCode.eq(language_code))
+        .filter(Func::lower(Func::cust("btrim").arg(Expr::col(recipe_translations::Column::Title))).eq(title.trim().to_lowercase()))
+        .one(db)
+        .await?;
+    Ok(found.is_some())
+}
+
 pub async fn find_by_id(
     db: &DatabaseConnection,
     id: Uuid,
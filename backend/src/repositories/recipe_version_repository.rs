@@ -1,3 +1,4 @@
+use crate::dto::collaborator_dto::RecipeCollaboratorRole;
 use crate::dto::recipe_dto::RecipeEditorDto;
 use crate::dto::recipe_version_dto::RecipeVersionDto;
 use crate::dto::user_dto::UserResponseDto;
@@ -5,17 +6,22 @@ use crate::errors::Error;
 use crate::repositories::role_repository;
 use chrono::Utc;
 use entity::{recipe_versions, users};
-use sea_orm::ColumnTrait;
+use sea_orm::{ColumnTrait, ConnectionTrait};
 use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
 use sea_orm::{QueryFilter, QueryOrder};
+use sea_orm::prelude::DateTimeWithTimeZone;
 use serde_json::json;
 use uuid::Uuid;
 
-pub async fn create(
-    db: &DatabaseConnection,
+pub async fn create<C>(
+    db: &C,
     recipe: RecipeEditorDto,
     user_id: Uuid,
-) -> Result<(), Error> {
+    edited_as: Option<RecipeCollaboratorRole>,
+) -> Result<(), Error>
+where
+    C: ConnectionTrait,
+{
     let version_data = serde_json::to_value(&recipe)
         .map_err(|e| Error::InternalServerError(json!({
             "message": "Failed to serialize recipe data for version",
@@ -32,6 +38,7 @@ pub async fn create(
         recipe_id: Set(recipe.id),
         data: Set(version_data),
         edited_by: Set(Some(user_id)),
+        edited_as: Set(edited_as.map(|role| role.as_str().to_string())),
         ..Default::default()
     }
         .insert(db)
@@ -93,6 +100,7 @@ pub async fn get_versions(
                 data: recipe_data,
                 recipe_id: version.recipe_id,
                 edited_by: user_dto,
+                edited_as: version.edited_as.as_deref().and_then(RecipeCollaboratorRole::parse),
                 created_at: version.created_at.with_timezone(&Utc),
             });
         }
@@ -151,6 +159,7 @@ pub async fn get_version(
             data: recipe_data,
             recipe_id: version.recipe_id,
             edited_by: user_dto,
+            edited_as: version.edited_as.as_deref().and_then(RecipeCollaboratorRole::parse),
             created_at: version.created_at.with_timezone(&Utc),
         })
     } else {
@@ -163,4 +172,53 @@ pub async fn get_version(
             "stage": "validation"
         })))
     }
-}
\ No newline at end of file
+}
+pub async fn find_by_recipe(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+) -> Result<Vec<recipe_versions::Model>, Error> {
+    recipe_versions::Entity::find()
+        .filter(recipe_versions::Column::RecipeId.eq(recipe_id))
+        .order_by_asc(recipe_versions::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to fetch recipe versions",
+            "operation": "find_by_recipe",
+            "entity": "recipe_versions",
+            "recipe_id": recipe_id.to_string(),
+            "error": e.to_string(),
+            "stage": "fetch"
+        })))
+}
+
+/// Inserts a version brought in from a cookbook archive, keeping its original date.
+pub async fn import(
+    db: &DatabaseConnection,
+    recipe: RecipeEditorDto,
+    user_id: Uuid,
+    created_at: DateTimeWithTimeZone,
+) -> Result<(), Error> {
+    let version_data = serde_json::to_value(&recipe)?;
+
+    recipe_versions::ActiveModel {
+        recipe_id: Set(recipe.id),
+        data: Set(version_data),
+        edited_by: Set(Some(user_id)),
+        created_at: Set(created_at),
+        ..Default::default()
+    }
+        .insert(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to insert imported recipe version",
+        "operation": "import",
+        "entity": "recipe_versions",
+        "recipe_id": recipe.id.to_string(),
+        "user_id": user_id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+
+    Ok(())
+}
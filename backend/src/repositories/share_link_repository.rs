@@ -0,0 +1,137 @@
+use chrono::Utc;
+use entity::recipe_share_links;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbBackend, EntityTrait, ExprTrait, FromQueryResult,
+    QueryFilter, QueryOrder, Set, Statement,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::dto::share_link_dto::ShareLinkInput;
+use crate::errors::Error;
+
+/// Whether `$2` is the image of recipe `$1` or of one of its steps.
+const RECIPE_IMAGE: &str = r#"
+    SELECT EXISTS (
+        SELECT 1 FROM recipes WHERE id = $1 AND image_url = $2
+        UNION ALL
+        SELECT 1
+        FROM steps s JOIN step_groups g ON g.id = s.step_group_id
+        WHERE g.recipe_id = $1 AND s.image_url = $2
+    ) AS found
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct FoundRow {
+    found: bool,
+}
+
+pub async fn find_by_recipe(db: &DatabaseConnection, recipe_id: Uuid) -> Result<Vec<recipe_share_links::Model>, Error> {
+    let links = recipe_share_links::Entity::find()
+        .filter(recipe_share_links::Column::RecipeId.eq(recipe_id))
+        .order_by_desc(recipe_share_links::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|e| Error::InternalServerError(json!({
+            "message": "Failed to fetch share links",
+            "operation": "find_by_recipe",
+            "entity": "recipe_share_links",
+            "recipe_id": recipe_id.to_string(),
+            "error": e.to_string(),
+            "stage": "select"
+        })))?;
+    Ok(links)
+}
+
+/// The link behind `token`, as long as it is neither revoked nor expired.
+pub async fn find_active(db: &DatabaseConnection, token: &str) -> Result<Option<recipe_share_links::Model>, Error> {
+    let link = recipe_share_links::Entity::find()
+        .filter(recipe_share_links::Column::Token.eq(token))
+        .filter(recipe_share_links::Column::RevokedAt.is_null())
+        .filter(
+            Condition::any()
+                .add(recipe_share_links::Column::ExpiresAt.is_null())
+                .add(recipe_share_links::Column::ExpiresAt.gt(Utc::now())),
+        )
+        .one(db)
+        .await?;
+    Ok(link)
+}
+
+pub async fn create(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    created_by: Uuid,
+    token: String,
+    input: ShareLinkInput,
+) -> Result<recipe_share_links::Model, Error> {
+    let link = recipe_share_links::ActiveModel {
+        recipe_id: Set(recipe_id),
+        created_by: Set(created_by),
+        token: Set(token),
+        label: Set(input.label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty())),
+        expires_at: Set(input.expires_at.map(Into::into)),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to insert share link",
+        "operation": "create",
+        "entity": "recipe_share_links",
+        "recipe_id": recipe_id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+    Ok(link)
+}
+
+/// Revoked links are kept so that their owner still sees how often they were opened.
+pub async fn revoke(db: &DatabaseConnection, recipe_id: Uuid, id: Uuid) -> Result<recipe_share_links::Model, Error> {
+    let link = recipe_share_links::Entity::find_by_id(id)
+        .filter(recipe_share_links::Column::RecipeId.eq(recipe_id))
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Share link not found"})))?;
+    if link.revoked_at.is_some() {
+        return Ok(link);
+    }
+
+    let mut active: recipe_share_links::ActiveModel = link.into();
+    active.revoked_at = Set(Some(Utc::now().into()));
+    let link = active.update(db).await?;
+    Ok(link)
+}
+
+pub async fn record_open(db: &DatabaseConnection, id: Uuid) -> Result<(), Error> {
+    recipe_share_links::Entity::update_many()
+        .col_expr(
+            recipe_share_links::Column::OpenCount,
+            Expr::col(recipe_share_links::Column::OpenCount).add(1),
+        )
+        .col_expr(recipe_share_links::Column::LastOpenedAt, Expr::current_timestamp())
+        .filter(recipe_share_links::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+pub async fn is_recipe_image(db: &DatabaseConnection, recipe_id: Uuid, image_url: &str) -> Result<bool, Error> {
+    let row = FoundRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        RECIPE_IMAGE,
+        [recipe_id.into(), image_url.into()],
+    ))
+    .one(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to look up recipe image",
+        "operation": "is_recipe_image",
+        "entity": "recipes",
+        "recipe_id": recipe_id.to_string(),
+        "error": e.to_string(),
+        "stage": "select"
+    })))?;
+    Ok(row.is_some_and(|r| r.found))
+}
@@ -0,0 +1,373 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use entity::{shopping_list_items, shopping_list_members, shopping_list_recipes, shopping_lists, users};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult, QueryFilter,
+    QueryOrder, Set, Statement, TransactionTrait,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::dto::shopping_list_dto::{Aisle, ShoppingListItemInput, ShoppingListItemUpdate, ShoppingListMemberDto, ShoppingListSummaryDto};
+use crate::errors::Error;
+use crate::utils::shopping_list::MergedItem;
+
+/// Lists owned by `$1` or shared with them, last changed first.
+const USER_LISTS: &str = r#"
+    SELECT
+        l.id,
+        l.name,
+        l.owner_id,
+        l.updated_at,
+        (SELECT count(*) FROM shopping_list_items i WHERE i.shopping_list_id = l.id) AS item_count,
+        (SELECT count(*) FROM shopping_list_items i WHERE i.shopping_list_id = l.id AND i.checked) AS checked_count
+    FROM shopping_lists l
+    WHERE l.owner_id = $1
+       OR l.id IN (SELECT m.shopping_list_id FROM shopping_list_members m WHERE m.user_id = $1)
+    ORDER BY l.updated_at DESC
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct ListSummaryRow {
+    id: Uuid,
+    name: String,
+    owner_id: Uuid,
+    updated_at: DateTime<FixedOffset>,
+    item_count: i64,
+    checked_count: i64,
+}
+
+pub async fn find_for_user(db: &DatabaseConnection, user_id: Uuid) -> Result<Vec<ShoppingListSummaryDto>, Error> {
+    let rows = ListSummaryRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        USER_LISTS,
+        [user_id.into()],
+    ))
+    .all(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to fetch shopping lists",
+        "operation": "find_for_user",
+        "entity": "shopping_lists",
+        "user_id": user_id.to_string(),
+        "error": e.to_string(),
+        "stage": "select"
+    })))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| ShoppingListSummaryDto {
+            id: r.id,
+            name: r.name,
+            owner_id: r.owner_id,
+            item_count: r.item_count,
+            checked_count: r.checked_count,
+            updated_at: r.updated_at.with_timezone(&Utc),
+        })
+        .collect())
+}
+
+pub async fn find_by_id(db: &DatabaseConnection, id: Uuid) -> Result<shopping_lists::Model, Error> {
+    shopping_lists::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Shopping list not found"})))
+}
+
+pub async fn create(db: &DatabaseConnection, owner_id: Uuid, name: String) -> Result<shopping_lists::Model, Error> {
+    let list = shopping_lists::ActiveModel {
+        owner_id: Set(owner_id),
+        name: Set(name),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to insert shopping list",
+        "operation": "create",
+        "entity": "shopping_lists",
+        "owner_id": owner_id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+    Ok(list)
+}
+
+pub async fn rename(db: &DatabaseConnection, list: shopping_lists::Model, name: String) -> Result<(), Error> {
+    let mut active: shopping_lists::ActiveModel = list.into();
+    active.name = Set(name);
+    active.updated_at = Set(Utc::now().into());
+    active.update(db).await?;
+    Ok(())
+}
+
+/// Marks the list as changed, for the order of the lists.
+pub async fn touch(db: &DatabaseConnection, id: Uuid) -> Result<(), Error> {
+    shopping_lists::Entity::update_many()
+        .col_expr(shopping_lists::Column::UpdatedAt, Expr::current_timestamp())
+        .filter(shopping_lists::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), Error> {
+    shopping_lists::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+pub async fn is_member(db: &DatabaseConnection, id: Uuid, user_id: Uuid) -> Result<bool, Error> {
+    let member = shopping_list_members::Entity::find_by_id((id, user_id)).one(db).await?;
+    Ok(member.is_some())
+}
+
+/// The owner first, then the members; everyone a change is pushed to.
+pub async fn find_user_ids(db: &DatabaseConnection, list: &shopping_lists::Model) -> Result<Vec<Uuid>, Error> {
+    let mut ids = vec![list.owner_id];
+    ids.extend(
+        shopping_list_members::Entity::find()
+            .filter(shopping_list_members::Column::ShoppingListId.eq(list.id))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|m| m.user_id),
+    );
+    Ok(ids)
+}
+
+pub async fn find_members(db: &DatabaseConnection, id: Uuid) -> Result<Vec<ShoppingListMemberDto>, Error> {
+    let members = shopping_list_members::Entity::find()
+        .filter(shopping_list_members::Column::ShoppingListId.eq(id))
+        .order_by_asc(shopping_list_members::Column::CreatedAt)
+        .find_also_related(users::Entity)
+        .all(db)
+        .await?;
+
+    Ok(members
+        .into_iter()
+        .filter_map(|(_, user)| user)
+        .map(|user| ShoppingListMemberDto {
+            user_id: user.id,
+            username: user.username,
+            avatar_url: user.avatar_url,
+        })
+        .collect())
+}
+
+pub async fn find_user_by_email(db: &DatabaseConnection, email: &str) -> Result<users::Model, Error> {
+    users::Entity::find()
+        .filter(users::Column::Email.eq(email.trim()))
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "User not found"})))
+}
+
+pub async fn add_member(db: &DatabaseConnection, id: Uuid, user_id: Uuid) -> Result<(), Error> {
+    shopping_list_members::Entity::insert(shopping_list_members::ActiveModel {
+        shopping_list_id: Set(id),
+        user_id: Set(user_id),
+        ..Default::default()
+    })
+    .on_conflict_do_nothing_on([
+        shopping_list_members::Column::ShoppingListId,
+        shopping_list_members::Column::UserId,
+    ])
+    .exec_without_returning(db)
+    .await?;
+    Ok(())
+}
+
+pub async fn remove_member(db: &DatabaseConnection, id: Uuid, user_id: Uuid) -> Result<(), Error> {
+    let result = shopping_list_members::Entity::delete_by_id((id, user_id)).exec(db).await?;
+    if result.rows_affected == 0 {
+        return Err(Error::NotFound(json!({"error": "Member not found"})));
+    }
+    Ok(())
+}
+
+pub async fn find_recipes(db: &DatabaseConnection, id: Uuid) -> Result<Vec<shopping_list_recipes::Model>, Error> {
+    let recipes = shopping_list_recipes::Entity::find()
+        .filter(shopping_list_recipes::Column::ShoppingListId.eq(id))
+        .order_by_asc(shopping_list_recipes::Column::CreatedAt)
+        .all(db)
+        .await?;
+    Ok(recipes)
+}
+
+/// Adds the recipe, or changes its servings when it is already on the list.
+pub async fn save_recipe(db: &DatabaseConnection, id: Uuid, recipe_id: Uuid, servings: i32) -> Result<(), Error> {
+    shopping_list_recipes::Entity::insert(shopping_list_recipes::ActiveModel {
+        shopping_list_id: Set(id),
+        recipe_id: Set(recipe_id),
+        servings: Set(servings),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([
+            shopping_list_recipes::Column::ShoppingListId,
+            shopping_list_recipes::Column::RecipeId,
+        ])
+        .update_column(shopping_list_recipes::Column::Servings)
+        .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await
+    .map_err(|e| Error::InternalServerError(json!({
+        "message": "Failed to add recipe to shopping list",
+        "operation": "save_recipe",
+        "entity": "shopping_list_recipes",
+        "shopping_list_id": id.to_string(),
+        "recipe_id": recipe_id.to_string(),
+        "error": e.to_string(),
+        "stage": "upsert"
+    })))?;
+    Ok(())
+}
+
+pub async fn remove_recipe(db: &DatabaseConnection, id: Uuid, recipe_id: Uuid) -> Result<(), Error> {
+    let result = shopping_list_recipes::Entity::delete_by_id((id, recipe_id)).exec(db).await?;
+    if result.rows_affected == 0 {
+        return Err(Error::NotFound(json!({"error": "Recipe not on this shopping list"})));
+    }
+    Ok(())
+}
+
+/// Sorted by name.
+pub async fn find_items(db: &DatabaseConnection, id: Uuid) -> Result<Vec<shopping_list_items::Model>, Error> {
+    let items = shopping_list_items::Entity::find()
+        .filter(shopping_list_items::Column::ShoppingListId.eq(id))
+        .order_by_asc(shopping_list_items::Column::Name)
+        .order_by_asc(shopping_list_items::Column::CreatedAt)
+        .all(db)
+        .await?;
+    Ok(items)
+}
+
+pub async fn find_item(db: &DatabaseConnection, id: Uuid, item_id: Uuid) -> Result<shopping_list_items::Model, Error> {
+    shopping_list_items::Entity::find_by_id(item_id)
+        .filter(shopping_list_items::Column::ShoppingListId.eq(id))
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Shopping list item not found"})))
+}
+
+/// Replaces the items merged from the recipes. Items still needed keep their id and
+/// checked state; manual items are left alone.
+pub async fn save_merged_items(db: &DatabaseConnection, id: Uuid, items: Vec<MergedItem>) -> Result<(), Error> {
+    let txn = db.begin().await?;
+
+    let keys: Vec<String> = items.iter().map(|i| i.key.clone()).collect();
+    shopping_list_items::Entity::delete_many()
+        .filter(shopping_list_items::Column::ShoppingListId.eq(id))
+        .filter(shopping_list_items::Column::ItemKey.is_not_null())
+        .filter(shopping_list_items::Column::ItemKey.is_not_in(keys))
+        .exec(&txn)
+        .await?;
+
+    if !items.is_empty() {
+        let models = items.into_iter().map(|item| shopping_list_items::ActiveModel {
+            shopping_list_id: Set(id),
+            item_key: Set(Some(item.key)),
+            name: Set(item.name),
+            quantity: Set(item.quantity),
+            unit_id: Set(Some(item.unit_id)),
+            canonical_ingredient_id: Set(item.canonical_ingredient_id),
+            aisle: Set(item.aisle.as_str().to_string()),
+            ..Default::default()
+        });
+        shopping_list_items::Entity::insert_many(models)
+            .on_conflict(
+                OnConflict::columns([shopping_list_items::Column::ShoppingListId, shopping_list_items::Column::ItemKey])
+                    .target_and_where(shopping_list_items::Column::ItemKey.is_not_null())
+                    .update_columns([
+                        shopping_list_items::Column::Name,
+                        shopping_list_items::Column::Quantity,
+                        shopping_list_items::Column::UnitId,
+                        shopping_list_items::Column::CanonicalIngredientId,
+                        shopping_list_items::Column::Aisle,
+                    ])
+                    .value(shopping_list_items::Column::UpdatedAt, Expr::current_timestamp())
+                    .to_owned(),
+            )
+            .exec_without_returning(&txn)
+            .await
+            .map_err(|e| Error::InternalServerError(json!({
+                "message": "Failed to save shopping list items",
+                "operation": "save_merged_items",
+                "entity": "shopping_list_items",
+                "shopping_list_id": id.to_string(),
+                "error": e.to_string(),
+                "stage": "upsert"
+            })))?;
+    }
+
+    txn.commit().await?;
+    Ok(())
+}
+
+pub async fn create_item(
+    db: &DatabaseConnection,
+    id: Uuid,
+    input: ShoppingListItemInput,
+) -> Result<shopping_list_items::Model, Error> {
+    let item = shopping_list_items::ActiveModel {
+        shopping_list_id: Set(id),
+        name: Set(input.name.trim().to_string()),
+        quantity: Set(input.quantity),
+        unit_id: Set(input.unit_id),
+        aisle: Set(input.aisle.unwrap_or_default().as_str().to_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| Error::BadRequest(json!({
+        "message": "Failed to insert shopping list item",
+        "operation": "create_item",
+        "entity": "shopping_list_items",
+        "shopping_list_id": id.to_string(),
+        "error": e.to_string(),
+        "stage": "insert"
+    })))?;
+    Ok(item)
+}
+
+pub async fn update_item(
+    db: &DatabaseConnection,
+    item: shopping_list_items::Model,
+    input: ShoppingListItemUpdate,
+) -> Result<(), Error> {
+    let mut active: shopping_list_items::ActiveModel = item.into();
+    if let Some(checked) = input.checked {
+        active.checked = Set(checked);
+    }
+    if let Some(name) = input.name {
+        active.name = Set(name.trim().to_string());
+    }
+    if let Some(quantity) = input.quantity {
+        active.quantity = Set(Some(quantity));
+    }
+    if let Some(unit_id) = input.unit_id {
+        active.unit_id = Set(Some(unit_id));
+    }
+    if let Some(aisle) = input.aisle {
+        active.aisle = Set(aisle.as_str().to_string());
+    }
+    active.updated_at = Set(Utc::now().into());
+    active.update(db).await.map_err(|e| Error::BadRequest(json!({
+        "message": "Failed to update shopping list item",
+        "operation": "update_item",
+        "entity": "shopping_list_items",
+        "error": e.to_string(),
+        "stage": "update"
+    })))?;
+    Ok(())
+}
+
+pub async fn delete_item(db: &DatabaseConnection, item_id: Uuid) -> Result<(), Error> {
+    shopping_list_items::Entity::delete_by_id(item_id).exec(db).await?;
+    Ok(())
+}
+
+pub fn item_aisle(item: &shopping_list_items::Model) -> Aisle {
+    Aisle::parse(&item.aisle).unwrap_or_default()
+}
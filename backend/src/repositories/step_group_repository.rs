@@ -184,6 +184,7 @@ pub async fn find_all_by_recipe(
                     })
                     .collect(),
                 image_url: step.image_url,
+                duration_minutes: step.duration_minutes,
             });
     }
 
@@ -31,6 +31,7 @@ pub async fn create_unit(txn: &DatabaseConnection, input: UnitInputDto) -> Resul
         name_fr: Set(input.name_fr),
         name_en: Set(input.name_en),
         system: Set(input.system),
+        base_unit_id: Set(input.base_unit_id),
         conversion_factor: Set(input.conversion_factor),
         is_fraction_allowed: Set(input.is_fraction_allowed),
         is_active: Set(input.is_active),
@@ -0,0 +1,457 @@
+use crate::dto::archive_dto::{ArchiveConflictDto, ArchiveConflictKind, ArchiveImportReport, ArchiveImportedDto, ArchiveManifest, ArchiveManifestEntry, ArchiveRecipe, ArchiveVersion};
+use crate::dto::recipe_dto::{CreateRecipeInput, RecipeEditorDto, RecipeResponse};
+use crate::dto::unit_dto::UnitDto;
+use crate::errors::Error;
+use crate::repositories::{recipe_repository, recipe_translation_repository, recipe_version_repository, tag_repository, unit_repository};
+use crate::services::{dietary_service, recipe_service};
+use crate::utils::file_upload::asset_root;
+use crate::utils::unit;
+use sea_orm::DatabaseConnection;
+use sqlx::SqlitePool;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Cursor, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use validator::Validate;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Bumped whenever the archive layout changes in a way older importers can't read.
+const ARCHIVE_FORMAT_VERSION: u32 = 1;
+const MANIFEST_FILE: &str = "manifest.json";
+/// Folder imported images are stored in, below the asset root.
+const IMAGE_DIR: &str = "recipes";
+/// Extensions an archived image may have to be imported.
+const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "webp", "gif"];
+/// Largest entry read from an archive, images included.
+const MAX_ENTRY_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Builds a zip holding `manifest.json`, one `recipes/{id}.json` per recipe of the
+/// author with every translation, and the images those recipes reference under `assets/`.
+pub async fn export(
+    db: &DatabaseConnection,
+    author_id: Uuid,
+    include_versions: bool,
+) -> Result<(String, Vec<u8>), Error> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+    let mut entries = Vec::new();
+    let mut images: Vec<String> = Vec::new();
+
+    for model in recipe_repository::find_by_author(db, author_id).await? {
+        let recipe = match recipe_service::get_by_id(db, model.id, &model.original_language_code, true).await? {
+            RecipeResponse::Editor(recipe) => recipe,
+            RecipeResponse::View(_) => {
+                return Err(Error::InternalServerError(json!({
+                    "message": "Unexpected response type while exporting recipe",
+                    "operation": "export",
+                    "recipe_id": model.id.to_string(),
+                    "expected": "RecipeResponse::Editor",
+                    "received": "RecipeResponse::View",
+                    "stage": "response_validation"
+                })));
+            }
+        };
+
+        let versions = if include_versions {
+            let mut versions = Vec::new();
+            for version in recipe_version_repository::find_by_recipe(db, model.id).await? {
+                // Snapshots written by older builds may not deserialize; they are left out.
+                if let Ok(data) = serde_json::from_value::<RecipeEditorDto>(version.data) {
+                    versions.push(ArchiveVersion {
+                        id: version.id,
+                        data,
+                        created_at: version.created_at,
+                    });
+                }
+            }
+            versions
+        } else {
+            Vec::new()
+        };
+
+        for path in std::iter::once(&recipe).chain(versions.iter().map(|v| &v.data)).flat_map(image_paths) {
+            if !images.contains(&path) {
+                images.push(path);
+            }
+        }
+
+        let file = format!("recipes/{}.json", recipe.id);
+        entries.push(ArchiveManifestEntry {
+            id: recipe.id,
+            title: primary_title(&recipe),
+            file: file.clone(),
+        });
+        zip.start_file(file, options)?;
+        zip.write_all(&serde_json::to_vec_pretty(&ArchiveRecipe { recipe, versions })?)?;
+    }
+
+    // Images that vanished from disk are dropped here; the importer reports them.
+    images.retain(|path| stored_file(path).is_file());
+    for path in &images {
+        zip.start_file(path.as_str(), options)?;
+        zip.write_all(&fs::read(stored_file(path))?)?;
+    }
+
+    let manifest = ArchiveManifest {
+        format_version: ARCHIVE_FORMAT_VERSION,
+        exported_at: chrono::Utc::now(),
+        recipes: entries,
+        units: unit_repository::get_all_admin(db).await?,
+        tags: tag_repository::get_all(db).await?,
+        images,
+    };
+    zip.start_file(MANIFEST_FILE, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+
+    let bytes = zip.finish()?.into_inner();
+    let file_name = format!("cookbook-{}.zip", manifest.exported_at.format("%Y%m%d"));
+    Ok((file_name, bytes))
+}
+
+/// Recreates every recipe of an archive under new ids. Units are matched by code,
+/// tags by name and images are copied under fresh file names; anything that could
+/// not be carried over as-is is listed in the report.
+pub async fn import(
+    db: &DatabaseConnection,
+    dict_db: &SqlitePool,
+    archive_path: &Path,
+    user_id: Uuid,
+    skip_duplicates: bool,
+    lang_code: &str,
+) -> Result<ArchiveImportReport, Error> {
+    let mut archive = ZipArchive::new(fs::File::open(archive_path)?)?;
+
+    let manifest: ArchiveManifest = match read_entry(&mut archive, MANIFEST_FILE)? {
+        Some(bytes) => serde_json::from_slice(&bytes)?,
+        None => {
+            return Err(Error::BadRequest(json!({
+                "message": "The archive has no manifest",
+                "operation": "import",
+                "file": MANIFEST_FILE,
+                "stage": "manifest"
+            })));
+        }
+    };
+    if manifest.format_version > ARCHIVE_FORMAT_VERSION {
+        return Err(Error::UnprocessableEntity(json!({
+            "message": "The archive was written by a newer version",
+            "operation": "import",
+            "format_version": manifest.format_version,
+            "supported_version": ARCHIVE_FORMAT_VERSION,
+            "stage": "manifest"
+        })));
+    }
+
+    fs::create_dir_all(asset_root().join(IMAGE_DIR))?;
+
+    let mut ctx = ImportContext {
+        units: unit_repository::get_all_admin(db).await?,
+        source_units: manifest.units.into_iter().map(|u| (u.id, u)).collect(),
+        resolved_units: HashMap::new(),
+        images: HashMap::new(),
+        conflicts: Vec::new(),
+    };
+    let mut report = ArchiveImportReport {
+        imported: Vec::new(),
+        skipped: Vec::new(),
+        conflicts: Vec::new(),
+    };
+
+    for entry in manifest.recipes {
+        let parsed = read_entry(&mut archive, &entry.file)?
+            .map(|bytes| serde_json::from_slice::<ArchiveRecipe>(&bytes));
+        let ArchiveRecipe { mut recipe, versions } = match parsed {
+            Some(Ok(parsed)) => parsed,
+            Some(Err(e)) => {
+                ctx.conflict(ArchiveConflictKind::InvalidRecipe, entry.id, &entry.title, e.to_string());
+                continue;
+            }
+            None => {
+                ctx.conflict(ArchiveConflictKind::InvalidRecipe, entry.id, &entry.title, format!("{} is missing from the archive", entry.file));
+                continue;
+            }
+        };
+        let source_id = recipe.id;
+        let title = primary_title(&recipe);
+
+        if has_duplicate_title(db, &recipe).await? {
+            let detail = if skip_duplicates { "skipped" } else { "imported as a second copy" };
+            ctx.conflict(ArchiveConflictKind::DuplicateTitle, source_id, &title, detail.to_string());
+            if skip_duplicates {
+                report.skipped.push(source_id);
+                continue;
+            }
+        }
+
+        ctx.remap(&mut recipe, &mut archive, &title)?;
+        let input = to_create_input(recipe, user_id);
+        if let Err(e) = input.validate() {
+            ctx.conflict(ArchiveConflictKind::InvalidRecipe, source_id, &title, e.to_string());
+            continue;
+        }
+
+        // Each entry is imported whole or not at all; a failure is reported and the
+        // next entry is imported regardless.
+        let created = match recipe_repository::create(db, input, lang_code, None).await {
+            Ok(created) => created,
+            Err(e) => {
+                log::error!("Failed to import archived recipe {}: {:?}", source_id, e);
+                ctx.conflict(ArchiveConflictKind::ImportFailed, source_id, &title, "the recipe could not be saved".to_string());
+                continue;
+            }
+        };
+        let restored = async {
+            let version_count = import_versions(db, &mut ctx, &mut archive, versions, created.id, user_id, &title).await?;
+            dietary_service::refresh_recipe(db, dict_db, created.id).await?;
+            Ok::<_, Error>(version_count)
+        };
+        let version_count = match restored.await {
+            Ok(version_count) => version_count,
+            Err(e) => {
+                log::error!("Failed to import the history of archived recipe {}: {:?}", source_id, e);
+                recipe_repository::delete(db, created.id).await?;
+                ctx.conflict(ArchiveConflictKind::ImportFailed, source_id, &title, "the recipe history could not be saved".to_string());
+                continue;
+            }
+        };
+
+        report.imported.push(ArchiveImportedDto {
+            source_id,
+            id: created.id,
+            title,
+            versions: version_count,
+        });
+    }
+
+    report.conflicts = ctx.conflicts;
+    Ok(report)
+}
+
+/// Whether a recipe already has a translation with the same title in the same language.
+async fn has_duplicate_title(db: &DatabaseConnection, recipe: &RecipeEditorDto) -> Result<bool, Error> {
+    for translation in &recipe.translations {
+        if recipe_translation_repository::exists_with_title(db, &translation.language_code, &translation.title).await? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Restores the history of a freshly imported recipe.
+async fn import_versions<R: Read + Seek>(
+    db: &DatabaseConnection,
+    ctx: &mut ImportContext,
+    archive: &mut ZipArchive<R>,
+    versions: Vec<ArchiveVersion>,
+    recipe_id: Uuid,
+    user_id: Uuid,
+    title: &str,
+) -> Result<usize, Error> {
+    let mut version_count = 0;
+    for ArchiveVersion { data: mut version, created_at, .. } in versions {
+        ctx.remap(&mut version, archive, title)?;
+        version.id = recipe_id;
+        recipe_version_repository::import(db, version, user_id, created_at).await?;
+        version_count += 1;
+    }
+    Ok(version_count)
+}
+
+struct ImportContext {
+    units: Vec<UnitDto>,
+    source_units: HashMap<Uuid, UnitDto>,
+    /// Source unit code → local unit, so an unknown unit is reported only once.
+    resolved_units: HashMap<String, UnitDto>,
+    /// Archived image path → local url, `None` when the file is not in the archive.
+    images: HashMap<String, Option<String>>,
+    conflicts: Vec<ArchiveConflictDto>,
+}
+
+impl ImportContext {
+    fn conflict(&mut self, kind: ArchiveConflictKind, source_id: Uuid, title: &str, detail: String) {
+        self.conflicts.push(ArchiveConflictDto {
+            kind,
+            source_id: Some(source_id),
+            title: Some(title.to_string()),
+            detail,
+        });
+    }
+
+    /// Points units and images of an archived recipe at their local counterparts.
+    fn remap<R: Read + Seek>(
+        &mut self,
+        recipe: &mut RecipeEditorDto,
+        archive: &mut ZipArchive<R>,
+        title: &str,
+    ) -> Result<(), Error> {
+        let source_id = recipe.id;
+
+        for ingredient in recipe.ingredient_groups.iter_mut().flat_map(|g| g.ingredients.iter_mut()) {
+            let source = self
+                .source_units
+                .get(&ingredient.unit_id)
+                .cloned()
+                .unwrap_or_else(|| ingredient.unit.clone());
+            if let Some(local) = self.resolve_unit(&source, source_id, title) {
+                ingredient.unit_id = local.id;
+                ingredient.unit = local;
+            }
+        }
+
+        recipe.image_url = self
+            .resolve_image(&recipe.image_url, archive, source_id, title)?
+            .unwrap_or_default();
+        for step in recipe.step_groups.iter_mut().flat_map(|g| g.steps.iter_mut()) {
+            if let Some(url) = step.image_url.take() {
+                step.image_url = self.resolve_image(&url, archive, source_id, title)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_unit(&mut self, source: &UnitDto, source_id: Uuid, title: &str) -> Option<UnitDto> {
+        if let Some(local) = self.resolved_units.get(&source.code) {
+            return Some(local.clone());
+        }
+
+        let local = self
+            .units
+            .iter()
+            .find(|u| u.code == source.code)
+            .or_else(|| unit::find_unit(&source.symbol, &self.units))
+            .cloned();
+        let local = match local {
+            Some(local) => local,
+            None => {
+                let fallback = self.units.iter().find(|u| u.code == "PIECE").cloned()?;
+                self.conflict(
+                    ArchiveConflictKind::UnknownUnit,
+                    source_id,
+                    title,
+                    format!("unit {} ({}) replaced by {}", source.code, source.symbol, fallback.code),
+                );
+                fallback
+            }
+        };
+        self.resolved_units.insert(source.code.clone(), local.clone());
+        Some(local)
+    }
+
+    /// Copies an archived image into `assets/recipes` under a new name. Urls that do
+    /// not point into `assets/` (remote images) are kept untouched; files that are not
+    /// images are left out.
+    fn resolve_image<R: Read + Seek>(
+        &mut self,
+        url: &str,
+        archive: &mut ZipArchive<R>,
+        source_id: Uuid,
+        title: &str,
+    ) -> Result<Option<String>, Error> {
+        let Some(path) = archive_image_path(url) else {
+            return Ok((!url.is_empty()).then(|| url.to_string()));
+        };
+        if let Some(local) = self.images.get(&path) {
+            return Ok(local.clone());
+        }
+
+        let extension = Path::new(&path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .filter(|ext| IMAGE_EXTENSIONS.contains(&ext.as_str()));
+        let Some(extension) = extension else {
+            self.conflict(ArchiveConflictKind::InvalidImage, source_id, title, path.clone());
+            self.images.insert(path, None);
+            return Ok(None);
+        };
+
+        let local = match read_entry(archive, &path)? {
+            Some(bytes) => {
+                let file_name = format!(
+                    "recipe_{}_{}.{}",
+                    Uuid::new_v4(),
+                    chrono::Utc::now().timestamp(),
+                    extension
+                );
+                fs::write(asset_root().join(IMAGE_DIR).join(&file_name), bytes)?;
+                Some(format!("/assets/{}/{}", IMAGE_DIR, file_name))
+            }
+            None => {
+                self.conflict(ArchiveConflictKind::MissingImage, source_id, title, path.clone());
+                None
+            }
+        };
+        self.images.insert(path, local.clone());
+        Ok(local)
+    }
+}
+
+fn to_create_input(recipe: RecipeEditorDto, user_id: Uuid) -> CreateRecipeInput {
+    CreateRecipeInput {
+        author_id: Some(user_id),
+        ..CreateRecipeInput::from(recipe)
+    }
+}
+
+fn primary_title(recipe: &RecipeEditorDto) -> String {
+    recipe
+        .translations
+        .iter()
+        .find(|t| t.language_code == recipe.primary_language)
+        .or_else(|| recipe.translations.first())
+        .map(|t| t.title.clone())
+        .unwrap_or_default()
+}
+
+/// Local image files referenced by a recipe and its steps, as archive paths.
+fn image_paths(recipe: &RecipeEditorDto) -> Vec<String> {
+    std::iter::once(recipe.image_url.as_str())
+        .chain(
+            recipe
+                .step_groups
+                .iter()
+                .flat_map(|g| g.steps.iter())
+                .filter_map(|s| s.image_url.as_deref()),
+        )
+        .filter_map(archive_image_path)
+        .collect()
+}
+
+/// "/assets/recipes/a.png" → "assets/recipes/a.png"; anything outside `assets/` or
+/// climbing out of it is rejected.
+fn archive_image_path(url: &str) -> Option<String> {
+    let path = url.trim().trim_start_matches('/');
+    (path.starts_with("assets/") && !path.split('/').any(|part| part == ".." || part.is_empty()))
+        .then(|| path.to_string())
+}
+
+/// "assets/recipes/a.png" → the file under the asset root.
+fn stored_file(path: &str) -> PathBuf {
+    asset_root().join(path.trim_start_matches("assets/"))
+}
+
+/// Contents of an archive entry, refused past `MAX_ENTRY_BYTES` whatever size the
+/// archive claims for it.
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<Option<Vec<u8>>, Error> {
+    let file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut bytes = Vec::new();
+    file.take(MAX_ENTRY_BYTES + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > MAX_ENTRY_BYTES {
+        return Err(Error::BadRequest(json!({
+            "message": "An entry of the archive is too large",
+            "operation": "import",
+            "file": name,
+            "max_bytes": MAX_ENTRY_BYTES,
+            "stage": "read_entry"
+        })));
+    }
+    Ok(Some(bytes))
+}
@@ -0,0 +1,83 @@
+use crate::domain::user::{AuthenticatedUser, Role};
+use crate::dto::collaborator_dto::{RecipeCollaboratorDto, RecipeCollaboratorInput, RecipeCollaboratorRole, RecipeCollaboratorRoleInput};
+use crate::errors::Error;
+use crate::repositories::{collaborator_repository, recipe_repository};
+use crate::services::recipe_service;
+use entity::recipes;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+/// Anyone who may edit the recipe sees who else does.
+pub async fn get_collaborators(
+    db: &DatabaseConnection,
+    auth: &AuthenticatedUser,
+    recipe_id: Uuid,
+) -> Result<Vec<RecipeCollaboratorDto>, Error> {
+    recipe_service::authorize_edit(db, recipe_id, auth).await?;
+    collaborator_repository::find_by_recipe(db, recipe_id).await
+}
+
+/// Adds the account behind the address to the recipe, or changes its role when it
+/// already collaborates on it.
+pub async fn add_collaborator(
+    db: &DatabaseConnection,
+    auth: &AuthenticatedUser,
+    recipe_id: Uuid,
+    input: RecipeCollaboratorInput,
+) -> Result<Vec<RecipeCollaboratorDto>, Error> {
+    let recipe = require_manager(db, auth, recipe_id).await?;
+    let user = collaborator_repository::find_user_by_email(db, &input.email).await?;
+    if recipe.author_id == Some(user.id) {
+        return Err(Error::BadRequest(json!({
+            "message": "The author of a recipe cannot be added as a collaborator",
+            "operation": "add_collaborator",
+            "entity": "recipe_collaborators",
+            "recipe_id": recipe_id.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    collaborator_repository::save(db, recipe_id, user.id, input.role, auth.user.id).await?;
+    collaborator_repository::find_by_recipe(db, recipe_id).await
+}
+
+pub async fn set_role(
+    db: &DatabaseConnection,
+    auth: &AuthenticatedUser,
+    recipe_id: Uuid,
+    user_id: Uuid,
+    input: RecipeCollaboratorRoleInput,
+) -> Result<Vec<RecipeCollaboratorDto>, Error> {
+    require_manager(db, auth, recipe_id).await?;
+    collaborator_repository::set_role(db, recipe_id, user_id, input.role).await?;
+    collaborator_repository::find_by_recipe(db, recipe_id).await
+}
+
+/// A manager removes a collaborator, or a collaborator leaves the recipe.
+pub async fn remove_collaborator(
+    db: &DatabaseConnection,
+    auth: &AuthenticatedUser,
+    recipe_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), Error> {
+    if user_id != auth.user.id {
+        require_manager(db, auth, recipe_id).await?;
+    }
+    collaborator_repository::remove(db, recipe_id, user_id).await
+}
+
+/// The author of the recipe, its co-authors, or a moderator choose its collaborators.
+async fn require_manager(
+    db: &DatabaseConnection,
+    auth: &AuthenticatedUser,
+    recipe_id: Uuid,
+) -> Result<recipes::Model, Error> {
+    let recipe = recipe_repository::find_by_id(db, recipe_id).await?;
+    if recipe.author_id != Some(auth.user.id)
+        && collaborator_repository::find_role(db, recipe_id, auth.user.id).await? != Some(RecipeCollaboratorRole::CoAuthor)
+    {
+        auth.require_roles(&[Role::Admin, Role::Moderator, Role::Superuser])?;
+    }
+    Ok(recipe)
+}
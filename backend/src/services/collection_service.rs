@@ -0,0 +1,200 @@
+use crate::dto::collection_dto::{
+    CollectionDto, CollectionInput, CollectionOrderInput, CollectionRecipeInput, CollectionSummaryDto,
+    FavoritesToCollectionInput,
+};
+use crate::dto::recipe_dto::RecipeViewDto;
+use crate::errors::Error;
+use crate::repositories::{collection_repository, recipe_repository, recipe_translation_repository};
+use crate::utils::file_upload::move_file_from_tmp;
+use chrono::Utc;
+use entity::collections;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use std::collections::HashSet;
+use std::fs;
+use uuid::Uuid;
+
+pub async fn get_mine(db: &DatabaseConnection, user_id: Uuid) -> Result<Vec<CollectionSummaryDto>, Error> {
+    collection_repository::find_by_owner(db, user_id, Some(user_id)).await
+}
+
+/// Collections shown on a profile: only the public ones, unless it is the viewer's own.
+pub async fn get_by_owner(
+    db: &DatabaseConnection,
+    owner_id: Uuid,
+    viewer_id: Option<Uuid>,
+) -> Result<Vec<CollectionSummaryDto>, Error> {
+    collection_repository::find_by_owner(db, owner_id, viewer_id).await
+}
+
+pub async fn get(
+    db: &DatabaseConnection,
+    id: Uuid,
+    viewer_id: Option<Uuid>,
+    lang_code: &str,
+) -> Result<CollectionDto, Error> {
+    let collection = collection_repository::find_by_id(db, id).await?;
+    if !collection.is_public && Some(collection.owner_id) != viewer_id {
+        return Err(Error::NotFound(json!({"error": "Collection not found"})));
+    }
+    build(db, collection, viewer_id, lang_code).await
+}
+
+pub async fn create(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    mut input: CollectionInput,
+    lang_code: &str,
+) -> Result<CollectionDto, Error> {
+    input.cover_image_url = save_cover(input.cover_image_url, None)?;
+    let collection = collection_repository::create(db, user_id, input).await?;
+    build(db, collection, Some(user_id), lang_code).await
+}
+
+pub async fn update(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    mut input: CollectionInput,
+    lang_code: &str,
+) -> Result<CollectionDto, Error> {
+    let collection = find_owned(db, id, user_id).await?;
+    input.cover_image_url = save_cover(input.cover_image_url, collection.cover_image_url.as_deref())?;
+    let collection = collection_repository::update(db, collection, input).await?;
+    build(db, collection, Some(user_id), lang_code).await
+}
+
+pub async fn delete(db: &DatabaseConnection, user_id: Uuid, id: Uuid) -> Result<(), Error> {
+    find_owned(db, id, user_id).await?;
+    collection_repository::delete(db, id).await
+}
+
+pub async fn add_recipe(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    input: CollectionRecipeInput,
+    lang_code: &str,
+) -> Result<CollectionDto, Error> {
+    find_owned(db, id, user_id).await?;
+    let recipe = collection_repository::find_recipe(db, input.recipe_id)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Recipe not found"})))?;
+    if !recipe_repository::is_visible(db, recipe.id, Some(user_id)).await? {
+        return Err(Error::NotFound(json!({"error": "Recipe not found"})));
+    }
+
+    collection_repository::add_recipe(db, id, recipe.id).await?;
+    get(db, id, Some(user_id), lang_code).await
+}
+
+pub async fn remove_recipe(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    recipe_id: Uuid,
+    lang_code: &str,
+) -> Result<CollectionDto, Error> {
+    find_owned(db, id, user_id).await?;
+    collection_repository::remove_recipe(db, id, recipe_id).await?;
+    get(db, id, Some(user_id), lang_code).await
+}
+
+/// The new order has to name every recipe of the collection exactly once, those the
+/// owner can no longer see included.
+pub async fn reorder(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    input: CollectionOrderInput,
+    lang_code: &str,
+) -> Result<CollectionDto, Error> {
+    find_owned(db, id, user_id).await?;
+    let current: HashSet<Uuid> = collection_repository::find_recipe_ids(db, id).await?.into_iter().collect();
+    let wanted: HashSet<Uuid> = input.recipe_ids.iter().copied().collect();
+    if wanted.len() != input.recipe_ids.len() || wanted != current {
+        return Err(Error::BadRequest(json!({
+            "message": "The order must list every recipe of the collection once",
+            "operation": "reorder",
+            "entity": "collection_recipes",
+            "collection_id": id.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    collection_repository::reorder(db, id, &input.recipe_ids).await?;
+    get(db, id, Some(user_id), lang_code).await
+}
+
+/// Turns the favorites of the user into a collection, keeping the order they were
+/// favorited in.
+pub async fn create_from_favorites(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    input: FavoritesToCollectionInput,
+    lang_code: &str,
+) -> Result<CollectionDto, Error> {
+    let collection = collection_repository::create_from_favorites(
+        db,
+        user_id,
+        CollectionInput {
+            title: input.title,
+            description: input.description,
+            cover_image_url: None,
+            is_public: input.is_public,
+        },
+        input.remove_favorites,
+    )
+    .await?;
+    build(db, collection, Some(user_id), lang_code).await
+}
+
+/// Collections of other users are treated as missing.
+async fn find_owned(db: &DatabaseConnection, id: Uuid, user_id: Uuid) -> Result<collections::Model, Error> {
+    let collection = collection_repository::find_by_id(db, id).await?;
+    if collection.owner_id != user_id {
+        return Err(Error::NotFound(json!({"error": "Collection not found"})));
+    }
+    Ok(collection)
+}
+
+/// Moves a newly uploaded cover out of the temporary folder; an unchanged cover is kept as is.
+fn save_cover(cover: Option<String>, current: Option<&str>) -> Result<Option<String>, Error> {
+    match cover {
+        Some(cover) if Some(cover.as_str()) != current => {
+            let target_dir = "assets/collections";
+            fs::create_dir_all(target_dir)?;
+            Ok(Some(move_file_from_tmp(&cover, target_dir)?))
+        }
+        cover => Ok(cover),
+    }
+}
+
+async fn build(
+    db: &DatabaseConnection,
+    collection: collections::Model,
+    viewer_id: Option<Uuid>,
+    lang_code: &str,
+) -> Result<CollectionDto, Error> {
+    let recipes = collection_repository::find_recipes(db, collection.id, viewer_id).await?;
+
+    let mut dtos = Vec::with_capacity(recipes.len());
+    for recipe in recipes {
+        let translation =
+            recipe_translation_repository::find_translation(db, recipe.id, lang_code, &recipe.original_language_code)
+                .await?;
+        dtos.push(RecipeViewDto::from((recipe, translation, None, None)));
+    }
+
+    Ok(CollectionDto {
+        id: collection.id,
+        owner_id: collection.owner_id,
+        title: collection.title,
+        description: collection.description,
+        cover_image_url: collection.cover_image_url,
+        is_public: collection.is_public,
+        recipes: dtos,
+        created_at: collection.created_at.with_timezone(&Utc),
+        updated_at: collection.updated_at.with_timezone(&Utc),
+    })
+}
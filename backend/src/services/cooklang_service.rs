@@ -0,0 +1,357 @@
+use crate::dto::ingredient_dto::{IngredientInput, IngredientRecipeViewDto, IngredientTranslationInput};
+use crate::dto::ingredient_group_dto::{IngredientGroupInput, IngredientGroupTranslationInput};
+use crate::dto::recipe_dto::{CreateRecipeInput, RecipeResponse, RecipeTranslationInput, RecipeViewDto};
+use crate::dto::step_dto::{StepInput, StepTranslationInput};
+use crate::dto::step_group_dto::{StepGroupInput, StepGroupTranslationInput};
+use crate::dto::tag_dto::InputTag;
+use crate::dto::unit_dto::UnitDto;
+use crate::errors::Error;
+use crate::repositories::unit_repository;
+use crate::services::recipe_service;
+use crate::utils::cooklang::{self, CooklangIngredient, CooklangRecipe, CooklangSection, CooklangStep, StepItem};
+use crate::utils::recipe_text::{default_title, file_name, MAX_INGREDIENT_NAME};
+use crate::utils::unit;
+use migration::prelude::Decimal;
+use regex::Regex;
+use rust_decimal::prelude::FromPrimitive;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+/// Renders a recipe as a `.cook` file. Every ingredient group becomes a section
+/// listing its ingredients, so groups, quantities and notes survive a round trip;
+/// steps then reference ingredients by name on their first mention.
+pub async fn export(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    lang_code: &str,
+) -> Result<(String, String), Error> {
+    let recipe = match recipe_service::get_by_id(db, recipe_id, lang_code, false).await? {
+        RecipeResponse::View(recipe) => recipe,
+        RecipeResponse::Editor(_) => {
+            return Err(Error::InternalServerError(json!({
+                "message": "Unexpected response type while exporting recipe",
+                "operation": "export",
+                "recipe_id": recipe_id.to_string(),
+                "expected": "RecipeResponse::View",
+                "received": "RecipeResponse::Editor",
+                "stage": "response_validation"
+            })));
+        }
+    };
+
+    let document = to_cooklang(&recipe, lang_code);
+    Ok((file_name(&recipe.title, "cook"), cooklang::to_string(&document)))
+}
+
+fn to_cooklang(recipe: &RecipeViewDto, lang_code: &str) -> CooklangRecipe {
+    let mut metadata = vec![
+        ("title".to_string(), recipe.title.clone()),
+        ("servings".to_string(), recipe.servings.to_string()),
+        ("prep time".to_string(), format!("{} minutes", recipe.prep_time_minutes)),
+        ("cook time".to_string(), format!("{} minutes", recipe.cook_time_minutes)),
+        ("locale".to_string(), lang_code.to_string()),
+    ];
+    if !recipe.description.is_empty() {
+        metadata.push(("description".to_string(), recipe.description.clone()));
+    }
+    if !recipe.tags.is_empty() {
+        let tags: Vec<String> = recipe.tags.iter().map(|t| t.name.clone()).collect();
+        metadata.push(("tags".to_string(), format!("[{}]", tags.join(", "))));
+    }
+    if let Some(author) = &recipe.author {
+        metadata.push(("author".to_string(), author.clone()));
+    }
+    if !recipe.image_url.is_empty() {
+        metadata.push(("image".to_string(), recipe.image_url.clone()));
+    }
+
+    let mut sections: Vec<CooklangSection> = recipe
+        .ingredient_groups
+        .iter()
+        .filter(|g| !g.ingredients.is_empty())
+        .map(|group| {
+            let mut items = Vec::new();
+            for (i, ingredient) in group.ingredients.iter().enumerate() {
+                if i > 0 {
+                    items.push(StepItem::Text("\n".to_string()));
+                }
+                items.push(StepItem::Ingredient(to_cooklang_ingredient(ingredient, lang_code)));
+            }
+            CooklangSection {
+                name: Some(group.title.clone()),
+                steps: vec![CooklangStep { items }],
+            }
+        })
+        .collect();
+
+    let mut names: Vec<&str> = recipe
+        .ingredient_groups
+        .iter()
+        .flat_map(|g| g.ingredients.iter())
+        .map(|i| i.name.as_str())
+        .filter(|n| !n.is_empty() && !n.contains(['{', '}', '@', '#', '~']))
+        .collect();
+    names.sort_by_key(|n| std::cmp::Reverse(n.chars().count()));
+    names.dedup();
+
+    for group in &recipe.step_groups {
+        let steps = group
+            .steps
+            .iter()
+            .map(|step| {
+                let mut items = mark_ingredients(&step.instruction, &mut names);
+                if let Some(minutes) = step.duration_minutes.filter(|m| *m > 0) {
+                    items.push(StepItem::Text(" ".to_string()));
+                    items.push(StepItem::Timer { quantity: minutes.to_string(), unit: "minutes".to_string() });
+                }
+                CooklangStep { items }
+            })
+            .collect();
+        sections.push(CooklangSection { name: Some(group.title.clone()), steps });
+    }
+
+    CooklangRecipe { metadata, notes: Vec::new(), sections }
+}
+
+fn to_cooklang_ingredient(ingredient: &IngredientRecipeViewDto, lang_code: &str) -> CooklangIngredient {
+    let unit = &ingredient.unit;
+    let (quantity, unit_label) = if ingredient.quantity.is_zero() {
+        (String::new(), String::new())
+    } else if unit.code == "PIECE" {
+        (ingredient.quantity.normalize().to_string(), String::new())
+    } else {
+        (ingredient.quantity.normalize().to_string(), unit_label(unit, lang_code))
+    };
+    CooklangIngredient {
+        name: ingredient.name.clone(),
+        quantity,
+        unit: unit_label,
+        note: ingredient.note.clone().filter(|n| !n.is_empty()),
+    }
+}
+
+/// Plain symbols ("g", "cup") are kept; symbols like "c. à soupe" are spelled out.
+fn unit_label(unit: &UnitDto, lang_code: &str) -> String {
+    if unit.symbol.chars().all(|c| c.is_ascii_alphabetic()) {
+        unit.symbol.clone()
+    } else if lang_code == "fr" {
+        unit.name_fr.to_lowercase()
+    } else {
+        unit.name_en.to_lowercase()
+    }
+}
+
+/// Turns the first mention of each ingredient name in `text` into an `@name{}`
+/// reference. Names already referenced in an earlier step are skipped.
+fn mark_ingredients(text: &str, names: &mut Vec<&str>) -> Vec<StepItem> {
+    let mut items = vec![StepItem::Text(text.to_string())];
+    let mut marked = Vec::new();
+
+    for (n, name) in names.iter().enumerate() {
+        let Ok(pattern) = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(name))) else {
+            continue;
+        };
+        let found = items.iter().enumerate().find_map(|(i, item)| match item {
+            StepItem::Text(t) => pattern.find(t).map(|m| (i, m.start(), m.end())),
+            _ => None,
+        });
+        let Some((i, start, end)) = found else {
+            continue;
+        };
+        let StepItem::Text(t) = items[i].clone() else {
+            continue;
+        };
+        let replacement = vec![
+            StepItem::Text(t[..start].to_string()),
+            StepItem::Ingredient(CooklangIngredient { name: t[start..end].to_string(), ..Default::default() }),
+            StepItem::Text(t[end..].to_string()),
+        ];
+        items.splice(i..=i, replacement);
+        marked.push(n);
+    }
+
+    for n in marked.into_iter().rev() {
+        names.remove(n);
+    }
+    items.retain(|item| !matches!(item, StepItem::Text(t) if t.is_empty()));
+    items
+}
+
+/// Maps a `.cook` file into a draft `CreateRecipeInput`. Each section becomes a
+/// step group; its ingredients land in an ingredient group of the same name.
+pub async fn import(
+    db: &DatabaseConnection,
+    source: &str,
+    lang_code: Option<&str>,
+    fallback_lang: &str,
+) -> Result<CreateRecipeInput, Error> {
+    let document = cooklang::parse(source);
+
+    let language = lang_code
+        .or_else(|| document.meta("locale"))
+        .or_else(|| document.meta("lang"))
+        .map(|l| l.chars().take(2).collect::<String>().to_lowercase())
+        .unwrap_or_else(|| fallback_lang.to_string());
+
+    let title = document.meta("title").map(str::to_string).ok_or_else(|| {
+        Error::UnprocessableEntity(json!({
+            "message": "The recipe has no title, add a `title:` line to its metadata",
+            "operation": "import",
+            "stage": "title"
+        }))
+    })?;
+
+    let units = unit_repository::get_all_admin(db).await?;
+    let mut ingredient_groups: Vec<(Option<String>, Vec<IngredientInput>)> = Vec::new();
+    let mut step_groups: Vec<(Option<String>, Vec<StepInput>)> = Vec::new();
+    let mut known: Vec<String> = Vec::new();
+
+    for section in &document.sections {
+        for step in &section.steps {
+            let declaration = step.is_ingredient_list();
+            for ingredient in step.ingredients() {
+                let key = ingredient.name.trim().to_lowercase();
+                if !declaration && ingredient.quantity.is_empty() && known.contains(&key) {
+                    continue;
+                }
+                known.push(key);
+
+                let group = group_entry(&mut ingredient_groups, &section.name);
+                let input = to_ingredient_input(ingredient, group.len() as i32, &language, &units)?;
+                group.push(input);
+            }
+            if declaration {
+                continue;
+            }
+
+            let steps = group_entry(&mut step_groups, &section.name);
+            steps.push(StepInput {
+                position: steps.len() as i32,
+                image_url: None,
+                duration_minutes: step.duration_minutes(),
+                translations: vec![StepTranslationInput {
+                    language_code: language.clone(),
+                    instruction: step.text(),
+                }],
+            });
+        }
+    }
+
+    let tags = document
+        .tags()
+        .into_iter()
+        .map(|name| InputTag::New { name: name.to_lowercase() })
+        .collect();
+
+    Ok(CreateRecipeInput {
+        primary_language: language.clone(),
+        translations: vec![RecipeTranslationInput {
+            language_code: language.clone(),
+            title,
+            description: document
+                .meta("description")
+                .map(str::to_string)
+                .unwrap_or_else(|| document.notes.join("\n")),
+        }],
+        image_url: document.meta("image").unwrap_or_default().to_string(),
+        servings: document
+            .meta("servings")
+            .or_else(|| document.meta("serves"))
+            .and_then(cooklang::parse_quantity)
+            .map(|s| s.round() as i32)
+            .unwrap_or(1)
+            .max(1),
+        prep_time_minutes: meta_minutes(&document, &["prep time", "prep_time", "prep"]).unwrap_or(0),
+        cook_time_minutes: meta_minutes(&document, &["cook time", "cook_time", "cook", "time"]).unwrap_or(0),
+        author_id: None,
+        author: document.meta("author").map(str::to_string),
+        is_private: true,
+        tags,
+        ingredient_groups: ingredient_groups
+            .into_iter()
+            .enumerate()
+            .map(|(position, (name, ingredients))| IngredientGroupInput {
+                translations: vec![IngredientGroupTranslationInput {
+                    language_code: language.clone(),
+                    title: name.unwrap_or_else(|| default_title(&language, "Ingredients", "Ingrédients")),
+                }],
+                position: position as i32,
+                ingredients,
+            })
+            .collect(),
+        step_groups: step_groups
+            .into_iter()
+            .enumerate()
+            .map(|(position, (name, steps))| StepGroupInput {
+                position: position as i32,
+                translations: vec![StepGroupTranslationInput {
+                    language_code: language.clone(),
+                    title: name.unwrap_or_else(|| default_title(&language, "Preparation", "Préparation")),
+                }],
+                steps,
+            })
+            .collect(),
+    })
+}
+
+fn group_entry<'a, T>(groups: &'a mut Vec<(Option<String>, Vec<T>)>, name: &Option<String>) -> &'a mut Vec<T> {
+    let index = match groups.iter().position(|(n, _)| n == name) {
+        Some(index) => index,
+        None => {
+            groups.push((name.clone(), Vec::new()));
+            groups.len() - 1
+        }
+    };
+    &mut groups[index].1
+}
+
+fn meta_minutes(document: &CooklangRecipe, keys: &[&str]) -> Option<i32> {
+    keys.iter()
+        .find_map(|key| document.meta(key))
+        .and_then(cooklang::parse_minutes)
+}
+
+/// An unknown unit falls back to "piece" and is kept in the note so nothing is lost.
+fn to_ingredient_input(
+    ingredient: &CooklangIngredient,
+    position: i32,
+    language: &str,
+    units: &[UnitDto],
+) -> Result<IngredientInput, Error> {
+    let quantity = cooklang::parse_quantity(&ingredient.quantity);
+    let matched = unit::find_unit(&ingredient.unit, units);
+
+    let mut note = ingredient.note.clone();
+    if matched.is_none() && !ingredient.unit.is_empty() {
+        note = Some(match note {
+            Some(n) => format!("{}, {}", ingredient.unit, n),
+            None => ingredient.unit.clone(),
+        });
+    }
+
+    let unit = matched
+        .or_else(|| unit::fallback_unit(units, quantity.is_some()))
+        .ok_or_else(|| Error::InternalServerError(json!({
+            "message": "No unit to fall back on for an unrecognised unit",
+            "operation": "import",
+            "entity": "ingredient_units",
+            "unit": &ingredient.unit,
+            "stage": "unit_fallback"
+        })))?;
+
+    Ok(IngredientInput {
+        translations: vec![IngredientTranslationInput {
+            language_code: language.to_string(),
+            data: ingredient.name.chars().take(MAX_INGREDIENT_NAME).collect(),
+            note,
+        }],
+        quantity: quantity
+            .and_then(Decimal::from_f64)
+            .map(|q| q.round_dp(3))
+            .unwrap_or(Decimal::ZERO),
+        unit_id: unit.id,
+        position,
+        canonical_ingredient_id: None,
+    })
+}
@@ -0,0 +1,29 @@
+use crate::dto::density_dto::{DensityDto, DensityInputDto};
+use crate::errors::Error;
+use crate::repositories::density_repository;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+pub async fn get_all(db: &DatabaseConnection) -> Result<Vec<DensityDto>, Error> {
+    let result = density_repository::get_all(db).await?;
+    Ok(result)
+}
+pub async fn get(db: &DatabaseConnection, density_id: Uuid) -> Result<DensityDto, Error> {
+    let result = density_repository::find_by_id(db, density_id).await?;
+    Ok(result)
+}
+pub async fn create(db: &DatabaseConnection, new_density: DensityInputDto) -> Result<DensityDto, Error> {
+    let result = density_repository::create(db, new_density).await?;
+    Ok(result)
+}
+pub async fn update(
+    db: &DatabaseConnection,
+    density_id: Uuid,
+    updated_density: DensityInputDto,
+) -> Result<DensityDto, Error> {
+    let result = density_repository::update(db, density_id, updated_density).await?;
+    Ok(result)
+}
+pub async fn delete(db: &DatabaseConnection, density_id: Uuid) -> Result<(), Error> {
+    density_repository::delete(db, density_id).await
+}
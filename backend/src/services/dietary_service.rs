@@ -0,0 +1,85 @@
+use crate::domain::user::{AuthenticatedUser, Role};
+use crate::dto::dietary_dto::{
+    DietaryOverridesInput, DietaryRefreshResultDto, DietaryRuleDto, DietaryRuleInput, RecipeDietaryLabelsDto,
+};
+use crate::errors::Error;
+use crate::recipe_parser::dictionary;
+use crate::repositories::{dietary_repository, recipe_repository};
+use crate::services::ingredient_service;
+use crate::utils::dietary;
+use sea_orm::DatabaseConnection;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub async fn get_rules(db: &DatabaseConnection) -> Result<Vec<DietaryRuleDto>, Error> {
+    dietary_repository::find_rules(db).await
+}
+
+pub async fn create_rule(db: &DatabaseConnection, input: DietaryRuleInput) -> Result<DietaryRuleDto, Error> {
+    let rule = dietary_repository::create_rule(db, input).await?;
+    dietary_repository::refresh_all(db).await?;
+    Ok(rule)
+}
+
+pub async fn delete_rule(db: &DatabaseConnection, id: Uuid) -> Result<(), Error> {
+    dietary_repository::delete_rule(db, id).await?;
+    dietary_repository::refresh_all(db).await?;
+    Ok(())
+}
+
+/// Creates the rules the English name of each lexicon ingredient suggests, then
+/// relabels every recipe.
+pub async fn seed_rules(db: &DatabaseConnection, dict_db: &SqlitePool) -> Result<DietaryRefreshResultDto, Error> {
+    let rules = dictionary::lexicon_ingredients(dict_db)
+        .await?
+        .into_iter()
+        .flat_map(|i| {
+            dietary::keyword_categories(&i.term_en)
+                .into_iter()
+                .map(move |category| (category, i.id))
+        })
+        .collect();
+    let rules_created = dietary_repository::add_lexicon_rules(db, rules).await?;
+
+    ingredient_service::sync_lexicon_links(db, dict_db).await?;
+    let recipes = dietary_repository::refresh_all(db).await?;
+
+    Ok(DietaryRefreshResultDto { rules_created, recipes })
+}
+
+/// Relabels every recipe, once ingredient texts edited since the last sync are
+/// resolved against the lexicon.
+pub async fn refresh(db: &DatabaseConnection, dict_db: &SqlitePool) -> Result<DietaryRefreshResultDto, Error> {
+    ingredient_service::sync_lexicon_links(db, dict_db).await?;
+    let recipes = dietary_repository::refresh_all(db).await?;
+
+    Ok(DietaryRefreshResultDto { rules_created: 0, recipes })
+}
+
+/// Relabels a recipe after it was saved. Its ingredient lines were rewritten, so
+/// their lexicon entries are resolved again first.
+pub async fn refresh_recipe(db: &DatabaseConnection, dict_db: &SqlitePool, recipe_id: Uuid) -> Result<(), Error> {
+    ingredient_service::sync_recipe_links(db, dict_db, recipe_id).await?;
+    dietary_repository::refresh_recipe(db, recipe_id).await
+}
+
+pub async fn get_labels(db: &DatabaseConnection, recipe_id: Uuid) -> Result<RecipeDietaryLabelsDto, Error> {
+    recipe_repository::find_by_id(db, recipe_id).await?;
+    dietary_repository::find_by_recipe(db, recipe_id).await
+}
+
+/// Only the author of the recipe, or a moderator, may override its labels.
+pub async fn set_overrides(
+    db: &DatabaseConnection,
+    auth: &AuthenticatedUser,
+    recipe_id: Uuid,
+    input: DietaryOverridesInput,
+) -> Result<RecipeDietaryLabelsDto, Error> {
+    let recipe = recipe_repository::find_by_id(db, recipe_id).await?;
+    if recipe.author_id != Some(auth.user.id) {
+        auth.require_roles(&[Role::Admin, Role::Moderator, Role::Superuser])?;
+    }
+
+    dietary_repository::save_overrides(db, recipe_id, &input.overrides).await?;
+    dietary_repository::find_by_recipe(db, recipe_id).await
+}
@@ -0,0 +1,271 @@
+use crate::domain::user::{AuthenticatedUser, Role};
+use crate::dto::household_dto::{
+    HouseholdDto, HouseholdInput, HouseholdInvitationDto, HouseholdInvitationInput, HouseholdMemberInput, HouseholdRole,
+    HouseholdSummaryDto,
+};
+use crate::dto::recipe_dto::RecipeViewDto;
+use crate::dto::user_dto::UserResponseDto;
+use crate::errors::Error;
+use crate::repositories::{household_repository, recipe_repository, recipe_translation_repository};
+use crate::utils::email_service::send_household_invitation;
+use chrono::{Duration, Utc};
+use entity::households;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+/// How long an invitation can be accepted.
+const INVITATION_DAYS: i64 = 7;
+/// How many invitations a household may send in a day.
+const INVITATIONS_PER_DAY: u64 = 20;
+
+pub async fn get_all(db: &DatabaseConnection, user_id: Uuid) -> Result<Vec<HouseholdSummaryDto>, Error> {
+    household_repository::find_for_user(db, user_id).await
+}
+
+pub async fn get(db: &DatabaseConnection, user_id: Uuid, id: Uuid) -> Result<HouseholdDto, Error> {
+    let (household, role) = find_shared(db, id, user_id).await?;
+    build(db, household, role).await
+}
+
+pub async fn create(db: &DatabaseConnection, user_id: Uuid, input: HouseholdInput) -> Result<HouseholdDto, Error> {
+    let household = household_repository::create(db, user_id, input.name).await?;
+    build(db, household, HouseholdRole::Owner).await
+}
+
+pub async fn rename(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    input: HouseholdInput,
+) -> Result<HouseholdDto, Error> {
+    let household = find_owned(db, id, user_id).await?;
+    let household = household_repository::rename(db, household, input.name).await?;
+    build(db, household, HouseholdRole::Owner).await
+}
+
+/// Its recipes stay with their authors, private to them again.
+pub async fn delete(db: &DatabaseConnection, user_id: Uuid, id: Uuid) -> Result<(), Error> {
+    find_owned(db, id, user_id).await?;
+    household_repository::delete(db, id).await
+}
+
+/// Emails an invitation to join the household. The address does not need an account yet.
+pub async fn invite(
+    db: &DatabaseConnection,
+    user: &UserResponseDto,
+    id: Uuid,
+    input: HouseholdInvitationInput,
+) -> Result<HouseholdInvitationDto, Error> {
+    let household = find_owned(db, id, user.id).await?;
+    if input.role == HouseholdRole::Owner {
+        return Err(Error::BadRequest(json!({
+            "message": "Members can only be invited as editor or viewer",
+            "operation": "invite",
+            "entity": "household_invitations",
+            "household_id": id.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    let sent_today = household_repository::count_invitations_since(db, id, Utc::now() - Duration::days(1)).await?;
+    if sent_today >= INVITATIONS_PER_DAY {
+        return Err(Error::TooManyRequests(json!({
+            "message": "This household has sent too many invitations today",
+            "operation": "invite",
+            "entity": "household_invitations",
+            "household_id": id.to_string(),
+            "stage": "rate_limit"
+        })));
+    }
+
+    let expires_at = Utc::now() + Duration::days(INVITATION_DAYS);
+    let invitation =
+        household_repository::create_invitation(db, id, user.id, &input.email, input.role, expires_at).await?;
+    send_household_invitation(&invitation.email, &user.username, &household.name, &invitation.token)?;
+
+    Ok(HouseholdInvitationDto {
+        id: invitation.id,
+        email: invitation.email,
+        role: input.role,
+        expires_at: invitation.expires_at.with_timezone(&Utc),
+        created_at: invitation.created_at.with_timezone(&Utc),
+    })
+}
+
+pub async fn revoke_invitation(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    invitation_id: Uuid,
+) -> Result<(), Error> {
+    find_owned(db, id, user_id).await?;
+    household_repository::revoke_invitation(db, id, invitation_id).await
+}
+
+/// An invitation only works for the account of the address it was sent to.
+pub async fn accept_invitation(
+    db: &DatabaseConnection,
+    user: &UserResponseDto,
+    token: Uuid,
+) -> Result<HouseholdDto, Error> {
+    let invitation = household_repository::find_pending_invitation(db, token).await?;
+    if !invitation.email.eq_ignore_ascii_case(user.email.trim()) {
+        return Err(Error::NotFound(json!({"error": "Invitation not found"})));
+    }
+
+    let household = household_repository::find_by_id(db, invitation.household_id).await?;
+    if household.owner_id == user.id {
+        return Err(Error::BadRequest(json!({
+            "message": "The owner already belongs to the household",
+            "operation": "accept_invitation",
+            "entity": "household_invitations",
+            "household_id": household.id.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    let role = HouseholdRole::parse(&invitation.role).unwrap_or(HouseholdRole::Viewer);
+    household_repository::accept_invitation(db, invitation, user.id).await?;
+    build(db, household, role).await
+}
+
+pub async fn set_member_role(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    member_id: Uuid,
+    input: HouseholdMemberInput,
+) -> Result<HouseholdDto, Error> {
+    let household = find_owned(db, id, user_id).await?;
+    if input.role == HouseholdRole::Owner || member_id == household.owner_id {
+        return Err(Error::BadRequest(json!({
+            "message": "The owner of a household cannot be changed",
+            "operation": "set_member_role",
+            "entity": "household_members",
+            "household_id": id.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    household_repository::set_member_role(db, id, member_id, input.role).await?;
+    build(db, household, HouseholdRole::Owner).await
+}
+
+/// The owner removes a member, or a member leaves the household.
+pub async fn remove_member(db: &DatabaseConnection, user_id: Uuid, id: Uuid, member_id: Uuid) -> Result<(), Error> {
+    let (household, _) = find_shared(db, id, user_id).await?;
+    if household.owner_id != user_id && member_id != user_id {
+        return Err(Error::Forbidden(json!({"message": "Unauthorized"})));
+    }
+    if member_id == household.owner_id {
+        return Err(Error::BadRequest(json!({
+            "message": "The owner cannot leave the household, delete it instead",
+            "operation": "remove_member",
+            "entity": "household_members",
+            "household_id": id.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    household_repository::remove_member(db, id, member_id).await
+}
+
+pub async fn get_recipes(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    lang_code: &str,
+) -> Result<Vec<RecipeViewDto>, Error> {
+    find_shared(db, id, user_id).await?;
+    let recipes = household_repository::find_recipes(db, id).await?;
+
+    let mut dtos = Vec::with_capacity(recipes.len());
+    for recipe in recipes {
+        let translation =
+            recipe_translation_repository::find_translation(db, recipe.id, lang_code, &recipe.original_language_code)
+                .await?;
+        dtos.push(RecipeViewDto::from((recipe, translation, None, None)));
+    }
+    Ok(dtos)
+}
+
+/// Editors share recipes they wrote with the household; moderators may share any recipe.
+/// A recipe belongs to one household at a time, so it leaves its previous one.
+pub async fn add_recipe(
+    db: &DatabaseConnection,
+    auth: &AuthenticatedUser,
+    id: Uuid,
+    recipe_id: Uuid,
+) -> Result<(), Error> {
+    let (_, role) = find_shared(db, id, auth.user.id).await?;
+    if !role.can_edit() {
+        return Err(Error::Forbidden(json!({"message": "Unauthorized"})));
+    }
+    let recipe = recipe_repository::find_by_id(db, recipe_id).await?;
+    if recipe.author_id != Some(auth.user.id) {
+        auth.require_roles(&[Role::Admin, Role::Moderator, Role::Superuser])?;
+    }
+
+    household_repository::set_recipe_household(db, recipe_id, Some(id)).await
+}
+
+/// The owner of the household, or the author of the recipe, takes it out of the household.
+pub async fn remove_recipe(
+    db: &DatabaseConnection,
+    auth: &AuthenticatedUser,
+    id: Uuid,
+    recipe_id: Uuid,
+) -> Result<(), Error> {
+    let (_, role) = find_shared(db, id, auth.user.id).await?;
+    let recipe = recipe_repository::find_by_id(db, recipe_id).await?;
+    if recipe.household_id != Some(id) {
+        return Err(Error::NotFound(json!({"error": "Recipe not in household"})));
+    }
+    if role != HouseholdRole::Owner && recipe.author_id != Some(auth.user.id) {
+        auth.require_roles(&[Role::Admin, Role::Moderator, Role::Superuser])?;
+    }
+
+    household_repository::set_recipe_household(db, recipe_id, None).await
+}
+
+/// Households are only visible to their members; to anyone else they do not exist.
+async fn find_shared(
+    db: &DatabaseConnection,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<(households::Model, HouseholdRole), Error> {
+    let household = household_repository::find_by_id(db, id).await?;
+    match household_repository::find_role(db, id, user_id).await? {
+        Some(role) => Ok((household, role)),
+        None => Err(Error::NotFound(json!({"error": "Household not found"}))),
+    }
+}
+
+async fn find_owned(db: &DatabaseConnection, id: Uuid, user_id: Uuid) -> Result<households::Model, Error> {
+    let (household, role) = find_shared(db, id, user_id).await?;
+    if role != HouseholdRole::Owner {
+        return Err(Error::Forbidden(json!({"message": "Unauthorized"})));
+    }
+    Ok(household)
+}
+
+async fn build(db: &DatabaseConnection, household: households::Model, role: HouseholdRole) -> Result<HouseholdDto, Error> {
+    let members = household_repository::find_members(db, &household).await?;
+    let invitations = if role == HouseholdRole::Owner {
+        household_repository::find_invitations(db, household.id).await?
+    } else {
+        Vec::new()
+    };
+
+    Ok(HouseholdDto {
+        id: household.id,
+        name: household.name,
+        owner_id: household.owner_id,
+        role,
+        members,
+        invitations,
+        created_at: household.created_at.with_timezone(&Utc),
+        updated_at: household.updated_at.with_timezone(&Utc),
+    })
+}
@@ -1,14 +1,96 @@
-use crate::dto::ingredient_dto::IngredientViewDto;
+use crate::app::state::AppState;
+use crate::dto::ingredient_dto::{CanonicalIngredientAisleInput, CanonicalIngredientDto, CatalogSeedResultDto, MergeCanonicalIngredientsInput};
 use crate::errors::Error;
-use crate::repositories::ingredient_repository;
+use crate::recipe_parser::dictionary;
+use crate::repositories::{canonical_ingredient_repository, ingredient_lexicon_repository};
+use crate::services::dietary_service;
 use sea_orm::DatabaseConnection;
+use sqlx::SqlitePool;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// How often ingredient texts edited outside of a recipe save are resolved.
+const LEXICON_SYNC_INTERVAL: Duration = Duration::from_secs(10 * 60);
 
 pub async fn get_all(
     db: &DatabaseConnection,
     search: Option<String>,
     limit: i32,
     lang_code: &str,
-)->Result<Vec<IngredientViewDto>, Error>{
-    let ingredients: Vec<IngredientViewDto> = ingredient_repository::get_all(db, search, limit, lang_code).await?;
+)->Result<Vec<CanonicalIngredientDto>, Error>{
+    let ingredients = canonical_ingredient_repository::search(db, search.as_deref(), limit.max(0) as u64, lang_code).await?;
     Ok(ingredients)
-}
\ No newline at end of file
+}
+
+pub async fn get(db: &DatabaseConnection, id: Uuid, lang_code: &str) -> Result<CanonicalIngredientDto, Error> {
+    canonical_ingredient_repository::find_by_id(db, id, lang_code).await
+}
+
+/// Creates the catalog entries missing from the lexicon, then links the recipe lines
+/// whose text resolves to one of them.
+pub async fn seed_catalog(db: &DatabaseConnection, dict_db: &SqlitePool) -> Result<CatalogSeedResultDto, Error> {
+    let lexicon = dictionary::lexicon_ingredients(dict_db).await?;
+    let created = canonical_ingredient_repository::seed_from_lexicon(db, lexicon).await?;
+
+    sync_lexicon_links(db, dict_db).await?;
+    let linked = canonical_ingredient_repository::link_from_lexicon(db).await?;
+
+    Ok(CatalogSeedResultDto { created, linked })
+}
+
+/// Merges duplicates into the target, then relabels the recipes using it: rules of
+/// the duplicates now apply through the target.
+pub async fn merge(
+    db: &DatabaseConnection,
+    dict_db: &SqlitePool,
+    target_id: Uuid,
+    input: MergeCanonicalIngredientsInput,
+    lang_code: &str,
+) -> Result<CanonicalIngredientDto, Error> {
+    let recipe_ids = canonical_ingredient_repository::merge(db, target_id, &input.source_ids).await?;
+    for recipe_id in recipe_ids {
+        dietary_service::refresh_recipe(db, dict_db, recipe_id).await?;
+    }
+    canonical_ingredient_repository::find_by_id(db, target_id, lang_code).await
+}
+
+pub async fn set_aisle(
+    db: &DatabaseConnection,
+    id: Uuid,
+    input: CanonicalIngredientAisleInput,
+    lang_code: &str,
+) -> Result<CanonicalIngredientDto, Error> {
+    canonical_ingredient_repository::set_aisle(db, id, input.aisle).await?;
+    canonical_ingredient_repository::find_by_id(db, id, lang_code).await
+}
+
+/// Resolves the lexicon entry of ingredient texts added or edited since the last sync.
+pub async fn sync_lexicon_links(db: &DatabaseConnection, dict_db: &SqlitePool) -> Result<(), Error> {
+    resolve_links(db, dict_db, None).await
+}
+
+/// Resolves the ingredient texts edited outside of a recipe save, every few minutes,
+/// for as long as the server runs.
+pub async fn run_lexicon_sync(state: AppState) {
+    let mut interval = tokio::time::interval(LEXICON_SYNC_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = sync_lexicon_links(&state.db, &state.dict_db).await {
+            log::error!("Failed to sync ingredient lexicon links: {:?}", e);
+        }
+    }
+}
+
+/// Same as [`sync_lexicon_links`], for the lines of one recipe after it was saved.
+pub async fn sync_recipe_links(db: &DatabaseConnection, dict_db: &SqlitePool, recipe_id: Uuid) -> Result<(), Error> {
+    resolve_links(db, dict_db, Some(recipe_id)).await
+}
+
+async fn resolve_links(db: &DatabaseConnection, dict_db: &SqlitePool, recipe_id: Option<Uuid>) -> Result<(), Error> {
+    let mut links = Vec::new();
+    for (translation_id, text) in ingredient_lexicon_repository::find_stale(db, recipe_id).await? {
+        let lexicon_id = dictionary::resolve_ingredient(&text, dict_db).await?;
+        links.push((translation_id, lexicon_id, text));
+    }
+    ingredient_lexicon_repository::save_links(db, links).await
+}
@@ -0,0 +1,216 @@
+use crate::dto::ingredient_dto::{IngredientInput, IngredientTranslationInput};
+use crate::dto::ingredient_group_dto::{IngredientGroupInput, IngredientGroupTranslationInput};
+use crate::dto::recipe_dto::{CreateRecipeInput, RecipeResponse, RecipeTranslationInput};
+use crate::dto::recipe_ocr::OcrMatchMetadata;
+use crate::dto::step_dto::{StepInput, StepTranslationInput};
+use crate::dto::step_group_dto::{StepGroupInput, StepGroupTranslationInput};
+use crate::dto::tag_dto::InputTag;
+use crate::dto::unit_dto::UnitDto;
+use crate::errors::Error;
+use crate::recipe_parser::dictionary;
+use crate::repositories::unit_repository;
+use crate::services::recipe_service;
+use crate::utils::recipe_text::{default_title, MAX_INGREDIENT_NAME};
+use crate::utils::{json_ld, unit};
+use migration::prelude::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use sea_orm::DatabaseConnection;
+use serde_json::{json, Value as JsonValue};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub async fn export(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    lang_code: &str,
+    origin: &str,
+) -> Result<JsonValue, Error> {
+    match recipe_service::get_by_id(db, recipe_id, lang_code, false).await? {
+        RecipeResponse::View(recipe) => Ok(json_ld::to_recipe_node(&recipe, lang_code, origin)),
+        RecipeResponse::Editor(_) => Err(Error::InternalServerError(json!({
+            "message": "Unexpected response type while exporting recipe",
+            "operation": "export",
+            "recipe_id": recipe_id.to_string(),
+            "expected": "RecipeResponse::View",
+            "received": "RecipeResponse::Editor",
+            "stage": "response_validation"
+        }))),
+    }
+}
+
+/// Maps a schema.org `Recipe` document into a draft `CreateRecipeInput`. Nothing is
+/// saved: the draft goes back to the editor like an OCR result.
+pub async fn import(
+    db: &DatabaseConnection,
+    dict_db: &SqlitePool,
+    document: JsonValue,
+    lang_code: Option<&str>,
+    fallback_lang: &str,
+) -> Result<CreateRecipeInput, Error> {
+    let recipe = json_ld::find_recipe_node(&document).ok_or_else(|| {
+        Error::BadRequest(json!({
+            "message": "No schema.org Recipe found in the document",
+            "operation": "import",
+            "stage": "recipe_lookup"
+        }))
+    })?;
+
+    let language = lang_code
+        .map(str::to_string)
+        .or_else(|| json_ld::text(recipe.get("inLanguage")))
+        .map(|l| l.chars().take(2).collect::<String>().to_lowercase())
+        .unwrap_or_else(|| fallback_lang.to_string());
+
+    let title = json_ld::text(recipe.get("name")).ok_or_else(|| {
+        Error::UnprocessableEntity(json!({
+            "message": "The recipe has no name",
+            "operation": "import",
+            "stage": "title"
+        }))
+    })?;
+
+    let prep = json_ld::text(recipe.get("prepTime")).and_then(|d| json_ld::parse_duration(&d));
+    let cook = json_ld::text(recipe.get("cookTime")).and_then(|d| json_ld::parse_duration(&d));
+    let total = json_ld::text(recipe.get("totalTime")).and_then(|d| json_ld::parse_duration(&d));
+    let prep_time_minutes = prep.unwrap_or(0);
+    let cook_time_minutes = cook
+        .or_else(|| total.map(|t| (t - prep_time_minutes).max(0)))
+        .unwrap_or(0);
+
+    let units = unit_repository::get_all_admin(db).await?;
+    let mut ingredients = Vec::new();
+    for (position, line) in json_ld::ingredient_lines(recipe).iter().enumerate() {
+        ingredients.push(resolve_ingredient(line, position as i32, &language, &units, dict_db).await?);
+    }
+
+    let ingredient_groups = if ingredients.is_empty() {
+        Vec::new()
+    } else {
+        vec![IngredientGroupInput {
+            translations: vec![IngredientGroupTranslationInput {
+                language_code: language.clone(),
+                title: default_title(&language, "Ingredients", "Ingrédients"),
+            }],
+            position: 0,
+            ingredients,
+        }]
+    };
+
+    let step_groups = json_ld::instruction_sections(recipe)
+        .into_iter()
+        .filter(|(_, steps)| !steps.is_empty())
+        .enumerate()
+        .map(|(position, (name, steps))| StepGroupInput {
+            position: position as i32,
+            translations: vec![StepGroupTranslationInput {
+                language_code: language.clone(),
+                title: name.unwrap_or_else(|| default_title(&language, "Preparation", "Préparation")),
+            }],
+            steps: steps
+                .into_iter()
+                .enumerate()
+                .map(|(i, instruction)| StepInput {
+                    position: i as i32,
+                    image_url: None,
+                    duration_minutes: None,
+                    translations: vec![StepTranslationInput {
+                        language_code: language.clone(),
+                        instruction,
+                    }],
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(CreateRecipeInput {
+        primary_language: language.clone(),
+        translations: vec![RecipeTranslationInput {
+            language_code: language,
+            title,
+            description: json_ld::text(recipe.get("description")).unwrap_or_default(),
+        }],
+        image_url: json_ld::image_url(recipe.get("image")).unwrap_or_default(),
+        servings: recipe.get("recipeYield").and_then(json_ld::parse_yield).unwrap_or(1).max(1),
+        prep_time_minutes,
+        cook_time_minutes,
+        author_id: None,
+        author: json_ld::text(recipe.get("author")),
+        is_private: true,
+        tags: tags(recipe),
+        ingredient_groups,
+        step_groups,
+    })
+}
+
+/// Runs an ingredient line through the OCR dictionary and maps the unit it finds
+/// onto `ingredient_units`.
+async fn resolve_ingredient(
+    line: &str,
+    position: i32,
+    language: &str,
+    units: &[UnitDto],
+    dict_db: &SqlitePool,
+) -> Result<IngredientInput, Error> {
+    let (quantity, unit, ingredient, _, display_name, _) =
+        dictionary::resolve_line(line, dict_db).await?;
+
+    let unit = unit
+        .as_ref()
+        .and_then(|meta| match_unit(meta, units))
+        .or_else(|| unit::fallback_unit(units, quantity.is_some()))
+        .ok_or_else(|| Error::InternalServerError(json!({
+            "message": "No unit to fall back on for an unrecognised unit",
+            "operation": "import",
+            "entity": "ingredient_units",
+            "line": line,
+            "stage": "unit_fallback"
+        })))?;
+
+    let name = ingredient
+        .map(|meta| if language == "fr" { meta.term_fr } else { meta.term_en })
+        .filter(|n| !n.is_empty())
+        .or_else(|| Some(display_name).filter(|n| !n.is_empty()))
+        .unwrap_or_else(|| line.to_string());
+    let name: String = name.chars().take(MAX_INGREDIENT_NAME).collect();
+    let note = (name != line).then(|| line.to_string());
+
+    Ok(IngredientInput {
+        translations: vec![IngredientTranslationInput {
+            language_code: language.to_string(),
+            data: name,
+            note,
+        }],
+        quantity: quantity
+            .and_then(Decimal::from_f32)
+            .map(|q| q.round_dp(3))
+            .unwrap_or(Decimal::ZERO),
+        unit_id: unit.id,
+        position,
+        canonical_ingredient_id: None,
+    })
+}
+
+fn match_unit<'a>(meta: &OcrMatchMetadata, units: &'a [UnitDto]) -> Option<&'a UnitDto> {
+    [&meta.raw_token, &meta.term_en, &meta.term_fr]
+        .into_iter()
+        .find_map(|term| unit::find_unit(term, units))
+}
+
+/// `keywords`, `recipeCategory` and `recipeCuisine` all become tags.
+fn tags(recipe: &JsonValue) -> Vec<InputTag> {
+    let mut names: Vec<String> = Vec::new();
+    for key in ["keywords", "recipeCategory", "recipeCuisine"] {
+        let values: Vec<String> = match recipe.get(key) {
+            Some(JsonValue::String(s)) => s.split(',').map(str::to_string).collect(),
+            Some(JsonValue::Array(items)) => items.iter().filter_map(|i| json_ld::text(Some(i))).collect(),
+            _ => Vec::new(),
+        };
+        for value in values {
+            let value = value.trim().to_lowercase();
+            if !value.is_empty() && !names.contains(&value) {
+                names.push(value);
+            }
+        }
+    }
+    names.into_iter().map(|name| InputTag::New { name }).collect()
+}
@@ -0,0 +1,299 @@
+use crate::dto::meal_plan_dto::{
+    MealPlanCopyWeekInput, MealPlanDayDto, MealPlanDto, MealPlanFeedDto, MealPlanInput, MealPlanMoveInput,
+    MealPlanWarning, MealPlanWeekDto, MealSlot,
+};
+use crate::errors::Error;
+use crate::repositories::{meal_plan_repository, recipe_repository, recipe_translation_repository};
+use crate::utils::icalendar::{self, CalendarEvent};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use entity::{meal_plans, recipes};
+use rand::distr::Alphanumeric;
+use rand::Rng;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+/// How far back and ahead the calendar feed reaches.
+const FEED_DAYS_BEFORE: i64 = 28;
+const FEED_DAYS_AFTER: i64 = 180;
+
+pub async fn get_week(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    week: Option<NaiveDate>,
+    lang_code: &str,
+) -> Result<MealPlanWeekDto, Error> {
+    let start = week_start(week.unwrap_or_else(|| Utc::now().date_naive()));
+    let end = start + Duration::days(6);
+    let plans = meal_plan_repository::find_range(db, user_id, start, end).await?;
+
+    let mut dtos = Vec::with_capacity(plans.len());
+    for (plan, recipe) in plans {
+        dtos.push(to_dto(db, user_id, plan, recipe, lang_code).await?);
+    }
+    dtos.sort_by_key(|p| (p.date, p.slot));
+
+    let warning_count = dtos.iter().filter(|p| p.warning.is_some()).count();
+    let days = (0..7)
+        .map(|offset| start + Duration::days(offset))
+        .map(|date| MealPlanDayDto {
+            date,
+            plans: dtos.iter().filter(|p| p.date == date).cloned().collect(),
+        })
+        .collect();
+
+    Ok(MealPlanWeekDto {
+        week_start: start,
+        week_end: end,
+        days,
+        warning_count,
+    })
+}
+
+pub async fn create(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    input: MealPlanInput,
+    lang_code: &str,
+) -> Result<MealPlanDto, Error> {
+    let (recipe, title, servings) = resolve_recipe(db, user_id, &input, None, lang_code).await?;
+    let plan = meal_plan_repository::create(db, user_id, input, title, servings).await?;
+    to_dto(db, user_id, plan, recipe, lang_code).await
+}
+
+pub async fn update(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    input: MealPlanInput,
+    lang_code: &str,
+) -> Result<MealPlanDto, Error> {
+    let plan = find_owned(db, id, user_id).await?;
+    let (recipe, title, servings) = resolve_recipe(db, user_id, &input, Some(&plan), lang_code).await?;
+    let plan = meal_plan_repository::update(db, plan, input, title, servings).await?;
+    to_dto(db, user_id, plan, recipe, lang_code).await
+}
+
+pub async fn move_plan(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    input: MealPlanMoveInput,
+    lang_code: &str,
+) -> Result<MealPlanDto, Error> {
+    let plan = find_owned(db, id, user_id).await?;
+    let plan = meal_plan_repository::move_to(db, plan, input.date, input.slot).await?;
+    let recipe = find_recipe(db, &plan).await?;
+    to_dto(db, user_id, plan, recipe, lang_code).await
+}
+
+/// Plans the same meal again on another day or slot.
+pub async fn copy_plan(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    input: MealPlanMoveInput,
+    lang_code: &str,
+) -> Result<MealPlanDto, Error> {
+    let plan = find_owned(db, id, user_id).await?;
+    let copy = MealPlanInput {
+        date: input.date,
+        slot: input.slot,
+        recipe_id: plan.recipe_id,
+        servings: Some(plan.servings),
+        note: plan.note,
+    };
+    let plan = meal_plan_repository::create(db, user_id, copy, plan.recipe_title, plan.servings).await?;
+    let recipe = find_recipe(db, &plan).await?;
+    to_dto(db, user_id, plan, recipe, lang_code).await
+}
+
+pub async fn delete(db: &DatabaseConnection, user_id: Uuid, id: Uuid) -> Result<(), Error> {
+    find_owned(db, id, user_id).await?;
+    meal_plan_repository::delete(db, id).await
+}
+
+/// Copies a whole week onto another one and returns the week copied to.
+pub async fn copy_week(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    input: MealPlanCopyWeekInput,
+    lang_code: &str,
+) -> Result<MealPlanWeekDto, Error> {
+    let from = week_start(input.from_week);
+    let to = week_start(input.to_week);
+    if from == to {
+        return Err(Error::BadRequest(json!({
+            "message": "A week cannot be copied onto itself",
+            "operation": "copy_week",
+            "entity": "meal_plans",
+            "week": from.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    meal_plan_repository::copy_week(db, user_id, from, to, input.replace).await?;
+    get_week(db, user_id, Some(to), lang_code).await
+}
+
+/// Creates the calendar feed of the user, or replaces its token so that a leaked
+/// link stops working.
+pub async fn create_feed(db: &DatabaseConnection, user_id: Uuid) -> Result<MealPlanFeedDto, Error> {
+    let token: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(48)
+        .map(char::from)
+        .collect();
+    meal_plan_repository::save_feed(db, user_id, token.clone()).await?;
+
+    Ok(MealPlanFeedDto {
+        path: format!("/api/meal-plans/feed/{}.ics", token),
+        token,
+    })
+}
+
+pub async fn delete_feed(db: &DatabaseConnection, user_id: Uuid) -> Result<(), Error> {
+    meal_plan_repository::delete_feed(db, user_id).await
+}
+
+/// Renders the plans of the feed's owner around today as an iCalendar document.
+pub async fn get_feed(db: &DatabaseConnection, token: &str, origin: &str, lang_code: &str) -> Result<String, Error> {
+    let feed = meal_plan_repository::find_feed_by_token(db, token).await?;
+    let today = Utc::now().date_naive();
+    let plans = meal_plan_repository::find_range(
+        db,
+        feed.user_id,
+        today - Duration::days(FEED_DAYS_BEFORE),
+        today + Duration::days(FEED_DAYS_AFTER),
+    )
+    .await?;
+
+    let origin = origin.trim_end_matches('/');
+    let mut events = Vec::with_capacity(plans.len());
+    for (plan, recipe) in plans {
+        let updated_at = plan.updated_at.with_timezone(&Utc);
+        let dto = to_dto(db, feed.user_id, plan, recipe, lang_code).await?;
+
+        let mut summary = format!("{}: {}", dto.slot.label(), dto.title.as_deref().or(dto.note.as_deref()).unwrap_or(""));
+        if dto.recipe_id.is_some() || dto.warning.is_some() {
+            summary.push_str(&format!(" ({} servings)", dto.servings));
+        }
+        let description = [
+            dto.warning.map(|w| w.message().to_string()),
+            dto.title.as_ref().and(dto.note.clone()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        events.push(CalendarEvent {
+            uid: format!("{}@meal-plans", dto.id),
+            date: dto.date,
+            summary,
+            description: (!description.is_empty()).then_some(description),
+            url: match dto.warning {
+                None => dto.recipe_id.map(|id| format!("{}/recipes/{}", origin, id)),
+                Some(_) => None,
+            },
+            updated_at,
+        });
+    }
+
+    Ok(icalendar::to_ics("Meal plan", &events))
+}
+
+/// Monday of the week `date` falls in.
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Plans of other users are treated as missing.
+async fn find_owned(db: &DatabaseConnection, id: Uuid, user_id: Uuid) -> Result<meal_plans::Model, Error> {
+    let plan = meal_plan_repository::find_by_id(db, id).await?;
+    if plan.user_id != user_id {
+        return Err(Error::NotFound(json!({"error": "Meal plan not found"})));
+    }
+    Ok(plan)
+}
+
+async fn find_recipe(db: &DatabaseConnection, plan: &meal_plans::Model) -> Result<Option<recipes::Model>, Error> {
+    match plan.recipe_id {
+        Some(recipe_id) => meal_plan_repository::find_recipe(db, recipe_id).await,
+        None => Ok(None),
+    }
+}
+
+/// Checks the recipe of the input can be planned and returns it with the title to
+/// keep on the plan and the servings to plan for. A recipe that turned private
+/// since it was planned stays on its plan; it just cannot be planned again.
+async fn resolve_recipe(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    input: &MealPlanInput,
+    current: Option<&meal_plans::Model>,
+    lang_code: &str,
+) -> Result<(Option<recipes::Model>, Option<String>, i32), Error> {
+    let Some(recipe_id) = input.recipe_id else {
+        if input.note.as_deref().is_none_or(|n| n.trim().is_empty()) {
+            return Err(Error::BadRequest(json!({
+                "message": "A meal plan needs a recipe or a note",
+                "operation": "save",
+                "entity": "meal_plans",
+                "stage": "validation"
+            })));
+        }
+        return Ok((None, None, input.servings.unwrap_or(1)));
+    };
+
+    let recipe = meal_plan_repository::find_recipe(db, recipe_id)
+        .await?
+        .ok_or(Error::NotFound(json!({"error": "Recipe not found"})))?;
+    let already_planned = current.is_some_and(|p| p.recipe_id == Some(recipe_id));
+    if !already_planned && !recipe_repository::is_visible(db, recipe.id, Some(user_id)).await? {
+        return Err(Error::NotFound(json!({"error": "Recipe not found"})));
+    }
+
+    let translation =
+        recipe_translation_repository::find_translation(db, recipe.id, lang_code, &recipe.original_language_code).await?;
+    let servings = input.servings.unwrap_or(recipe.servings).max(1);
+    Ok((Some(recipe), Some(translation.title), servings))
+}
+
+async fn to_dto(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    plan: meal_plans::Model,
+    recipe: Option<recipes::Model>,
+    lang_code: &str,
+) -> Result<MealPlanDto, Error> {
+    let warning = match &recipe {
+        Some(r) if !recipe_repository::is_visible(db, r.id, Some(user_id)).await? => {
+            Some(MealPlanWarning::RecipePrivate)
+        }
+        None if plan.recipe_title.is_some() => Some(MealPlanWarning::RecipeDeleted),
+        _ => None,
+    };
+
+    // A recipe that is no longer visible only keeps the title it was planned under
+    let (title, image_url) = match (&recipe, warning) {
+        (Some(r), None) => {
+            let translation =
+                recipe_translation_repository::find_translation(db, r.id, lang_code, &r.original_language_code).await?;
+            (Some(translation.title), Some(r.image_url.clone()))
+        }
+        _ => (plan.recipe_title, None),
+    };
+
+    Ok(MealPlanDto {
+        id: plan.id,
+        date: plan.plan_date,
+        slot: MealSlot::parse(&plan.slot).unwrap_or(MealSlot::Dinner),
+        recipe_id: recipe.map(|r| r.id),
+        title,
+        image_url,
+        servings: plan.servings,
+        note: plan.note,
+        warning,
+    })
+}
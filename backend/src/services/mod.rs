@@ -6,6 +6,20 @@ pub(crate) mod ingredient_service;
 pub(crate) mod tag_service;
 pub(crate) mod language_service;
 pub(crate) mod unit_service;
+pub(crate) mod density_service;
+pub(crate) mod json_ld_service;
+pub(crate) mod cooklang_service;
+pub(crate) mod archive_service;
+pub(crate) mod pdf_service;
 pub(crate) mod ocr_service;
 pub(crate) mod notification_service;
-pub(crate) mod studio_service;
\ No newline at end of file
+pub(crate) mod studio_service;
+pub(crate) mod pantry_service;
+pub(crate) mod nutrition_service;
+pub(crate) mod dietary_service;
+pub(crate) mod shopping_list_service;
+pub(crate) mod meal_plan_service;
+pub(crate) mod collection_service;
+pub(crate) mod share_link_service;
+pub(crate) mod household_service;
+pub(crate) mod collaborator_service;
@@ -27,7 +27,7 @@ pub async fn get_for_user(
         let mut actor_name = None;
 
         match model.category.as_str() {
-            "recipe_comment" | "comment_reply" | "recipe_favorite" => {
+            "recipe_comment" | "comment_reply" | "recipe_favorite" | "recipe_forked" => {
                 if let Some(a_id) = model.actor_id {
                     if let Ok(actor) = user_repository::find_by_id(db, a_id).await {
                         let name = actor.username;
@@ -96,32 +96,31 @@ pub async fn trigger(state: &AppState, trigger: NotificationTrigger) -> Result<(
     // 2. Translate the templates while they still have {actor} and {recipe_title}
     let (translated_title, translated_msg) = if lang == "en" {
         (title_tpl, msg_tpl)
+    } else if let Some(tpl) = notification_template_repository::find_by_category_lang(db, &trigger.category, &lang).await? {
+        // Templates shipped in the user's language need no translation
+        (tpl.title_template, tpl.message_template)
     } else {
         // 1. Armor with symbols that translators usually ignore
-        let p_title = title_tpl.replace("{actor}", "[#A#]").replace("{recipe_title}", "[#R#]");
-        let p_msg = msg_tpl.replace("{actor}", "[#A#]").replace("{recipe_title}", "[#R#]");
-
-        let t_title = call_libretranslate(state, &p_title, &lang).await?;
-        let t_message = call_libretranslate(state, &p_msg, &lang).await?;
+        let t_title = call_libretranslate(state, &armor_placeholders(&title_tpl), &lang).await?;
+        let t_message = call_libretranslate(state, &armor_placeholders(&msg_tpl), &lang).await?;
 
         // 2. Flexible Restoration
-        // We handle potential spaces added by the translator (e.g. "[# A #]")
-        let restore = |s: String| {
-            s.replace("[#A#]", "{actor}")
-                .replace("[# A #]", "{actor}")
-                .replace("[#R#]", "{recipe_title}")
-                .replace("[# R #]", "{recipe_title}")
-        };
-
-        (restore(t_title), restore(t_message))
+        (restore_placeholders(t_title), restore_placeholders(t_message))
     };
 
     // 3. Inject ONLY the comment_preview (so it stays raw and untranslated)
     let raw_comment = trigger.variables.get("comment_preview").cloned().unwrap_or_default();
-    let final_message = translated_msg
+    let mut final_message = translated_msg
         .replace("{comment_preview}", &raw_comment)
         .replace("{comment preview}", &raw_comment); // Handle LibreTranslate space quirk
 
+    // Pantry items are the user's own words, injected raw like the comment preview
+    for key in ["item_name", "best_before"] {
+        if let Some(value) = trigger.variables.get(key) {
+            final_message = final_message.replace(&format!("{{{}}}", key), value);
+        }
+    }
+
     // 4. Save to DB (The message still contains {actor} and {recipe_title})
     let saved_notif = notification_repository::create(
         db,
@@ -171,17 +170,12 @@ async fn get_or_translate_template(
         .ok_or_else(|| Error::NotFound(format!("Base English template for {} not found", category).into()))?;
 
     // SWAP: Protect during the initial translation/caching process
-    let p_title = en_tpl.title_template.replace("{actor}", "[#A#]").replace("{recipe_title}", "[#R#]");
-    let p_msg = en_tpl.message_template.replace("{actor}", "[#A#]").replace("{recipe_title}", "[#R#]");
-
-    let t_title_raw = call_libretranslate(state, &p_title, lang).await?;
-    let t_msg_raw = call_libretranslate(state, &p_msg, lang).await?;
+    let t_title_raw = call_libretranslate(state, &armor_placeholders(&en_tpl.title_template), lang).await?;
+    let t_msg_raw = call_libretranslate(state, &armor_placeholders(&en_tpl.message_template), lang).await?;
 
     // Restore
-    let translated_title = t_title_raw.replace("[#A#]", "{actor}").replace("[# A #]", "{actor}")
-        .replace("[#R#]", "{recipe_title}").replace("[# R #]", "{recipe_title}");
-    let translated_msg = t_msg_raw.replace("[#A#]", "{actor}").replace("[# A #]", "{actor}")
-        .replace("[#R#]", "{recipe_title}").replace("[# R #]", "{recipe_title}");
+    let translated_title = restore_placeholders(t_title_raw);
+    let translated_msg = restore_placeholders(t_msg_raw);
 
     notification_template_repository::create(
         db,
@@ -258,6 +252,31 @@ async fn call_libretranslate(
         .unwrap_or(text)
         .to_string())
 }
+/// Placeholders a template may hold, with the symbols that stand in for them while
+/// LibreTranslate works on the text.
+const PLACEHOLDERS: [(&str, &str); 4] = [
+    ("{actor}", "A"),
+    ("{recipe_title}", "R"),
+    ("{item_name}", "I"),
+    ("{best_before}", "B"),
+];
+
+/// Internal: Swaps every placeholder for symbols that translators usually ignore.
+fn armor_placeholders(template: &str) -> String {
+    PLACEHOLDERS.iter().fold(template.to_string(), |text, (placeholder, symbol)| {
+        text.replace(placeholder, &format!("[#{}#]", symbol))
+    })
+}
+
+/// Internal: Puts the placeholders back, including the spaces a translator may add
+/// (e.g. "[# A #]").
+fn restore_placeholders(translated: String) -> String {
+    PLACEHOLDERS.iter().fold(translated, |text, (placeholder, symbol)| {
+        text.replace(&format!("[#{}#]", symbol), placeholder)
+            .replace(&format!("[# {} #]", symbol), placeholder)
+    })
+}
+
 /// Internal: Replaces {key} in strings with values from the trigger HashMap.
 fn inject_variables(mut template: String, variables: &HashMap<String, String>) -> String {
     for (key, value) in variables {
@@ -0,0 +1,111 @@
+use crate::dto::nutrition_dto::{
+    FoodDto, FoodImportReportDto, FoodMappingDto, FoodMappingInput, FoodTableFormat, NutrientsDto, NutritionDto,
+    UnmatchedIngredientDto, UnmatchedReason,
+};
+use crate::dto::recipe_dto::RecipeViewDto;
+use crate::errors::Error;
+use crate::repositories::food_repository;
+use crate::utils::food_composition;
+use crate::utils::unit::UnitConverter;
+use rust_decimal::prelude::ToPrimitive;
+use sea_orm::DatabaseConnection;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+const DEFAULT_SEARCH_LIMIT: u64 = 20;
+
+pub async fn import_foods(
+    db: &DatabaseConnection,
+    file_path: &Path,
+    format: FoodTableFormat,
+) -> Result<FoodImportReportDto, Error> {
+    let table = food_composition::parse(&fs::read(file_path)?, format)?;
+    let imported = food_repository::upsert(db, format.as_str(), table.records).await?;
+
+    Ok(FoodImportReportDto {
+        source: format.as_str().to_string(),
+        imported,
+        skipped: table.skipped,
+    })
+}
+
+pub async fn search_foods(db: &DatabaseConnection, search: Option<String>, limit: Option<u64>) -> Result<Vec<FoodDto>, Error> {
+    food_repository::search(db, search.as_deref(), limit.unwrap_or(DEFAULT_SEARCH_LIMIT)).await
+}
+
+pub async fn get_mapping(db: &DatabaseConnection, canonical_ingredient_id: Uuid) -> Result<FoodMappingDto, Error> {
+    food_repository::find_mapping(db, canonical_ingredient_id).await
+}
+
+pub async fn save_mapping(
+    db: &DatabaseConnection,
+    canonical_ingredient_id: Uuid,
+    input: FoodMappingInput,
+) -> Result<FoodMappingDto, Error> {
+    food_repository::save_mapping(db, canonical_ingredient_id, input).await
+}
+
+pub async fn delete_mapping(db: &DatabaseConnection, canonical_ingredient_id: Uuid) -> Result<(), Error> {
+    food_repository::delete_mapping(db, canonical_ingredient_id).await
+}
+
+/// Nutrition facts of `recipe` as stored, with the totals given for `servings`. Each
+/// line is weighed in grams: weights directly, volumes through the density of the
+/// ingredient and counted units through the unit weight of its food. Pinches and
+/// "to taste" amounts are left out.
+pub async fn compute(
+    db: &DatabaseConnection,
+    recipe: &RecipeViewDto,
+    servings: i32,
+    converter: &UnitConverter,
+) -> Result<NutritionDto, Error> {
+    let foods = food_repository::find_by_recipe(db, recipe.id).await?;
+
+    let mut total = NutrientsDto::default();
+    let mut counted = 0;
+    let mut unmatched = Vec::new();
+    for ingredient in recipe.ingredient_groups.iter().flat_map(|g| &g.ingredients) {
+        let quantity = ingredient.quantity.to_f64().unwrap_or(0.0);
+        if quantity <= 0.0 || ingredient.unit.conversion_factor <= 0.0 {
+            continue;
+        }
+        counted += 1;
+
+        let Some(food) = foods.get(&ingredient.id) else {
+            unmatched.push(UnmatchedIngredientDto {
+                ingredient_id: ingredient.id,
+                name: ingredient.name.clone(),
+                reason: UnmatchedReason::NoFood,
+            });
+            continue;
+        };
+
+        let density = converter.density_for(&ingredient.name).map(|d| d.grams_per_ml);
+        let grams = converter.to_grams(quantity, &ingredient.unit, density).or_else(|| {
+            food.grams_per_unit
+                .filter(|_| !converter.is_convertible(&ingredient.unit))
+                .map(|per_unit| UnitConverter::to_base(quantity, &ingredient.unit) * per_unit)
+        });
+        match grams {
+            Some(grams) => total.add(&NutrientsDto::of(&food.food, grams)),
+            None => unmatched.push(UnmatchedIngredientDto {
+                ingredient_id: ingredient.id,
+                name: ingredient.name.clone(),
+                reason: UnmatchedReason::NoWeight,
+            }),
+        }
+    }
+
+    let stored_servings = recipe.servings.max(1) as f64;
+    Ok(NutritionDto {
+        per_recipe: total.divided(stored_servings / servings.max(1) as f64),
+        per_serving: total.divided(stored_servings),
+        coverage: if counted == 0 {
+            0.0
+        } else {
+            ((counted - unmatched.len()) as f64 / counted as f64 * 100.0).round() / 100.0
+        },
+        unmatched,
+    })
+}
@@ -214,7 +214,7 @@ pub async fn process_ocr_confirmation(
     teach_lexicon(&payload, sqlite_pool).await?;
 
     // 3. PERSIST: Save the clean recipe to Postgres
-    let result = recipe_service::create(pg_db, payload.modified_recipe, preferred_language).await?;
+    let result = recipe_service::create(pg_db, sqlite_pool, payload.modified_recipe, preferred_language).await?;
 
     Ok(result)
 }
@@ -0,0 +1,433 @@
+use crate::app::state::AppState;
+use crate::dto::notification_dto::NotificationTrigger;
+use crate::dto::pantry_dto::{
+    PantryExpiringQuery, PantryItemDto, PantryItemInput, PantryItemStatus, PantryLocation, PantryMatchDto,
+    PantryReminderResult, PantrySearchInput, PantrySearchResponse, PantrySuggestionDto, PantrySuggestionResponse,
+};
+use crate::dto::recipe_dto::RecipeViewDto;
+use crate::dto::unit_dto::UnitDto;
+use crate::errors::Error;
+use crate::recipe_parser::dictionary;
+use crate::repositories::{
+    canonical_ingredient_repository, ingredient_lexicon_repository, pantry_repository, recipe_repository,
+    recipe_translation_repository, unit_repository, user_repository,
+};
+use crate::services::notification_service;
+use crate::utils::unit::round_for_unit;
+use chrono::{NaiveDate, Utc};
+use entity::pantry_items;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+use std::time::Duration;
+use uuid::Uuid;
+
+const DEFAULT_LIMIT: usize = 20;
+/// Days ahead an item counts as expiring soon, and is reminded of.
+const EXPIRING_WITHIN_DAYS: i64 = 3;
+const REMINDER_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// A distinct ingredient of a recipe: its lexicon entry when known and its display name.
+struct RecipeIngredient {
+    ingredient_id: Uuid,
+    lexicon_id: Option<i32>,
+    name: String,
+    name_in_lang: bool,
+}
+
+/// Public recipes ranked by how many of their ingredients are on hand, matched on
+/// lexicon entries so that an ingredient is found whatever language it is written in.
+/// The ranking is done by the database; only the recipes returned are loaded.
+pub async fn find_cookable(
+    db: &DatabaseConnection,
+    dict_db: &SqlitePool,
+    input: PantrySearchInput,
+    lang_code: &str,
+) -> Result<PantrySearchResponse, Error> {
+    let mut on_hand = HashSet::new();
+    let mut unrecognized = Vec::new();
+    for item in input.ingredients.iter().filter(|i| !i.trim().is_empty()) {
+        let ids = dictionary::pantry_ingredient_ids(item, dict_db).await?;
+        if ids.is_empty() {
+            unrecognized.push(item.clone());
+        }
+        on_hand.extend(ids);
+    }
+
+    let staples: HashSet<i32> = if input.ignore_staples.unwrap_or(true) {
+        dictionary::staple_ingredient_ids(dict_db).await?.into_iter().collect()
+    } else {
+        HashSet::new()
+    };
+
+    let scores = ingredient_lexicon_repository::find_public_recipe_coverage(
+        db,
+        &on_hand.iter().copied().collect::<Vec<_>>(),
+        &staples.iter().copied().collect::<Vec<_>>(),
+        input.min_coverage.unwrap_or(0.0),
+        input.limit.unwrap_or(DEFAULT_LIMIT) as u64,
+    )
+    .await?;
+
+    let recipe_ids: Vec<Uuid> = scores.iter().map(|s| s.recipe_id).collect();
+    let mut recipes: HashMap<Uuid, Vec<RecipeIngredient>> =
+        recipe_ingredients(db, &recipe_ids, lang_code).await?.into_iter().collect();
+
+    let mut results = Vec::new();
+    for coverage in scores {
+        let ingredients = recipes.remove(&coverage.recipe_id).unwrap_or_default();
+        let (_, _, missing) = score(ingredients, &on_hand, &staples);
+        results.push(PantryMatchDto {
+            recipe: recipe_view(db, coverage.recipe_id, lang_code).await?,
+            coverage: coverage.matched as f32 / coverage.total as f32,
+            matched: coverage.matched as usize,
+            total: coverage.total as usize,
+            missing,
+        });
+    }
+
+    Ok(PantrySearchResponse { results, unrecognized })
+}
+
+pub async fn get_items(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    location: Option<PantryLocation>,
+) -> Result<Vec<PantryItemDto>, Error> {
+    let items = pantry_repository::find_by_user(db, user_id, location).await?;
+    to_dtos(db, items).await
+}
+
+pub async fn create_item(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    input: PantryItemInput,
+    lang_code: &str,
+) -> Result<PantryItemDto, Error> {
+    check_references(db, &input, lang_code).await?;
+    let item = pantry_repository::create(db, user_id, input).await?;
+    to_dto(db, item).await
+}
+
+pub async fn update_item(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    id: Uuid,
+    input: PantryItemInput,
+    lang_code: &str,
+) -> Result<PantryItemDto, Error> {
+    let item = find_owned(db, id, user_id).await?;
+    check_references(db, &input, lang_code).await?;
+    let item = pantry_repository::update(db, item, input).await?;
+    to_dto(db, item).await
+}
+
+pub async fn delete_item(db: &DatabaseConnection, user_id: Uuid, id: Uuid) -> Result<(), Error> {
+    find_owned(db, id, user_id).await?;
+    pantry_repository::delete(db, id).await
+}
+
+/// Items best before the given number of days from now, expired ones included.
+pub async fn get_expiring(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    query: PantryExpiringQuery,
+) -> Result<Vec<PantryItemDto>, Error> {
+    let until = today() + chrono::Duration::days(query.days.unwrap_or(EXPIRING_WITHIN_DAYS));
+    let items = pantry_repository::find_expiring(db, user_id, until).await?;
+    to_dtos(db, items).await
+}
+
+/// Public recipes using the items about to expire, those using the most of them
+/// first. Items already past their date are left out, and the rest of the inventory
+/// counts towards the coverage.
+pub async fn suggest_for_expiring(
+    db: &DatabaseConnection,
+    dict_db: &SqlitePool,
+    user_id: Uuid,
+    query: PantryExpiringQuery,
+    lang_code: &str,
+) -> Result<PantrySuggestionResponse, Error> {
+    let today = today();
+    let until = today + chrono::Duration::days(query.days.unwrap_or(EXPIRING_WITHIN_DAYS));
+    let items = pantry_repository::find_by_user(db, user_id, None).await?;
+
+    let mut on_hand = HashSet::new();
+    // Lexicon ids of each expiring item, soonest first
+    let mut expiring: Vec<(pantry_items::Model, HashSet<i32>)> = Vec::new();
+    for item in items {
+        let ids = item_lexicon_ids(db, dict_db, &item, lang_code).await?;
+        on_hand.extend(ids.iter().copied());
+        if item.best_before.is_some_and(|d| d >= today && d <= until) && !ids.is_empty() {
+            expiring.push((item, ids));
+        }
+    }
+
+    let staples: HashSet<i32> = dictionary::staple_ingredient_ids(dict_db).await?.into_iter().collect();
+
+    let mut matches = Vec::new();
+    if !expiring.is_empty() {
+        let expiring_ids: Vec<i32> = expiring.iter().flat_map(|(_, ids)| ids.iter().copied()).collect();
+        let recipe_ids = ingredient_lexicon_repository::find_public_recipes_using(db, &expiring_ids).await?;
+        for (recipe_id, ingredients) in recipe_ingredients(db, &recipe_ids, lang_code).await? {
+            let recipe_ids: HashSet<i32> = ingredients.iter().filter_map(|i| i.lexicon_id).collect();
+            let uses: Vec<usize> = expiring
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, ids))| !ids.is_disjoint(&recipe_ids))
+                .map(|(index, _)| index)
+                .collect();
+            if uses.is_empty() {
+                continue;
+            }
+
+            let (matched, total, missing) = score(ingredients, &on_hand, &staples);
+            let coverage = if total == 0 { 0.0 } else { matched as f32 / total as f32 };
+            matches.push((recipe_id, uses, coverage, missing));
+        }
+    }
+
+    // Most expiring items used, then the soonest one used, then the best coverage
+    matches.sort_by(|a, b| {
+        b.1.len()
+            .cmp(&a.1.len())
+            .then(a.1.first().cmp(&b.1.first()))
+            .then(b.2.total_cmp(&a.2))
+    });
+    matches.truncate(query.limit.unwrap_or(DEFAULT_LIMIT));
+
+    let mut results = Vec::new();
+    for (recipe_id, uses, coverage, missing) in matches {
+        results.push(PantrySuggestionDto {
+            recipe: recipe_view(db, recipe_id, lang_code).await?,
+            uses: uses.into_iter().map(|index| expiring[index].0.name.clone()).collect(),
+            coverage,
+            missing,
+        });
+    }
+
+    let expiring = to_dtos(db, expiring.into_iter().map(|(item, _)| item).collect()).await?;
+    Ok(PantrySuggestionResponse { expiring, results })
+}
+
+/// Tells every user about the items coming to their best-before date, once per
+/// date. Users who turned the reminders off are skipped but still marked, so that
+/// turning them back on does not bring up old items.
+pub async fn send_expiry_reminders(state: &AppState) -> Result<PantryReminderResult, Error> {
+    let db = &state.db;
+    let today = today();
+    let due = pantry_repository::find_due_reminders(db, today, today + chrono::Duration::days(EXPIRING_WITHIN_DAYS)).await?;
+
+    let mut notified = 0;
+    for item in due {
+        let recipient = match user_repository::find_by_id(db, item.user_id).await {
+            Ok(recipient) => recipient,
+            Err(e) => {
+                log::error!("Failed to load the owner of pantry item {}: {:?}", item.id, e);
+                continue;
+            }
+        };
+        let is_enabled = recipient.preferences
+            .get("pantry_expiring_enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        if is_enabled {
+            let mut variables = HashMap::new();
+            variables.insert("item_name".to_string(), item.name.clone());
+            variables.insert(
+                "best_before".to_string(),
+                item.best_before.map(|d| d.to_string()).unwrap_or_default(),
+            );
+
+            let trigger = NotificationTrigger {
+                recipient_id: item.user_id,
+                actor_id: None,
+                category: "pantry_expiring".to_string(),
+                target_id: Some(item.id),
+                variables,
+            };
+            if let Err(e) = notification_service::trigger(state, trigger).await {
+                // Left unmarked so the next run tries again
+                log::error!("Failed to send expiry reminder for pantry item {}: {:?}", item.id, e);
+                continue;
+            }
+            notified += 1;
+        }
+        if let Err(e) = pantry_repository::mark_notified(db, item.id).await {
+            log::error!("Failed to mark pantry item {} as notified: {:?}", item.id, e);
+        }
+    }
+
+    Ok(PantryReminderResult { notified })
+}
+
+/// Sends the expiry reminders every hour, for as long as the server runs.
+pub async fn run_expiry_reminders(state: AppState) {
+    let mut interval = tokio::time::interval(REMINDER_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = send_expiry_reminders(&state).await {
+            log::error!("Failed to send pantry expiry reminders: {:?}", e);
+        }
+    }
+}
+
+fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+async fn find_owned(db: &DatabaseConnection, id: Uuid, user_id: Uuid) -> Result<pantry_items::Model, Error> {
+    let item = pantry_repository::find_by_id(db, id).await?;
+    if item.user_id != user_id {
+        return Err(Error::NotFound(json!({"error": "Pantry item not found"})));
+    }
+    Ok(item)
+}
+
+async fn check_references(db: &DatabaseConnection, input: &PantryItemInput, lang_code: &str) -> Result<(), Error> {
+    if let Some(id) = input.canonical_ingredient_id {
+        canonical_ingredient_repository::find_by_id(db, id, lang_code).await?;
+    }
+    if let Some(unit_id) = input.unit_id {
+        unit_repository::find_by_id(db, unit_id).await?;
+    }
+    Ok(())
+}
+
+/// Lexicon entries an item stands for: those of its catalog ingredient, and those
+/// its name resolves to.
+async fn item_lexicon_ids(
+    db: &DatabaseConnection,
+    dict_db: &SqlitePool,
+    item: &pantry_items::Model,
+    lang_code: &str,
+) -> Result<HashSet<i32>, Error> {
+    let mut ids: HashSet<i32> = dictionary::pantry_ingredient_ids(&item.name, dict_db).await?.into_iter().collect();
+    if let Some(id) = item.canonical_ingredient_id {
+        let catalog = canonical_ingredient_repository::find_by_id(db, id, lang_code).await?;
+        ids.extend(catalog.lexicon_id);
+    }
+    Ok(ids)
+}
+
+async fn to_dtos(db: &DatabaseConnection, items: Vec<pantry_items::Model>) -> Result<Vec<PantryItemDto>, Error> {
+    let units = unit_repository::get_all_admin(db).await?;
+    Ok(items.into_iter().map(|item| item_dto(item, &units)).collect())
+}
+
+async fn to_dto(db: &DatabaseConnection, item: pantry_items::Model) -> Result<PantryItemDto, Error> {
+    let units = unit_repository::get_all_admin(db).await?;
+    Ok(item_dto(item, &units))
+}
+
+fn item_dto(item: pantry_items::Model, units: &[UnitDto]) -> PantryItemDto {
+    let unit = item.unit_id.and_then(|u| units.iter().find(|unit| unit.id == u)).cloned();
+    let rounded = match (item.quantity, &unit) {
+        (Some(q), Some(u)) => Some(round_for_unit(q, u)),
+        (Some(q), None) => Decimal::from_f64_retain(q)
+            .map(|d| d.round_dp(2).normalize())
+            .map(|d| (d, d.to_string())),
+        (None, _) => None,
+    };
+
+    let days_left = item.best_before.map(|d| (d - today()).num_days());
+    let status = match days_left {
+        None => PantryItemStatus::NoDate,
+        Some(days) if days < 0 => PantryItemStatus::Expired,
+        Some(days) if days <= EXPIRING_WITHIN_DAYS => PantryItemStatus::ExpiringSoon,
+        Some(_) => PantryItemStatus::Fresh,
+    };
+
+    PantryItemDto {
+        id: item.id,
+        name: item.name,
+        canonical_ingredient_id: item.canonical_ingredient_id,
+        quantity: rounded.as_ref().map(|(q, _)| *q),
+        display_quantity: rounded.map(|(_, d)| d),
+        unit,
+        location: PantryLocation::parse(&item.location).unwrap_or(PantryLocation::Pantry),
+        best_before: item.best_before,
+        days_left,
+        status,
+    }
+}
+
+/// Ingredients of a recipe on hand, counted, and the names of those missing. Staples
+/// are left out of both counts.
+fn score(
+    ingredients: Vec<RecipeIngredient>,
+    on_hand: &HashSet<i32>,
+    staples: &HashSet<i32>,
+) -> (usize, usize, Vec<String>) {
+    let mut seen = HashSet::new();
+    let (mut matched, mut total, mut missing) = (0, 0, Vec::new());
+
+    for ingredient in ingredients {
+        if let Some(id) = ingredient.lexicon_id {
+            // The same ingredient listed in two groups only counts once.
+            if staples.contains(&id) || !seen.insert(id) { continue; }
+        }
+        total += 1;
+        if ingredient.lexicon_id.is_some_and(|id| on_hand.contains(&id)) {
+            matched += 1;
+        } else {
+            missing.push(ingredient.name);
+        }
+    }
+    (matched, total, missing)
+}
+
+/// Distinct ingredients of the given public recipes, named in `lang_code` when translated.
+async fn recipe_ingredients(
+    db: &DatabaseConnection,
+    recipe_ids: &[Uuid],
+    lang_code: &str,
+) -> Result<Vec<(Uuid, Vec<RecipeIngredient>)>, Error> {
+    let mut recipes: Vec<(Uuid, Vec<RecipeIngredient>)> = Vec::new();
+    for row in ingredient_lexicon_repository::find_public_recipe_ingredients(db, recipe_ids).await? {
+        if recipes.last().is_none_or(|(id, _)| *id != row.recipe_id) {
+            recipes.push((row.recipe_id, Vec::new()));
+        }
+        let Some((_, ingredients)) = recipes.last_mut() else { continue };
+
+        let in_lang = row.language_code == lang_code;
+        match ingredients.iter_mut().find(|i| i.ingredient_id == row.ingredient_id) {
+            Some(ingredient) => {
+                // The lowest entry when translations disagree, as the database scores them
+                ingredient.lexicon_id = match (ingredient.lexicon_id, row.lexicon_id) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+                if in_lang && !ingredient.name_in_lang {
+                    ingredient.name = row.data;
+                    ingredient.name_in_lang = true;
+                }
+            }
+            None => ingredients.push(RecipeIngredient {
+                ingredient_id: row.ingredient_id,
+                lexicon_id: row.lexicon_id,
+                name: row.data,
+                name_in_lang: in_lang,
+            }),
+        }
+    }
+    Ok(recipes)
+}
+
+async fn recipe_view(db: &DatabaseConnection, recipe_id: Uuid, lang_code: &str) -> Result<RecipeViewDto, Error> {
+    let recipe = recipe_repository::find_by_id(db, recipe_id).await?;
+    let translation = recipe_translation_repository::find_translation(
+        db,
+        recipe.id,
+        lang_code,
+        recipe.original_language_code.deref(),
+    )
+    .await?;
+    let (nb_ingredients, nb_steps) = recipe_repository::get_recipe_counts(db, recipe.id).await?.unwrap_or((0, 0));
+
+    Ok(RecipeViewDto::from((recipe, translation, Some(nb_ingredients), Some(nb_steps))))
+}
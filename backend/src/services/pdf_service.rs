@@ -0,0 +1,80 @@
+use crate::dto::pdf_dto::{CookbookPdfInput, RecipePdfQuery};
+use crate::dto::recipe_dto::{RecipeResponse, RecipeViewDto};
+use crate::dto::unit_dto::UnitSystem;
+use crate::errors::Error;
+use crate::repositories::recipe_repository;
+use crate::services::recipe_service;
+use crate::utils::pdf;
+use crate::utils::recipe_text::{default_title, file_name};
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+/// Renders one recipe in `lang`, scaled to the requested servings, and counts it
+/// as printed.
+pub async fn recipe(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    lang_code: &str,
+    query: &RecipePdfQuery,
+    origin: &str,
+    user_id: Option<Uuid>,
+) -> Result<(String, Vec<u8>), Error> {
+    let recipe = view(db, recipe_id, lang_code, query.servings, query.unit_system).await?;
+    let layout = query.layout.unwrap_or_default();
+    let url = recipe_url(origin, recipe_id);
+    let document = pdf::render_recipe(&recipe, layout, lang_code, Some(&url));
+
+    recipe_repository::add_print(db, recipe_id, user_id, layout.as_str()).await?;
+    Ok((file_name(&recipe.title, "pdf"), document))
+}
+
+/// Renders the selected recipes as one cookbook, in the order they were given.
+pub async fn cookbook(
+    db: &DatabaseConnection,
+    input: CookbookPdfInput,
+    lang_code: &str,
+    origin: &str,
+    user_id: Option<Uuid>,
+) -> Result<(String, Vec<u8>), Error> {
+    let mut recipes = Vec::new();
+    for recipe_id in &input.recipe_ids {
+        let recipe = view(db, *recipe_id, lang_code, None, input.unit_system).await?;
+        recipes.push((recipe, recipe_url(origin, *recipe_id)));
+    }
+
+    let title = input
+        .title
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| default_title(lang_code, "Cookbook", "Livre de recettes"));
+    let document = pdf::render_cookbook(&title, &recipes, input.layout, lang_code);
+
+    for recipe_id in &input.recipe_ids {
+        recipe_repository::add_print(db, *recipe_id, user_id, input.layout.as_str()).await?;
+    }
+    Ok((file_name(&title, "pdf"), document))
+}
+
+async fn view(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    lang_code: &str,
+    servings: Option<i32>,
+    unit_system: Option<UnitSystem>,
+) -> Result<RecipeViewDto, Error> {
+    match recipe_service::get_by_id(db, recipe_id, lang_code, false).await? {
+        RecipeResponse::View(recipe) => recipe_service::adjust_view(db, recipe, servings, unit_system).await,
+        RecipeResponse::Editor(_) => Err(Error::InternalServerError(json!({
+            "message": "Unexpected response type while rendering recipe",
+            "operation": "render_pdf",
+            "recipe_id": recipe_id.to_string(),
+            "expected": "RecipeResponse::View",
+            "received": "RecipeResponse::Editor",
+            "stage": "response_validation"
+        }))),
+    }
+}
+
+fn recipe_url(origin: &str, recipe_id: Uuid) -> String {
+    format!("{}/recipes/{}", origin.trim_end_matches('/'), recipe_id)
+}
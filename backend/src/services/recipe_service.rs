@@ -1,33 +1,46 @@
 use std::collections::HashMap;
 use crate::domain::user::{AuthenticatedUser, Role};
+use crate::dto::collaborator_dto::RecipeCollaboratorRole;
 use crate::dto::comment_dto::{CommentDto, CreateCommentDto};
-use crate::dto::recipe_dto::{CreateRecipeInput, EditRecipeInput, GetAllRecipesByPageQuery, RecipeEditorDto, RecipeFilter, RecipeFilterByPage, RecipeResponse, RecipeViewDto};
+use crate::dto::facet_dto::RecipeFacetsDto;
+use crate::dto::recipe_dto::{CreateRecipeInput, EditRecipeInput, GetAllRecipesByPageQuery, RecipeEditorDto, RecipeFilter, RecipeFilterByPage, RecipeLineageDto, RecipePagination, RecipeResponse, RecipeSearchMatchDto, RecipeViewDto};
 use crate::dto::recipe_rating_dto::RecipeRatingDto;
-use crate::dto::recipe_version_dto::RecipeVersionDto;
+use crate::dto::recipe_version_dto::{RecipeVersionDiffDto, RecipeVersionDto};
+use crate::dto::tag_dto::InputTag;
+use crate::dto::unit_dto::UnitSystem;
 use crate::dto::user_dto::UserResponseDto;
 use crate::errors::Error;
-use crate::repositories::{ingredient_group_repository, recipe_repository, recipe_translation_repository, recipe_version_repository, role_repository, step_group_repository, tag_repository, user_repository};
-use crate::utils::file_upload::move_file_from_tmp;
+use crate::repositories::{collaborator_repository, density_repository, household_repository, ingredient_group_repository, recipe_repository, recipe_translation_repository, recipe_version_repository, role_repository, step_group_repository, tag_repository, unit_repository, user_repository};
+use crate::utils::file_upload::{asset_path, copy_stored_file, move_file_from_tmp};
+use crate::utils::{recipe_diff, serving_scaler, unit};
+use crate::utils::unit::UnitConverter;
+use entity::recipes;
 use sea_orm::DatabaseConnection;
+use sqlx::SqlitePool;
 use serde_json::json;
 use std::fs;
+use std::path::PathBuf;
 use std::ops::Deref;
 use actix_web::web::Data;
 use uuid::Uuid;
 use crate::app::state::AppState;
 use crate::dto::notification_dto::NotificationTrigger;
-use crate::services::notification_service;
+use crate::services::{dietary_service, notification_service, nutrition_service, share_link_service};
 
 pub async fn get_all(
     db: &DatabaseConnection,
     lang_code: &str,
     filter: RecipeFilter,
+    viewer_id: Option<Uuid>,
 ) -> Result<Vec<RecipeViewDto>, Error> {
-    let recipes = recipe_repository::find_by_query(db, filter, lang_code).await?;
+    let search = search_term(filter.search.as_deref());
+    let recipes = recipe_repository::find_by_query(db, filter, lang_code, viewer_id).await?;
 
     let mut dtos = Vec::new();
 
     if let Some(recipes) = recipes {
+        let recipe_ids: Vec<Uuid> = recipes.iter().map(|r| r.id).collect();
+        let mut matches = search_matches(db, &recipe_ids, lang_code, search.as_deref()).await?;
         for recipe in recipes {
             let translation = recipe_translation_repository::find_translation(
                 db,
@@ -38,7 +51,9 @@ pub async fn get_all(
             .await?;
             let (nb_ingredients, nb_steps) = recipe_repository::get_recipe_counts(db, recipe.id).await?.unwrap_or((0, 0));
 
-            let dto = RecipeViewDto::from((recipe, translation, Some(nb_ingredients), Some(nb_steps)));
+            let recipe_id = recipe.id;
+            let mut dto = RecipeViewDto::from((recipe, translation, Some(nb_ingredients), Some(nb_steps)));
+            dto.search = matches.remove(&recipe_id);
             dtos.push(dto);
         }
     }
@@ -91,13 +106,100 @@ pub async fn get_by_id(
         )
         .await?;
 
-        Ok(RecipeResponse::View(RecipeViewDto::build(
+        let mut view = RecipeViewDto::build(
             recipe,
             recipe_translation,
             tags,
             ingredient_groups,
             step_groups,
-        )))
+        );
+        if view.forked_from.is_some() {
+            view.lineage = get_lineage(db, view.id, lang_code).await?;
+        }
+        Ok(RecipeResponse::View(view))
+    }
+}
+/// Listing every recipe, private ones included, is reserved to staff.
+pub fn authorize_scope(auth: Option<&AuthenticatedUser>) -> Result<(), Error> {
+    match auth {
+        Some(auth) => auth.require_roles(&[Role::Admin, Role::Moderator, Role::Superuser]),
+        None => Err(Error::Unauthorized(json!({"error": "Authentication required"}))),
+    }
+}
+/// Private recipes are only shown to their author, their collaborators, the members of
+/// their household and staff, or read-only to anyone holding an active share link. Returns whether the share
+/// link is what let the reader in.
+pub async fn authorize_read(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    auth: Option<&AuthenticatedUser>,
+    share: Option<&str>,
+    include_translations: bool,
+) -> Result<bool, Error> {
+    if let Some(token) = share
+        && !include_translations
+        && share_link_service::grants(db, recipe_id, token).await?
+    {
+        return Ok(true);
+    }
+
+    recipe_repository::find_by_id(db, recipe_id).await?;
+    if auth.is_some_and(|a| a.require_roles(&[Role::Admin, Role::Moderator, Role::Superuser]).is_ok())
+        || recipe_repository::is_visible(db, recipe_id, auth.map(|a| a.user.id)).await?
+    {
+        return Ok(false);
+    }
+    Err(Error::NotFound(json!({"error": "Recipe not found"})))
+}
+/// File under `/assets` at `path`. Images of recipes are only served to those who may read
+/// one of the recipes showing them; anything else is reported missing.
+pub async fn authorize_asset(
+    db: &DatabaseConnection,
+    path: &str,
+    auth: Option<&AuthenticatedUser>,
+) -> Result<PathBuf, Error> {
+    let not_found = || Error::NotFound(json!({"error": "File not found"}));
+    let file = asset_path(path).ok_or_else(not_found)?;
+
+    let image_url = format!("/assets/{}", path);
+    if auth.is_none_or(|a| a.require_roles(&[Role::Admin, Role::Moderator, Role::Superuser]).is_err())
+        && !recipe_repository::is_image_visible(db, &image_url, auth.map(|a| a.user.id)).await?
+    {
+        return Err(not_found());
+    }
+    Ok(file)
+}
+/// The author and the collaborators of a recipe may change it, as may staff and the owner
+/// and editors of its household. Returns the role of a collaborator, so that their changes
+/// are recorded as such.
+pub async fn authorize_edit(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    auth: &AuthenticatedUser,
+) -> Result<Option<RecipeCollaboratorRole>, Error> {
+    let recipe = recipe_repository::find_by_id(db, recipe_id).await?;
+    if recipe.author_id == Some(auth.user.id) {
+        return Ok(None);
+    }
+    if let Some(role) = collaborator_repository::find_role(db, recipe_id, auth.user.id).await? {
+        return Ok(Some(role));
+    }
+    if let Some(household_id) = recipe.household_id
+        && let Some(role) = household_repository::find_role(db, household_id, auth.user.id).await?
+        && role.can_edit()
+    {
+        return Ok(None);
+    }
+    auth.require_roles(&[Role::Admin, Role::Moderator, Role::Superuser])?;
+    Ok(None)
+}
+/// Only the author of a recipe, or staff, may delete it or change who sees it; recipes
+/// without an author are left to staff.
+pub async fn authorize_owner(db: &DatabaseConnection, recipe_id: Uuid, auth: &AuthenticatedUser) -> Result<(), Error> {
+    let recipe = recipe_repository::find_by_id(db, recipe_id).await?;
+    match recipe.author_id {
+        Some(author_id) => auth.require_owner_or_roles(author_id, &[Role::Admin, Role::Moderator, Role::Superuser]),
+        None => auth.require_roles(&[Role::Admin, Role::Moderator, Role::Superuser]),
     }
 }
 pub async fn get_by_author(
@@ -128,6 +230,7 @@ pub async fn get_by_author(
 
 pub async fn create(
     db: &DatabaseConnection,
+    dict_db: &SqlitePool,
     mut new_recipe: CreateRecipeInput,
     preferred_language: &str,
 ) -> Result<RecipeViewDto, Error> {
@@ -146,7 +249,8 @@ pub async fn create(
     }
 
     let inserted_recipe: RecipeViewDto =
-        recipe_repository::create(db, new_recipe, preferred_language).await?;
+        recipe_repository::create(db, new_recipe, preferred_language, None).await?;
+    dietary_service::refresh_recipe(db, dict_db, inserted_recipe.id).await?;
     Ok(inserted_recipe)
 }
 pub async fn get_last(
@@ -188,6 +292,8 @@ pub async fn get_recent(
 ) -> Result<Vec<RecipeViewDto>, Error> {
     // 1. Fetch latest public recipes from repository
     let recipes = recipe_repository::find_latest_work(db, limit, user_id).await?;
+    let recipe_ids: Vec<Uuid> = recipes.iter().map(|r| r.id).collect();
+    let mut collaborators = collaborator_repository::find_by_recipes(db, &recipe_ids).await?;
 
     let mut dtos = Vec::new();
 
@@ -202,7 +308,9 @@ pub async fn get_recent(
 
         let (nb_ingredients, nb_steps) = recipe_repository::get_recipe_counts(db, recipe.id).await?.unwrap_or((0, 0));
 
-        let dto = RecipeViewDto::from((recipe, translation, Some(nb_ingredients), Some(nb_steps)));
+        let recipe_id = recipe.id;
+        let mut dto = RecipeViewDto::from((recipe, translation, Some(nb_ingredients), Some(nb_steps)));
+        dto.collaborators = collaborators.remove(&recipe_id).unwrap_or_default();
         dtos.push(dto);
     }
 
@@ -212,9 +320,15 @@ pub async fn get_by_author_and_filter(
     db: &DatabaseConnection,
     user_id: Uuid,
     filter: GetAllRecipesByPageQuery,
-    lang_code: &String
-) -> Result<Vec<RecipeViewDto>, Error> {
-    let recipes = recipe_repository::get_by_author_and_filter(db, user_id, filter, lang_code).await?;
+    lang_code: &str
+) -> Result<RecipePagination, Error> {
+    let search = search_term(filter.filters.as_ref().and_then(|f| f.search.as_deref()));
+    let page = filter.page.unwrap_or(1).max(1) as i32;
+    let per_page = filter.per_page.unwrap_or(10).max(1) as i32;
+    let (recipes, total, next_cursor) = recipe_repository::get_by_author_and_filter(db, user_id, filter, lang_code).await?;
+    let recipe_ids: Vec<Uuid> = recipes.iter().map(|r| r.id).collect();
+    let mut collaborators = collaborator_repository::find_by_recipes(db, &recipe_ids).await?;
+    let mut matches = search_matches(db, &recipe_ids, lang_code, search.as_deref()).await?;
 
     let mut dtos = Vec::new();
 
@@ -229,38 +343,71 @@ pub async fn get_by_author_and_filter(
 
         let (nb_ingredients, nb_steps) = recipe_repository::get_recipe_counts(db, recipe.id).await?.unwrap_or((0, 0));
 
-        let dto = RecipeViewDto::from((recipe, translation, Some(nb_ingredients), Some(nb_steps)));
+        let recipe_id = recipe.id;
+        let mut dto = RecipeViewDto::from((recipe, translation, Some(nb_ingredients), Some(nb_steps)));
+        dto.collaborators = collaborators.remove(&recipe_id).unwrap_or_default();
+        dto.search = matches.remove(&recipe_id);
         dtos.push(dto);
     }
 
-    Ok(dtos)
+    Ok(RecipePagination {
+        data: dtos,
+        total: total as i32,
+        page,
+        per_page,
+        has_more: next_cursor.is_some(),
+        next_cursor,
+    })
+}
+
+pub async fn get_facets(
+    db: &DatabaseConnection,
+    lang_code: &str,
+    filter: RecipeFilter,
+    viewer_id: Option<Uuid>,
+) -> Result<RecipeFacetsDto, Error> {
+    recipe_repository::get_facets(db, &filter, lang_code, viewer_id).await
 }
 
 pub async fn get_all_by_page(
     db: &DatabaseConnection,
     lang_code: &str,
     filter: RecipeFilterByPage,
-) -> Result<Vec<RecipeViewDto>, Error> {
-    let recipes = recipe_repository::find_by_query_by_page(db, filter, lang_code).await?;
+    viewer_id: Option<Uuid>,
+) -> Result<RecipePagination, Error> {
+    let search = search_term(filter.filters.as_ref().and_then(|f| f.search.as_deref()));
+    let page = filter.page.unwrap_or(1).max(1);
+    let per_page = filter.per_page.unwrap_or(10).max(1);
+    let (recipes, total, next_cursor) = recipe_repository::find_by_query_by_page(db, filter, lang_code, viewer_id).await?;
+    let recipe_ids: Vec<Uuid> = recipes.iter().map(|r| r.id).collect();
+    let mut matches = search_matches(db, &recipe_ids, lang_code, search.as_deref()).await?;
 
     let mut dtos = Vec::new();
 
-    if let Some(recipes) = recipes {
-        for recipe in recipes {
-            let translation = recipe_translation_repository::find_translation(
-                db,
-                recipe.id,
-                lang_code,
-                recipe.original_language_code.deref(),
-            )
-            .await?;
-            let (nb_ingredients, nb_steps) = recipe_repository::get_recipe_counts(db, recipe.id).await?.unwrap_or((0, 0));
+    for recipe in recipes {
+        let translation = recipe_translation_repository::find_translation(
+            db,
+            recipe.id,
+            lang_code,
+            recipe.original_language_code.deref(),
+        )
+        .await?;
+        let (nb_ingredients, nb_steps) = recipe_repository::get_recipe_counts(db, recipe.id).await?.unwrap_or((0, 0));
 
-            let dto = RecipeViewDto::from((recipe, translation, Some(nb_ingredients), Some(nb_steps)));
-            dtos.push(dto);
-        }
+        let recipe_id = recipe.id;
+        let mut dto = RecipeViewDto::from((recipe, translation, Some(nb_ingredients), Some(nb_steps)));
+        dto.search = matches.remove(&recipe_id);
+        dtos.push(dto);
     }
-    Ok(dtos)
+
+    Ok(RecipePagination {
+        data: dtos,
+        total: total as i32,
+        page,
+        per_page,
+        has_more: next_cursor.is_some(),
+        next_cursor,
+    })
 }
 pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<bool, Error> {
     let result = recipe_repository::delete(db, id).await?;
@@ -268,19 +415,29 @@ pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<bool, Error> {
 }
 pub async fn update(
     db: &DatabaseConnection,
+    dict_db: &SqlitePool,
     updated_recipe: EditRecipeInput,
     recipe_id: Uuid,
     lang_code: &str,
-    user: UserResponseDto,
+    auth: &AuthenticatedUser,
+    edited_as: Option<RecipeCollaboratorRole>,
 ) -> Result<RecipeViewDto, Error> {
-    let original = get_by_id(db, recipe_id, lang_code, true).await?;
-    recipe_repository::update(db, updated_recipe, recipe_id, lang_code).await?;
-    match original {
-        RecipeResponse::View(_) => {}
-        RecipeResponse::Editor(original) => {
-            recipe_version_repository::create(db, original, user.id).await?;
+    let may_change_visibility = authorize_owner(db, recipe_id, auth).await.is_ok();
+    let original = match get_by_id(db, recipe_id, lang_code, true).await? {
+        RecipeResponse::Editor(original) => original,
+        RecipeResponse::View(_) => {
+            return Err(Error::InternalServerError(json!({
+                "message": "Unexpected response type before recipe update",
+                "operation": "update",
+                "recipe_id": recipe_id.to_string(),
+                "expected": "RecipeResponse::Editor",
+                "received": "RecipeResponse::View",
+                "stage": "response_validation"
+            })));
         }
-    }
+    };
+    recipe_repository::update(db, updated_recipe, recipe_id, lang_code, may_change_visibility, original, auth.user.id, edited_as).await?;
+    dietary_service::refresh_recipe(db, dict_db, recipe_id).await?;
     let result = get_by_id(db, recipe_id, lang_code, false).await?;
     match result {
         RecipeResponse::View(recipe_view) => Ok(recipe_view),
@@ -362,6 +519,187 @@ pub async fn toggle_favorite(
 
     Ok(true)
 }
+/// Copies a recipe with every translation, group, step and tag into a new recipe of
+/// `user`, images included, and tells the original author about it. The fork keeps
+/// the visibility of its original.
+pub async fn fork(
+    state: &Data<AppState>,
+    recipe_id: Uuid,
+    user: UserResponseDto,
+    lang_code: &str,
+) -> Result<RecipeViewDto, Error> {
+    let db = &state.db;
+    let original = recipe_repository::find_by_id(db, recipe_id).await?;
+    if !recipe_repository::is_visible(db, recipe_id, Some(user.id)).await? {
+        return Err(Error::NotFound(json!({"error": "Recipe not found"})));
+    }
+
+    let RecipeResponse::Editor(editor) = get_by_id(db, recipe_id, lang_code, true).await? else {
+        return Err(Error::InternalServerError(json!({
+            "message": "Unexpected response type before recipe fork",
+            "operation": "fork",
+            "recipe_id": recipe_id.to_string(),
+            "expected": "RecipeResponse::Editor",
+            "received": "RecipeResponse::View",
+            "stage": "response_validation"
+        })));
+    };
+
+    // Tags are shared, not copied
+    let tags = editor.tags.iter().map(|t| InputTag::Existing { id: t.id }).collect();
+    let input = CreateRecipeInput {
+        author_id: Some(user.id),
+        author: Some(user.username.clone()),
+        tags,
+        ..CreateRecipeInput::from(editor)
+    };
+
+    let created = recipe_repository::create(db, input, lang_code, Some(recipe_id)).await?;
+    // Images are copied once the fork exists, so a failed insert leaves no stray files
+    if let Err(e) = copy_fork_images(db, &created).await {
+        recipe_repository::delete(db, created.id).await?;
+        return Err(e);
+    }
+    dietary_service::refresh_recipe(db, &state.dict_db, created.id).await?;
+
+    if let Some(author_id) = original.author_id
+        && author_id != user.id
+    {
+        // The fork is made either way; a failed notification is only logged
+        if let Err(e) = notify_fork(state, &original, author_id, created.id, user).await {
+            log::error!("Failed to notify the author of recipe {} about fork {}: {:?}", recipe_id, created.id, e);
+        }
+    }
+
+    match get_by_id(db, created.id, lang_code, false).await? {
+        RecipeResponse::View(recipe_view) => Ok(recipe_view),
+        RecipeResponse::Editor(_) => Err(Error::InternalServerError(json!({
+            "message": "Unexpected response type after recipe fork",
+            "operation": "fork",
+            "recipe_id": created.id.to_string(),
+            "expected": "RecipeResponse::View",
+            "received": "RecipeResponse::Editor",
+            "stage": "response_validation"
+        }))),
+    }
+}
+async fn copy_fork_images(db: &DatabaseConnection, fork: &RecipeViewDto) -> Result<(), Error> {
+    let target_dir = "assets/recipes";
+    fs::create_dir_all(target_dir)?;
+
+    let image_url = copy_stored_file(&fork.image_url, target_dir)?;
+    let mut step_image_urls = Vec::new();
+    for group in &fork.step_groups {
+        for step in &group.steps {
+            if let Some(url) = &step.image_url {
+                step_image_urls.push((step.id, copy_stored_file(url, target_dir)?));
+            }
+        }
+    }
+    recipe_repository::set_image_urls(db, fork.id, image_url, step_image_urls).await
+}
+pub async fn get_forks(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    viewer_id: Option<Uuid>,
+    lang: &str,
+) -> Result<Vec<RecipeViewDto>, Error> {
+    recipe_repository::find_by_id(db, recipe_id).await?;
+    if !recipe_repository::is_visible(db, recipe_id, viewer_id).await? {
+        return Err(Error::NotFound(json!({"error": "Recipe not found"})));
+    }
+
+    let forks = recipe_repository::find_forks(db, recipe_id, viewer_id).await?;
+
+    let mut dtos = Vec::new();
+    for fork in forks {
+        let translation = recipe_translation_repository::find_translation(
+            db,
+            fork.id,
+            lang,
+            fork.original_language_code.deref(),
+        )
+        .await?;
+        let (nb_ingredients, nb_steps) = recipe_repository::get_recipe_counts(db, fork.id).await?.unwrap_or((0, 0));
+        dtos.push(RecipeViewDto::from((fork, translation, Some(nb_ingredients), Some(nb_steps))));
+    }
+
+    Ok(dtos)
+}
+async fn notify_fork(
+    state: &Data<AppState>,
+    original: &recipes::Model,
+    author_id: Uuid,
+    fork_id: Uuid,
+    user: UserResponseDto,
+) -> Result<(), Error> {
+    let db = &state.db;
+    let recipient = user_repository::find_by_id(db, author_id).await?;
+
+    let is_enabled = recipient.preferences
+        .get("recipe_forked_enabled")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+    if !is_enabled {
+        return Ok(());
+    }
+
+    let lang = recipient.preferences
+        .get("language")
+        .and_then(|v| v.as_str())
+        .unwrap_or("en");
+    let translation = recipe_translation_repository::find_translation(
+        db, original.id, lang, &original.original_language_code
+    ).await?;
+
+    let mut variables = HashMap::new();
+    variables.insert("actor".to_string(), user.username);
+    variables.insert("recipe_title".to_string(), translation.title);
+
+    let trigger = NotificationTrigger {
+        recipient_id: author_id,
+        actor_id: Some(user.id),
+        category: "recipe_forked".to_string(),
+        target_id: Some(fork_id),
+        variables,
+    };
+
+    notification_service::trigger(state, trigger).await
+}
+/// Ancestors of a forked recipe, its parent first. Private ones are reduced to their id.
+async fn get_lineage(db: &DatabaseConnection, recipe_id: Uuid, lang_code: &str) -> Result<Vec<RecipeLineageDto>, Error> {
+    let ancestors = recipe_repository::find_lineage(db, recipe_id).await?;
+
+    let mut lineage = Vec::with_capacity(ancestors.len());
+    for ancestor in ancestors {
+        if ancestor.is_private {
+            lineage.push(RecipeLineageDto {
+                id: ancestor.id,
+                title: None,
+                author_id: None,
+                author: None,
+                is_private: true,
+            });
+            continue;
+        }
+
+        let translation = recipe_translation_repository::find_translation(
+            db,
+            ancestor.id,
+            lang_code,
+            &ancestor.original_language_code,
+        )
+        .await?;
+        lineage.push(RecipeLineageDto {
+            id: ancestor.id,
+            title: Some(translation.title),
+            author_id: ancestor.author_id,
+            author: ancestor.author,
+            is_private: false,
+        });
+    }
+    Ok(lineage)
+}
 pub async fn rate(
     db: &DatabaseConnection,
     recipe_id: Uuid,
@@ -510,3 +848,119 @@ pub async fn get_version(
 ) -> Result<RecipeVersionDto, Error> {
     recipe_version_repository::get_version(db, recipe_id, version_id).await
 }
+pub async fn restore_version(
+    db: &DatabaseConnection,
+    dict_db: &SqlitePool,
+    recipe_id: Uuid,
+    version_id: Uuid,
+    lang_code: &str,
+    auth: &AuthenticatedUser,
+    edited_as: Option<RecipeCollaboratorRole>,
+) -> Result<RecipeViewDto, Error> {
+    let may_change_visibility = authorize_owner(db, recipe_id, auth).await.is_ok();
+    let version = recipe_version_repository::get_version(db, recipe_id, version_id).await?;
+    let current = match get_by_id(db, recipe_id, lang_code, true).await? {
+        RecipeResponse::Editor(current) => current,
+        RecipeResponse::View(_) => {
+            return Err(Error::InternalServerError(json!({
+                "message": "Unexpected response type before recipe restore",
+                "operation": "restore_version",
+                "recipe_id": recipe_id.to_string(),
+                "expected": "RecipeResponse::Editor",
+                "received": "RecipeResponse::View",
+                "stage": "response_validation"
+            })));
+        }
+    };
+
+    recipe_repository::restore(db, recipe_id, version.data, current, auth.user.id, edited_as, may_change_visibility).await?;
+    dietary_service::refresh_recipe(db, dict_db, recipe_id).await?;
+
+    match get_by_id(db, recipe_id, lang_code, false).await? {
+        RecipeResponse::View(recipe_view) => Ok(recipe_view),
+        RecipeResponse::Editor(_) => Err(Error::InternalServerError(json!({
+            "message": "Unexpected response type after recipe restore",
+            "operation": "restore_version",
+            "recipe_id": recipe_id.to_string(),
+            "expected": "RecipeResponse::View",
+            "received": "RecipeResponse::Editor",
+            "stage": "response_validation"
+        }))),
+    }
+}
+pub async fn diff_versions(
+    db: &DatabaseConnection,
+    recipe_id: Uuid,
+    version_id: Uuid,
+    compare_to: Option<Uuid>,
+    lang_code: &str,
+) -> Result<RecipeVersionDiffDto, Error> {
+    let from = recipe_version_repository::get_version(db, recipe_id, version_id).await?;
+    let to = match compare_to {
+        Some(other_id) => recipe_version_repository::get_version(db, recipe_id, other_id).await?.data,
+        None => match get_by_id(db, recipe_id, lang_code, true).await? {
+            RecipeResponse::Editor(current) => current,
+            RecipeResponse::View(_) => {
+                return Err(Error::InternalServerError(json!({
+                    "message": "Unexpected response type while diffing recipe versions",
+                    "operation": "diff_versions",
+                    "recipe_id": recipe_id.to_string(),
+                    "expected": "RecipeResponse::Editor",
+                    "received": "RecipeResponse::View",
+                    "stage": "response_validation"
+                })));
+            }
+        },
+    };
+
+    Ok(recipe_diff::diff(recipe_id, version_id, compare_to, &from.data, &to, lang_code))
+}
+pub async fn adjust_view(
+    db: &DatabaseConnection,
+    mut recipe: RecipeViewDto,
+    servings: Option<i32>,
+    unit_system: Option<UnitSystem>,
+) -> Result<RecipeViewDto, Error> {
+    if let Some(target) = servings.filter(|s| *s <= 0) {
+        return Err(Error::BadRequest(json!({
+            "message": "Servings must be greater than zero",
+            "operation": "adjust_view",
+            "recipe_id": recipe.id.to_string(),
+            "servings": target
+        })));
+    }
+
+    let converter = UnitConverter::new(unit_repository::get_active_units(db).await?)
+        .with_densities(density_repository::get_all(db).await?);
+    let nutrition = nutrition_service::compute(db, &recipe, servings.unwrap_or(recipe.servings), &converter).await?;
+    if let Some(target) = servings {
+        serving_scaler::scale_recipe(&mut recipe, target, &converter);
+    }
+    if let Some(system) = unit_system {
+        unit::convert_recipe(&mut recipe, system, &converter);
+    }
+    unit::attach_equivalents(&mut recipe, unit_system, &converter);
+    recipe.nutrition = Some(Box::new(nutrition));
+    Ok(recipe)
+}
+
+fn search_term(search: Option<&str>) -> Option<String> {
+    search
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+async fn search_matches(
+    db: &DatabaseConnection,
+    recipe_ids: &[Uuid],
+    lang_code: &str,
+    search: Option<&str>,
+) -> Result<HashMap<Uuid, RecipeSearchMatchDto>, Error> {
+    match search {
+        Some(search) if !recipe_ids.is_empty() => {
+            recipe_repository::find_search_matches(db, recipe_ids, lang_code, search).await
+        }
+        _ => Ok(HashMap::new()),
+    }
+}
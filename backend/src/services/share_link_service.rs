@@ -0,0 +1,133 @@
+use crate::domain::user::{AuthenticatedUser, Role};
+use crate::dto::recipe_dto::RecipeViewDto;
+use crate::dto::share_link_dto::{ShareLinkDto, ShareLinkInput};
+use crate::errors::Error;
+use crate::repositories::{recipe_repository, share_link_repository};
+use crate::utils::file_upload::asset_path;
+use chrono::Utc;
+use entity::recipe_share_links;
+use rand::distr::Alphanumeric;
+use rand::Rng;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+pub async fn get_links(
+    db: &DatabaseConnection,
+    auth: &AuthenticatedUser,
+    recipe_id: Uuid,
+) -> Result<Vec<ShareLinkDto>, Error> {
+    require_manager(db, auth, recipe_id).await?;
+    let links = share_link_repository::find_by_recipe(db, recipe_id).await?;
+    Ok(links.into_iter().map(to_dto).collect())
+}
+
+pub async fn create_link(
+    db: &DatabaseConnection,
+    auth: &AuthenticatedUser,
+    recipe_id: Uuid,
+    input: ShareLinkInput,
+) -> Result<ShareLinkDto, Error> {
+    require_manager(db, auth, recipe_id).await?;
+    if input.expires_at.is_some_and(|at| at <= Utc::now()) {
+        return Err(Error::BadRequest(json!({
+            "message": "The expiry of a share link must be in the future",
+            "operation": "create_link",
+            "entity": "recipe_share_links",
+            "recipe_id": recipe_id.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    let token: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(48)
+        .map(char::from)
+        .collect();
+    let link = share_link_repository::create(db, recipe_id, auth.user.id, token, input).await?;
+    Ok(to_dto(link))
+}
+
+pub async fn revoke_link(
+    db: &DatabaseConnection,
+    auth: &AuthenticatedUser,
+    recipe_id: Uuid,
+    id: Uuid,
+) -> Result<ShareLinkDto, Error> {
+    require_manager(db, auth, recipe_id).await?;
+    let link = share_link_repository::revoke(db, recipe_id, id).await?;
+    Ok(to_dto(link))
+}
+
+/// Whether `token` is an active link to `recipe_id`; any other token grants nothing.
+pub async fn grants(db: &DatabaseConnection, recipe_id: Uuid, token: &str) -> Result<bool, Error> {
+    let link = share_link_repository::find_active(db, token).await?;
+    Ok(link.is_some_and(|link| link.recipe_id == recipe_id))
+}
+
+/// Counts an opening of the recipe page through `token`. Exports and the other reads
+/// the link grants are not counted.
+pub async fn record_view(db: &DatabaseConnection, recipe_id: Uuid, token: &str) -> Result<(), Error> {
+    if let Some(link) = share_link_repository::find_active(db, token).await?
+        && link.recipe_id == recipe_id
+    {
+        share_link_repository::record_open(db, link.id).await?;
+    }
+    Ok(())
+}
+
+/// Points the stored images of a shared recipe at the token, so that they load
+/// without an account.
+pub fn share_images(view: &mut RecipeViewDto, token: &str) {
+    view.image_url = share_image(&view.image_url, token);
+    for step in view.step_groups.iter_mut().flat_map(|g| g.steps.iter_mut()) {
+        step.image_url = step.image_url.as_deref().map(|url| share_image(url, token));
+    }
+}
+
+/// File of an image of the shared recipe; any other file is reported missing.
+pub async fn find_image(db: &DatabaseConnection, token: &str, path: &str) -> Result<PathBuf, Error> {
+    let not_found = || Error::NotFound(json!({"error": "Image not found"}));
+    let link = share_link_repository::find_active(db, token).await?.ok_or_else(not_found)?;
+    let file = asset_path(path).ok_or_else(not_found)?;
+
+    let image_url = format!("/assets/{}", path);
+    if !share_link_repository::is_recipe_image(db, link.recipe_id, &image_url).await? {
+        return Err(not_found());
+    }
+    Ok(file)
+}
+
+fn share_image(url: &str, token: &str) -> String {
+    match url.strip_prefix("/assets/") {
+        Some(path) => format!("/api/recipes/shared/{}/assets/{}", token, path),
+        None => url.to_string(),
+    }
+}
+
+/// Only the author of the recipe, or a moderator, may manage its links.
+async fn require_manager(db: &DatabaseConnection, auth: &AuthenticatedUser, recipe_id: Uuid) -> Result<(), Error> {
+    let recipe = recipe_repository::find_by_id(db, recipe_id).await?;
+    if recipe.author_id != Some(auth.user.id) {
+        auth.require_roles(&[Role::Admin, Role::Moderator, Role::Superuser])?;
+    }
+    Ok(())
+}
+
+fn to_dto(link: recipe_share_links::Model) -> ShareLinkDto {
+    let now = Utc::now();
+    ShareLinkDto {
+        id: link.id,
+        recipe_id: link.recipe_id,
+        path: format!("/api/recipes/{}?share={}", link.recipe_id, link.token),
+        token: link.token,
+        label: link.label,
+        is_active: link.revoked_at.is_none() && link.expires_at.is_none_or(|at| at > now),
+        expires_at: link.expires_at.map(|at| at.with_timezone(&Utc)),
+        revoked_at: link.revoked_at.map(|at| at.with_timezone(&Utc)),
+        open_count: link.open_count,
+        last_opened_at: link.last_opened_at.map(|at| at.with_timezone(&Utc)),
+        created_at: link.created_at.with_timezone(&Utc),
+    }
+}
@@ -0,0 +1,355 @@
+use crate::app::state::AppState;
+use crate::dto::shopping_list_dto::{
+    Aisle, ShoppingListAisleDto, ShoppingListDto, ShoppingListEventDto, ShoppingListEventKind, ShoppingListInput,
+    ShoppingListItemDto, ShoppingListItemInput, ShoppingListItemUpdate, ShoppingListMemberInput, ShoppingListRecipeDto,
+    ShoppingListRecipeInput, ShoppingListSummaryDto,
+};
+use crate::errors::Error;
+use crate::repositories::{
+    canonical_ingredient_repository, ingredient_group_repository, recipe_repository, recipe_translation_repository,
+    shopping_list_repository, unit_repository,
+};
+use crate::utils::shopping_list::{self, ShoppingLine};
+use crate::utils::unit::{round_for_unit, UnitConverter};
+use chrono::Utc;
+use entity::shopping_lists;
+use rust_decimal::prelude::ToPrimitive;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn get_all(db: &DatabaseConnection, user_id: Uuid) -> Result<Vec<ShoppingListSummaryDto>, Error> {
+    shopping_list_repository::find_for_user(db, user_id).await
+}
+
+pub async fn get(db: &DatabaseConnection, user_id: Uuid, id: Uuid, lang_code: &str) -> Result<ShoppingListDto, Error> {
+    let list = find_shared(db, id, user_id).await?;
+    build(db, list, lang_code).await
+}
+
+pub async fn create(
+    state: &AppState,
+    user_id: Uuid,
+    input: ShoppingListInput,
+    lang_code: &str,
+) -> Result<ShoppingListDto, Error> {
+    let list = shopping_list_repository::create(&state.db, user_id, input.name.trim().to_string()).await?;
+    build(&state.db, list, lang_code).await
+}
+
+pub async fn rename(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+    input: ShoppingListInput,
+    lang_code: &str,
+) -> Result<ShoppingListDto, Error> {
+    let list = find_owned(&state.db, id, user_id).await?;
+    shopping_list_repository::rename(&state.db, list, input.name.trim().to_string()).await?;
+    publish(state, id, lang_code).await
+}
+
+pub async fn delete(state: &AppState, user_id: Uuid, id: Uuid) -> Result<(), Error> {
+    let list = find_owned(&state.db, id, user_id).await?;
+    let user_ids = shopping_list_repository::find_user_ids(&state.db, &list).await?;
+    shopping_list_repository::delete(&state.db, id).await?;
+
+    broadcast(state, &user_ids, ShoppingListEventKind::ShoppingListDeleted, id, None).await;
+    Ok(())
+}
+
+/// Adds a recipe at the given servings, or changes the servings of one already on
+/// the list, then merges the ingredients again.
+pub async fn add_recipe(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+    input: ShoppingListRecipeInput,
+    lang_code: &str,
+) -> Result<ShoppingListDto, Error> {
+    find_shared(&state.db, id, user_id).await?;
+    let recipe = recipe_repository::find_by_id(&state.db, input.recipe_id).await?;
+    if !recipe_repository::is_visible(&state.db, recipe.id, Some(user_id)).await? {
+        return Err(Error::NotFound(json!({"error": "Recipe not found"})));
+    }
+
+    let servings = input.servings.unwrap_or(recipe.servings).max(1);
+    shopping_list_repository::save_recipe(&state.db, id, recipe.id, servings).await?;
+    rebuild(&state.db, id, lang_code).await?;
+    publish(state, id, lang_code).await
+}
+
+pub async fn remove_recipe(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+    recipe_id: Uuid,
+    lang_code: &str,
+) -> Result<ShoppingListDto, Error> {
+    find_shared(&state.db, id, user_id).await?;
+    shopping_list_repository::remove_recipe(&state.db, id, recipe_id).await?;
+    rebuild(&state.db, id, lang_code).await?;
+    publish(state, id, lang_code).await
+}
+
+/// Merges the recipes of the list again, e.g. after one of them was edited.
+pub async fn refresh(state: &AppState, user_id: Uuid, id: Uuid, lang_code: &str) -> Result<ShoppingListDto, Error> {
+    find_shared(&state.db, id, user_id).await?;
+    rebuild(&state.db, id, lang_code).await?;
+    publish(state, id, lang_code).await
+}
+
+pub async fn add_item(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+    input: ShoppingListItemInput,
+    lang_code: &str,
+) -> Result<ShoppingListDto, Error> {
+    find_shared(&state.db, id, user_id).await?;
+    if let Some(unit_id) = input.unit_id {
+        unit_repository::find_by_id(&state.db, unit_id).await?;
+    }
+    shopping_list_repository::create_item(&state.db, id, input).await?;
+    publish(state, id, lang_code).await
+}
+
+/// Items merged from recipes follow their recipes, so only their checked state
+/// may be changed.
+pub async fn update_item(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+    item_id: Uuid,
+    input: ShoppingListItemUpdate,
+    lang_code: &str,
+) -> Result<ShoppingListDto, Error> {
+    find_shared(&state.db, id, user_id).await?;
+    let item = shopping_list_repository::find_item(&state.db, id, item_id).await?;
+    let edits_details = input.name.is_some() || input.quantity.is_some() || input.unit_id.is_some() || input.aisle.is_some();
+    if item.item_key.is_some() && edits_details {
+        return Err(Error::BadRequest(json!({
+            "message": "Items merged from recipes can only be checked or unchecked",
+            "operation": "update_item",
+            "entity": "shopping_list_items",
+            "item_id": item_id.to_string(),
+            "stage": "validation"
+        })));
+    }
+    if let Some(unit_id) = input.unit_id {
+        unit_repository::find_by_id(&state.db, unit_id).await?;
+    }
+
+    shopping_list_repository::update_item(&state.db, item, input).await?;
+    publish(state, id, lang_code).await
+}
+
+pub async fn delete_item(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+    item_id: Uuid,
+    lang_code: &str,
+) -> Result<ShoppingListDto, Error> {
+    find_shared(&state.db, id, user_id).await?;
+    let item = shopping_list_repository::find_item(&state.db, id, item_id).await?;
+    if item.item_key.is_some() {
+        return Err(Error::BadRequest(json!({
+            "message": "Items merged from recipes go away with their recipe",
+            "operation": "delete_item",
+            "entity": "shopping_list_items",
+            "item_id": item_id.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    shopping_list_repository::delete_item(&state.db, item_id).await?;
+    publish(state, id, lang_code).await
+}
+
+/// Shares the list with another user, found by email. Only the owner may do so.
+pub async fn add_member(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+    input: ShoppingListMemberInput,
+    lang_code: &str,
+) -> Result<ShoppingListDto, Error> {
+    let list = find_owned(&state.db, id, user_id).await?;
+    let member = shopping_list_repository::find_user_by_email(&state.db, &input.email).await?;
+    if member.id == list.owner_id {
+        return Err(Error::BadRequest(json!({
+            "message": "The owner already has access to the list",
+            "operation": "add_member",
+            "entity": "shopping_list_members",
+            "shopping_list_id": id.to_string(),
+            "stage": "validation"
+        })));
+    }
+
+    shopping_list_repository::add_member(&state.db, id, member.id).await?;
+    publish(state, id, lang_code).await
+}
+
+/// The owner removes a member, or a member leaves the list.
+pub async fn remove_member(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+    member_id: Uuid,
+    lang_code: &str,
+) -> Result<(), Error> {
+    let list = find_shared(&state.db, id, user_id).await?;
+    if list.owner_id != user_id && member_id != user_id {
+        return Err(Error::Forbidden(json!({"message": "Unauthorized"})));
+    }
+
+    shopping_list_repository::remove_member(&state.db, id, member_id).await?;
+    broadcast(state, &[member_id], ShoppingListEventKind::ShoppingListDeleted, id, None).await;
+    publish(state, id, lang_code).await?;
+    Ok(())
+}
+
+/// Lists are only visible to their owner and members; to anyone else they do not exist.
+async fn find_shared(db: &DatabaseConnection, id: Uuid, user_id: Uuid) -> Result<shopping_lists::Model, Error> {
+    let list = shopping_list_repository::find_by_id(db, id).await?;
+    if list.owner_id != user_id && !shopping_list_repository::is_member(db, id, user_id).await? {
+        return Err(Error::NotFound(json!({"error": "Shopping list not found"})));
+    }
+    Ok(list)
+}
+
+async fn find_owned(db: &DatabaseConnection, id: Uuid, user_id: Uuid) -> Result<shopping_lists::Model, Error> {
+    let list = find_shared(db, id, user_id).await?;
+    if list.owner_id != user_id {
+        return Err(Error::Forbidden(json!({"message": "Unauthorized"})));
+    }
+    Ok(list)
+}
+
+/// Scales the ingredients of every recipe on the list and merges them into items.
+async fn rebuild(db: &DatabaseConnection, id: Uuid, lang_code: &str) -> Result<(), Error> {
+    let converter = UnitConverter::new(unit_repository::get_active_units(db).await?);
+
+    let mut lines = Vec::new();
+    for entry in shopping_list_repository::find_recipes(db, id).await? {
+        let recipe = recipe_repository::find_by_id(db, entry.recipe_id).await?;
+        let ratio = if recipe.servings > 0 { entry.servings as f64 / recipe.servings as f64 } else { 1.0 };
+        let groups =
+            ingredient_group_repository::find_by_recipe(db, recipe.id, lang_code, &recipe.original_language_code).await?;
+
+        for ingredient in groups.into_iter().flat_map(|g| g.ingredients) {
+            lines.push(ShoppingLine {
+                name: ingredient.name,
+                quantity: ingredient.quantity.to_f64().unwrap_or(0.0) * ratio,
+                unit: ingredient.unit,
+                canonical_ingredient_id: ingredient.canonical_ingredient_id,
+                aisle: Aisle::Other,
+            });
+        }
+    }
+
+    let catalog_ids = lines.iter().filter_map(|l| l.canonical_ingredient_id).collect();
+    let aisles = canonical_ingredient_repository::find_aisles(db, catalog_ids).await?;
+    for line in &mut lines {
+        if let Some(aisle) = line.canonical_ingredient_id.and_then(|c| aisles.get(&c)) {
+            line.aisle = *aisle;
+        }
+    }
+
+    let items = shopping_list::merge_lines(lines, &converter);
+    shopping_list_repository::save_merged_items(db, id, items).await
+}
+
+async fn build(db: &DatabaseConnection, list: shopping_lists::Model, lang_code: &str) -> Result<ShoppingListDto, Error> {
+    let members = shopping_list_repository::find_members(db, list.id).await?;
+
+    let mut recipes = Vec::new();
+    for entry in shopping_list_repository::find_recipes(db, list.id).await? {
+        let recipe = recipe_repository::find_by_id(db, entry.recipe_id).await?;
+        let translation =
+            recipe_translation_repository::find_translation(db, recipe.id, lang_code, &recipe.original_language_code)
+                .await?;
+        recipes.push(ShoppingListRecipeDto {
+            recipe_id: recipe.id,
+            title: translation.title,
+            servings: entry.servings,
+        });
+    }
+
+    let units = unit_repository::get_all_admin(db).await?;
+    let items: Vec<ShoppingListItemDto> = shopping_list_repository::find_items(db, list.id)
+        .await?
+        .into_iter()
+        .map(|item| {
+            let unit = item.unit_id.and_then(|u| units.iter().find(|unit| unit.id == u)).cloned();
+            let rounded = match (item.quantity, &unit) {
+                (Some(q), Some(u)) => Some(round_for_unit(q, u)),
+                (Some(q), None) => rust_decimal::Decimal::from_f64_retain(q)
+                    .map(|d| d.round_dp(2).normalize())
+                    .map(|d| (d, d.to_string())),
+                (None, _) => None,
+            };
+            ShoppingListItemDto {
+                id: item.id,
+                aisle: shopping_list_repository::item_aisle(&item),
+                name: item.name,
+                quantity: rounded.as_ref().map(|(q, _)| *q),
+                display_quantity: rounded.map(|(_, d)| d),
+                unit,
+                checked: item.checked,
+                from_recipes: item.item_key.is_some(),
+                canonical_ingredient_id: item.canonical_ingredient_id,
+            }
+        })
+        .collect();
+
+    let aisles = Aisle::ALL
+        .into_iter()
+        .map(|aisle| ShoppingListAisleDto {
+            aisle,
+            items: items.iter().filter(|i| i.aisle == aisle).cloned().collect(),
+        })
+        .filter(|a| !a.items.is_empty())
+        .collect();
+
+    Ok(ShoppingListDto {
+        id: list.id,
+        name: list.name,
+        owner_id: list.owner_id,
+        members,
+        recipes,
+        aisles,
+        updated_at: list.updated_at.with_timezone(&Utc),
+    })
+}
+
+/// Marks the list as changed and pushes it to everyone sharing it.
+async fn publish(state: &AppState, id: Uuid, lang_code: &str) -> Result<ShoppingListDto, Error> {
+    shopping_list_repository::touch(&state.db, id).await?;
+    let list = shopping_list_repository::find_by_id(&state.db, id).await?;
+    let user_ids = shopping_list_repository::find_user_ids(&state.db, &list).await?;
+    let dto = build(&state.db, list, lang_code).await?;
+
+    broadcast(state, &user_ids, ShoppingListEventKind::ShoppingListUpdated, id, Some(dto.clone())).await;
+    Ok(dto)
+}
+
+async fn broadcast(
+    state: &AppState,
+    user_ids: &[Uuid],
+    event: ShoppingListEventKind,
+    id: Uuid,
+    shopping_list: Option<ShoppingListDto>,
+) {
+    let payload = serde_json::to_string(&ShoppingListEventDto {
+        event,
+        shopping_list_id: id,
+        shopping_list,
+    })
+    .unwrap_or_default();
+
+    for user_id in user_ids {
+        state.notification_hub.broadcast_to_user(*user_id, payload.clone()).await;
+    }
+}
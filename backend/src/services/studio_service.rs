@@ -4,7 +4,7 @@ use sea_orm::DatabaseConnection;
 use serde_json::json;
 use uuid::Uuid;
 use crate::errors::Error;
-use crate::repositories::recipe_repository;
+use crate::repositories::{collaborator_repository, recipe_repository};
 use crate::services::recipe_service;
 
 pub async fn get_stats(
@@ -27,12 +27,15 @@ pub async fn get_recipe_telemetry(
     user_id: Uuid,
 ) -> Result<RecipeAnalytics, Error> {
     let recipe = recipe_repository::find_by_id(db, recipe_id).await?;
-    if recipe.author_id != Some(user_id) {
+    if recipe.author_id != Some(user_id)
+        && collaborator_repository::find_role(db, recipe_id, user_id).await?.is_none()
+    {
         return Err(Error::Forbidden(json!({"message": "Unauthorized"})));
     }
 
     let total_views = recipe_repository::get_total_views(db, recipe_id).await?;
     let views_7d = recipe_repository::get_views_last_7_days(db, recipe_id).await?;
+    let print_count = recipe_repository::get_total_prints(db, recipe_id).await?;
 
     let (steps, ingredients) = recipe_repository::get_recipe_counts(db, recipe_id)
         .await?
@@ -51,7 +54,7 @@ pub async fn get_recipe_telemetry(
         total_views,
         views_7d,
         avg_session_duration: "02:45".to_string(),
-        print_count: 12,
+        print_count: print_count as i32,
         share_count: 5,
         last_modified: DateTime::from(recipe.updated_at.unwrap_or_default()),
         health_score,
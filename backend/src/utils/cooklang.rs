@@ -0,0 +1,409 @@
+use crate::utils::json_ld;
+use regex::Regex;
+use std::sync::LazyLock;
+
+static BLOCK_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)\[-.*?-\]").unwrap());
+
+/// A number and the unit right after it: "15 min", "1h", "2,5 heures".
+static DURATION_PART: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d+(?:[.,]\d+)?)\s*([a-zA-Zé]*)").unwrap());
+
+/// A Cooklang document: metadata, then sections of steps. Ingredients, cookware and
+/// timers stay inline in the step they appear in.
+#[derive(Debug, Clone, Default)]
+pub struct CooklangRecipe {
+    pub metadata: Vec<(String, String)>,
+    pub notes: Vec<String>,
+    pub sections: Vec<CooklangSection>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CooklangSection {
+    pub name: Option<String>,
+    pub steps: Vec<CooklangStep>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CooklangStep {
+    pub items: Vec<StepItem>,
+}
+
+#[derive(Debug, Clone)]
+pub enum StepItem {
+    Text(String),
+    Ingredient(CooklangIngredient),
+    Cookware(String),
+    Timer { quantity: String, unit: String },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CooklangIngredient {
+    pub name: String,
+    pub quantity: String,
+    pub unit: String,
+    pub note: Option<String>,
+}
+
+impl CooklangRecipe {
+    pub fn meta(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// `tags: [a, b]`, `tags: a, b` or a YAML list in the front matter.
+    pub fn tags(&self) -> Vec<String> {
+        self.meta("tags")
+            .map(|v| {
+                v.trim_matches(|c| c == '[' || c == ']')
+                    .split(',')
+                    .map(|t| t.trim().trim_matches('"').to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl CooklangStep {
+    pub fn ingredients(&self) -> impl Iterator<Item = &CooklangIngredient> {
+        self.items.iter().filter_map(|item| match item {
+            StepItem::Ingredient(i) => Some(i),
+            _ => None,
+        })
+    }
+
+    /// A paragraph holding nothing but ingredients declares an ingredient group
+    /// rather than an instruction.
+    pub fn is_ingredient_list(&self) -> bool {
+        self.ingredients().next().is_some()
+            && self.items.iter().all(|item| match item {
+                StepItem::Ingredient(_) => true,
+                StepItem::Text(t) => t.chars().all(|c| c.is_whitespace() || c.is_ascii_punctuation()),
+                _ => false,
+            })
+    }
+
+    /// The instruction as plain text. A timer closing the step only sets its duration.
+    pub fn text(&self) -> String {
+        let last_timer = self.items.iter().rposition(|item| !matches!(item, StepItem::Text(t) if t.trim().is_empty()))
+            .filter(|i| matches!(self.items[*i], StepItem::Timer { .. }));
+
+        let text: String = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| match item {
+                StepItem::Text(t) => t.clone(),
+                StepItem::Ingredient(ingredient) => ingredient.name.clone(),
+                StepItem::Cookware(name) => name.clone(),
+                StepItem::Timer { .. } if Some(i) == last_timer => String::new(),
+                StepItem::Timer { quantity, unit } => format!("{} {}", quantity, unit).trim().to_string(),
+            })
+            .collect();
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Total of the step's timers, in minutes.
+    pub fn duration_minutes(&self) -> Option<i32> {
+        let minutes: Vec<f64> = self
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                StepItem::Timer { quantity, unit } => {
+                    parse_quantity(quantity).map(|q| q * minutes_per(unit))
+                }
+                _ => None,
+            })
+            .collect();
+        (!minutes.is_empty()).then(|| minutes.iter().sum::<f64>().round() as i32)
+    }
+}
+
+pub fn parse(input: &str) -> CooklangRecipe {
+    let input = BLOCK_COMMENT.replace_all(input, "");
+
+    let mut recipe = CooklangRecipe::default();
+    let mut lines = input.lines().peekable();
+
+    if lines.peek().map(|l| l.trim() == "---").unwrap_or(false) {
+        lines.next();
+        let mut list_key: Option<String> = None;
+        for line in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+            if let (Some(key), Some(item)) = (&list_key, line.trim().strip_prefix("- ")) {
+                push_list_item(&mut recipe.metadata, key, item.trim());
+                continue;
+            }
+            if let Some((key, value)) = line.split_once(':') {
+                let (key, value) = (key.trim().to_lowercase(), value.trim().trim_matches('"'));
+                list_key = value.is_empty().then(|| key.clone());
+                recipe.metadata.push((key, value.to_string()));
+            }
+        }
+    }
+
+    let mut section = CooklangSection::default();
+    let mut paragraph = String::new();
+    for line in lines {
+        let line = strip_line_comment(line);
+        let trimmed = line.trim();
+
+        if let Some(meta) = trimmed.strip_prefix(">>") {
+            if let Some((key, value)) = meta.split_once(':') {
+                recipe.metadata.push((key.trim().to_lowercase(), value.trim().to_string()));
+            }
+        } else if let Some(note) = trimmed.strip_prefix('>') {
+            recipe.notes.push(note.trim().to_string());
+        } else if trimmed.starts_with('=') {
+            flush_paragraph(&mut paragraph, &mut section);
+            if !section.steps.is_empty() || section.name.is_some() {
+                recipe.sections.push(std::mem::take(&mut section));
+            }
+            let name = trimmed.trim_matches('=').trim();
+            section.name = (!name.is_empty()).then(|| name.to_string());
+        } else if trimmed.is_empty() {
+            flush_paragraph(&mut paragraph, &mut section);
+        } else {
+            if !paragraph.is_empty() {
+                paragraph.push('\n');
+            }
+            paragraph.push_str(trimmed);
+        }
+    }
+    flush_paragraph(&mut paragraph, &mut section);
+    if !section.steps.is_empty() || section.name.is_some() {
+        recipe.sections.push(section);
+    }
+    recipe
+}
+
+fn push_list_item(metadata: &mut [(String, String)], key: &str, item: &str) {
+    if let Some((_, value)) = metadata.iter_mut().rev().find(|(k, _)| k == key) {
+        if !value.is_empty() {
+            value.push_str(", ");
+        }
+        value.push_str(item.trim_matches('"'));
+    }
+}
+
+/// Drops a `-- comment`, leaving `---` and hyphenated words alone.
+fn strip_line_comment(line: &str) -> &str {
+    let mut start = 0;
+    while let Some(pos) = line[start..].find("--") {
+        let at = start + pos;
+        let before_ok = at == 0 || line[..at].ends_with(char::is_whitespace);
+        let after_ok = !line[at + 2..].starts_with('-');
+        if before_ok && after_ok {
+            return &line[..at];
+        }
+        start = at + 2;
+    }
+    line
+}
+
+fn flush_paragraph(paragraph: &mut String, section: &mut CooklangSection) {
+    if !paragraph.trim().is_empty() {
+        section.steps.push(parse_step(paragraph));
+    }
+    paragraph.clear();
+}
+
+fn parse_step(text: &str) -> CooklangStep {
+    let chars: Vec<char> = text.chars().collect();
+    let mut items = Vec::new();
+    let mut buffer = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let starts_component = matches!(c, '@' | '#' | '~')
+            && (i == 0 || !chars[i - 1].is_alphanumeric())
+            && chars
+                .get(i + 1)
+                .map(|n| n.is_alphabetic() || *n == '{' || (c == '@' && "?&+-".contains(*n)))
+                .unwrap_or(false);
+
+        if !starts_component {
+            buffer.push(c);
+            i += 1;
+            continue;
+        }
+
+        let mut j = i + 1;
+        if c == '@' {
+            while j < chars.len() && "?&+-".contains(chars[j]) {
+                j += 1;
+            }
+        }
+        let (name, amount, next) = read_component(&chars, j);
+        if !buffer.is_empty() {
+            items.push(StepItem::Text(std::mem::take(&mut buffer)));
+        }
+        let (quantity, unit) = split_amount(amount.as_deref().unwrap_or(""));
+        i = next;
+
+        match c {
+            '@' => {
+                let mut note = None;
+                if amount.is_some()
+                    && chars.get(i) == Some(&'(')
+                    && let Some(close) = chars[i..].iter().position(|ch| *ch == ')')
+                {
+                    note = Some(chars[i + 1..i + close].iter().collect::<String>().trim().to_string())
+                        .filter(|n| !n.is_empty());
+                    i += close + 1;
+                }
+                items.push(StepItem::Ingredient(CooklangIngredient { name, quantity, unit, note }));
+            }
+            '#' => items.push(StepItem::Cookware(name)),
+            _ => items.push(StepItem::Timer { quantity, unit }),
+        }
+    }
+    if !buffer.is_empty() {
+        items.push(StepItem::Text(buffer));
+    }
+    CooklangStep { items }
+}
+
+/// Reads `name{amount}` from `start`. A multi-word name needs the braces and runs up to
+/// them, punctuation included; without them the name stops at the first character that
+/// is not part of a word.
+fn read_component(chars: &[char], start: usize) -> (String, Option<String>, usize) {
+    let brace = chars[start..]
+        .iter()
+        .position(|c| matches!(c, '{' | '@' | '#' | '~' | '\n'))
+        .map(|p| start + p)
+        .filter(|p| chars[*p] == '{');
+
+    if let Some(open) = brace
+        && let Some(close) = chars[open..].iter().position(|c| *c == '}')
+    {
+        let name: String = chars[start..open].iter().collect();
+        let amount: String = chars[open + 1..open + close].iter().collect();
+        return (name.trim().to_string(), Some(amount), open + close + 1);
+    }
+
+    let end = chars[start..]
+        .iter()
+        .position(|c| !(c.is_alphanumeric() || *c == '_' || *c == '-' || *c == '\''))
+        .map(|p| start + p)
+        .unwrap_or(chars.len());
+    (chars[start..end].iter().collect(), None, end)
+}
+
+fn split_amount(amount: &str) -> (String, String) {
+    let (quantity, unit) = amount.split_once('%').unwrap_or((amount, ""));
+    (
+        quantity.trim().trim_start_matches('=').trim().to_string(),
+        unit.trim().to_string(),
+    )
+}
+
+/// "2", "1.5", "1/2", "1 1/2" or a range like "2-3" (the lower bound).
+pub fn parse_quantity(quantity: &str) -> Option<f64> {
+    let quantity = quantity.trim().replace(',', ".");
+    let first = quantity.split('-').next()?.trim();
+    if first.is_empty() {
+        return None;
+    }
+    first.split_whitespace().try_fold(0.0, |total, part| {
+        let value = match part.split_once('/') {
+            Some((n, d)) => {
+                let d: f64 = d.parse().ok()?;
+                (d != 0.0).then_some(n.parse::<f64>().ok()? / d)?
+            }
+            None => part.parse::<f64>().ok()?,
+        };
+        Some(total + value)
+    })
+}
+
+fn minutes_per(unit: &str) -> f64 {
+    let unit = unit.trim().to_lowercase();
+    if unit.starts_with('h') {
+        60.0
+    } else if unit.starts_with('s') {
+        1.0 / 60.0
+    } else if unit.starts_with('d') || unit.starts_with('j') {
+        24.0 * 60.0
+    } else {
+        1.0
+    }
+}
+
+/// Reads "15 minutes", "1 hour 30 min", "1h30", "90" or an ISO-8601 duration.
+pub fn parse_minutes(value: &str) -> Option<i32> {
+    let value = value.trim();
+    if value.starts_with(['P', 'p']) {
+        return json_ld::parse_duration(value);
+    }
+    let mut total = 0.0;
+    let mut found = false;
+    let mut previous_hours = false;
+    for caps in DURATION_PART.captures_iter(value) {
+        let n: f64 = caps[1].replace(',', ".").parse().ok()?;
+        let unit = &caps[2];
+        total += if unit.is_empty() && previous_hours { n } else { n * minutes_per(unit) };
+        previous_hours = unit.to_lowercase().starts_with('h');
+        found = true;
+    }
+    found.then(|| total.round() as i32)
+}
+
+pub fn to_string(recipe: &CooklangRecipe) -> String {
+    let mut out = String::new();
+
+    if !recipe.metadata.is_empty() {
+        out.push_str("---\n");
+        for (key, value) in &recipe.metadata {
+            out.push_str(&format!("{}: {}\n", key, value.replace('\n', " ")));
+        }
+        out.push_str("---\n\n");
+    }
+    for note in &recipe.notes {
+        out.push_str(&format!("> {}\n", note));
+    }
+    if !recipe.notes.is_empty() {
+        out.push('\n');
+    }
+
+    for section in &recipe.sections {
+        if let Some(name) = &section.name {
+            out.push_str(&format!("== {} ==\n\n", name));
+        }
+        for step in &section.steps {
+            for item in &step.items {
+                match item {
+                    StepItem::Text(t) => out.push_str(t),
+                    StepItem::Ingredient(ingredient) => {
+                        out.push('@');
+                        out.push_str(&ingredient.name);
+                        out.push_str(&amount(&ingredient.quantity, &ingredient.unit));
+                        if let Some(note) = &ingredient.note {
+                            out.push_str(&format!("({})", note));
+                        }
+                    }
+                    StepItem::Cookware(name) => out.push_str(&format!("#{}{{}}", name)),
+                    StepItem::Timer { quantity, unit } => {
+                        out.push('~');
+                        out.push_str(&amount(quantity, unit));
+                    }
+                }
+            }
+            out.push_str("\n\n");
+        }
+    }
+    out.trim_end().to_string() + "\n"
+}
+
+fn amount(quantity: &str, unit: &str) -> String {
+    if unit.is_empty() {
+        format!("{{{}}}", quantity)
+    } else {
+        format!("{{{}%{}}}", quantity, unit)
+    }
+}
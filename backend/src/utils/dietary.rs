@@ -0,0 +1,153 @@
+use crate::dto::dietary_dto::{DietaryCategory, DietaryLabel};
+use std::collections::HashSet;
+
+/// English words putting a lexicon ingredient in a category, unless one of the
+/// exceptions is part of its name ("coconut milk" is not dairy). Used to seed the
+/// rules; they are then maintained by hand.
+const CATEGORY_KEYWORDS: [(DietaryCategory, &[&str], &[&str]); 8] = [
+    (
+        DietaryCategory::Nuts,
+        &[
+            "almond", "walnut", "hazelnut", "pecan", "cashew", "pistachio", "macadamia", "brazil nut", "pine nut",
+            "peanut", "chestnut", "praline", "marzipan", "nut", "gianduja",
+        ],
+        &["nutmeg", "butternut", "coconut", "water chestnut"],
+    ),
+    (
+        DietaryCategory::Gluten,
+        &[
+            "wheat", "flour", "bread", "breadcrumb", "breadcrumbs", "pasta", "spaghetti", "noodle", "macaroni",
+            "lasagna", "penne", "fettuccine", "linguine", "tagliatelle", "couscous", "semolina", "barley", "rye",
+            "bulgur", "farro", "spelt", "seitan", "cracker", "pastry", "tortilla", "pita", "baguette", "brioche",
+            "croissant", "panko", "biscuit", "cookie", "cake", "beer", "soy sauce", "dough",
+        ],
+        &[
+            "rice flour", "almond flour", "coconut flour", "corn flour", "cornflour", "chickpea flour",
+            "potato flour", "tapioca flour", "buckwheat", "corn tortilla", "rice noodle", "gluten-free",
+            "gluten free",
+        ],
+    ),
+    (
+        DietaryCategory::Dairy,
+        &[
+            "milk", "butter", "buttermilk", "cheese", "cream", "yogurt", "yoghurt", "ghee", "mozzarella", "parmesan",
+            "cheddar", "ricotta", "mascarpone", "feta", "brie", "camembert", "gruyere", "gruyère", "emmental",
+            "whey", "kefir", "crème fraîche", "creme fraiche",
+        ],
+        &[
+            "coconut milk", "almond milk", "soy milk", "oat milk", "rice milk", "peanut butter", "almond butter",
+            "nut butter", "cocoa butter", "coconut cream", "cream of tartar", "shea butter",
+        ],
+    ),
+    (
+        DietaryCategory::Eggs,
+        &["egg", "yolk", "mayonnaise", "meringue"],
+        &[],
+    ),
+    (
+        DietaryCategory::Meat,
+        &[
+            "beef", "pork", "chicken", "lamb", "veal", "turkey", "duck", "goose", "rabbit", "venison", "bacon",
+            "ham", "sausage", "prosciutto", "salami", "chorizo", "pancetta", "pepperoni", "steak", "mince", "meat",
+            "lard", "gelatin", "gelatine",
+        ],
+        &[],
+    ),
+    (
+        DietaryCategory::Fish,
+        &[
+            "fish", "salmon", "tuna", "cod", "trout", "anchovy", "anchovies", "sardine", "mackerel", "halibut",
+            "tilapia", "haddock", "herring", "sea bass",
+        ],
+        &[],
+    ),
+    (
+        DietaryCategory::Shellfish,
+        &[
+            "shrimp", "prawn", "crab", "lobster", "mussel", "clam", "oyster", "scallop", "squid", "octopus",
+            "crayfish", "langoustine",
+        ],
+        &[],
+    ),
+    (DietaryCategory::Honey, &["honey"], &["honeydew"]),
+];
+
+/// Categories whose absence makes a "-free" label.
+const FREE_LABELS: [(DietaryCategory, DietaryLabel); 4] = [
+    (DietaryCategory::Nuts, DietaryLabel::NutFree),
+    (DietaryCategory::Gluten, DietaryLabel::GlutenFree),
+    (DietaryCategory::Dairy, DietaryLabel::DairyFree),
+    (DietaryCategory::Eggs, DietaryLabel::EggFree),
+];
+
+const CONTAINS_LABELS: [(DietaryCategory, DietaryLabel); 7] = [
+    (DietaryCategory::Nuts, DietaryLabel::ContainsNuts),
+    (DietaryCategory::Gluten, DietaryLabel::ContainsGluten),
+    (DietaryCategory::Dairy, DietaryLabel::ContainsDairy),
+    (DietaryCategory::Eggs, DietaryLabel::ContainsEggs),
+    (DietaryCategory::Meat, DietaryLabel::ContainsMeat),
+    (DietaryCategory::Fish, DietaryLabel::ContainsFish),
+    (DietaryCategory::Shellfish, DietaryLabel::ContainsShellfish),
+];
+
+const ANIMAL_FLESH: [DietaryCategory; 3] = [DietaryCategory::Meat, DietaryCategory::Fish, DietaryCategory::Shellfish];
+const ANIMAL_PRODUCTS: [DietaryCategory; 3] = [DietaryCategory::Dairy, DietaryCategory::Eggs, DietaryCategory::Honey];
+
+/// One ingredient line: whether it resolved to a lexicon or catalog entry, and the
+/// categories the rules put it in.
+#[derive(Debug, Default)]
+pub struct LineCategories {
+    pub identified: bool,
+    pub categories: HashSet<DietaryCategory>,
+}
+
+/// "contains-…" labels come from any line. "-free", vegetarian and vegan are only
+/// claimed when every line could be identified, since an unknown ingredient may be
+/// the one an allergic reader has to avoid.
+pub fn derive_labels(lines: &[LineCategories]) -> Vec<DietaryLabel> {
+    let found: HashSet<DietaryCategory> = lines.iter().flat_map(|l| l.categories.iter().copied()).collect();
+
+    let mut labels: Vec<DietaryLabel> = CONTAINS_LABELS
+        .iter()
+        .filter(|(category, _)| found.contains(category))
+        .map(|(_, label)| *label)
+        .collect();
+
+    if !lines.is_empty() && lines.iter().all(|l| l.identified) {
+        labels.extend(
+            FREE_LABELS
+                .iter()
+                .filter(|(category, _)| !found.contains(category))
+                .map(|(_, label)| *label),
+        );
+        if !ANIMAL_FLESH.iter().any(|c| found.contains(c)) {
+            labels.push(DietaryLabel::Vegetarian);
+            if !ANIMAL_PRODUCTS.iter().any(|c| found.contains(c)) {
+                labels.push(DietaryLabel::Vegan);
+            }
+        }
+    }
+
+    labels.sort();
+    labels
+}
+
+/// Categories an ingredient falls in from its English name, matching whole words
+/// and their plural.
+pub fn keyword_categories(term_en: &str) -> Vec<DietaryCategory> {
+    let name = term_en.trim().to_lowercase();
+    let padded = format!(" {} ", name.replace(['-', ',', '(', ')'], " "));
+    let has_word = |word: &str| {
+        ["", "s", "es"]
+            .iter()
+            .any(|suffix| padded.contains(&format!(" {}{} ", word, suffix)))
+    };
+
+    CATEGORY_KEYWORDS
+        .iter()
+        .filter(|(_, keywords, exceptions)| {
+            keywords.iter().any(|k| has_word(k)) && !exceptions.iter().any(|e| name.contains(e))
+        })
+        .map(|(category, _, _)| *category)
+        .collect()
+}
@@ -81,8 +81,55 @@ pub fn send_password_reset(user: users::Model, token: &Uuid) -> Result<(), Error
     send_via_smtp(email)
 }
 
+pub fn send_household_invitation(email: &str, inviter: &str, household: &str, token: &Uuid) -> Result<(), Error> {
+    let frontend_origin = get_frontend_origin()?;
+    let join_url = format!("{}/households/join?token={}", frontend_origin, token);
+    let (html_inviter, html_household) = (escape_html(inviter), escape_html(household));
+
+    let html_body = format!(
+        "<html><body style='font-family: sans-serif; color: #18181b;'>\
+        <h3>Hello,</h3>\
+        <p><b>{}</b> invited you to share recipes in the <b>{}</b> household on <b>HomeRecipes</b>.</p>\
+        <div style='margin: 24px 0;'>\
+            <a href='{}' style='background-color: #2563eb; color: white; padding: 12px 24px; text-decoration: none; border-radius: 8px; font-weight: bold; display: inline-block;'>Join Household</a>\
+        </div>\
+        <p>This invitation will expire in 7 days. Sign in or create an account with this email address to accept it.</p>\
+        <p style='font-size: 12px; color: #71717a;'>If the button doesn't work, copy and paste this link:<br/>{}</p>\
+        <p>Thanks,<br/>The HomeRecipes Team</p>\
+        </body></html>",
+        html_inviter, html_household, join_url, join_url
+    );
+
+    let text_body = format!(
+        "Hello,\n\n{} invited you to share recipes in the {} household on HomeRecipes.\n\nJoin here: {}\n\nThis invitation expires in 7 days.",
+        inviter, household, join_url
+    );
+
+    let message = Message::builder()
+        .from(get_mailbox("MAIL_FROM_ADDRESS", "no-reply@homerecipes.com")?)
+        .to(parse_recipient(email)?)
+        .subject("HomeRecipes - Household Invitation")
+        .multipart(
+            MultiPart::alternative()
+                .singlepart(SinglePart::plain(text_body))
+                .singlepart(SinglePart::html(html_body)),
+        )
+        .map_err(|e| log_email_err("Message Building", e.to_string(), email))?;
+
+    send_via_smtp(message)
+}
+
 // --- Internal Helpers ---
 
+/// Names chosen by users are shown as text in the html part, never as markup.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
 fn get_frontend_origin() -> Result<String, Error> {
     env::var("FRONTEND_ORIGIN").map_err(|_| {
         log_email_err(
@@ -1,7 +1,22 @@
+use std::env;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 
+/// Folder the files under `/assets` are served from, `FILE_URL` or `./assets`.
+pub fn asset_root() -> PathBuf {
+    PathBuf::from(env::var("FILE_URL").unwrap_or_else(|_| "./assets".to_string()))
+}
+
+/// File at `path` under the asset root. `None` for an absolute path or one with empty
+/// or `..` segments, which could point outside of it.
+pub fn asset_path(path: &str) -> Option<PathBuf> {
+    if path.split('/').any(|part| part == ".." || part.is_empty()) {
+        return None;
+    }
+    Some(asset_root().join(path))
+}
+
 pub fn move_file_from_tmp(src_path: &str, target_dir: &str) -> Result<String, io::Error> {
     let sanitized_src = src_path.strip_prefix("/").unwrap_or(src_path);
     let path = Path::new(sanitized_src);
@@ -22,4 +37,29 @@ pub fn move_file_from_tmp(src_path: &str, target_dir: &str) -> Result<String, io
     fs::rename(sanitized_src, &dest_path)?;
     
     Ok(format!("/{}", dest_path.to_string_lossy()))
-}
\ No newline at end of file
+}
+/// Copies a stored image into `target_dir` under a new name, so that each copy can be
+/// replaced on its own. Urls outside `assets/` (remote images) and missing files are
+/// returned untouched.
+pub fn copy_stored_file(src_path: &str, target_dir: &str) -> Result<String, io::Error> {
+    let sanitized_src = src_path.strip_prefix("/").unwrap_or(src_path);
+    let path = Path::new(sanitized_src);
+
+    let is_stored = sanitized_src.starts_with("assets/") && !sanitized_src.split('/').any(|part| part == "..");
+    if !is_stored || !path.is_file() {
+        return Ok(src_path.to_string());
+    }
+
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("png");
+    let file_name = format!(
+        "recipe_{}_{}.{}",
+        uuid::Uuid::new_v4(),
+        chrono::Utc::now().timestamp(),
+        extension
+    );
+    let dest_path = PathBuf::from(target_dir).join(file_name);
+
+    fs::copy(path, &dest_path)?;
+
+    Ok(format!("/{}", dest_path.to_string_lossy()))
+}
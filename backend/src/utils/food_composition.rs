@@ -0,0 +1,195 @@
+use crate::dto::nutrition_dto::FoodTableFormat;
+use crate::errors::Error;
+use serde_json::json;
+
+/// One food of a composition table, nutrients per 100 g.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FoodRecord {
+    pub source_code: String,
+    pub name_en: Option<String>,
+    pub name_fr: Option<String>,
+    pub energy_kcal: Option<f64>,
+    pub protein_g: Option<f64>,
+    pub fat_g: Option<f64>,
+    pub carbohydrates_g: Option<f64>,
+    pub fiber_g: Option<f64>,
+    pub sodium_mg: Option<f64>,
+}
+
+#[derive(Debug, Default)]
+pub struct FoodTable {
+    pub records: Vec<FoodRecord>,
+    pub skipped: u64,
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    Code,
+    NameEn,
+    NameFr,
+    EnergyKcal,
+    Protein,
+    Fat,
+    Carbohydrates,
+    Fiber,
+    Sodium,
+}
+
+/// Header spellings of each column: a header matches when it contains every fragment
+/// of one of the alternatives (case insensitive). The first matching header wins, which
+/// picks the regulatory energy and the Jones-factor protein in CIQUAL.
+type ColumnSpec = (Field, &'static [&'static [&'static str]]);
+
+const CIQUAL_COLUMNS: [ColumnSpec; 9] = [
+    (Field::Code, &[&["alim_code"]]),
+    (Field::NameEn, &[&["alim_nom_eng"]]),
+    (Field::NameFr, &[&["alim_nom_fr"]]),
+    (Field::EnergyKcal, &[&["energie", "kcal"]]),
+    (Field::Protein, &[&["protéines"]]),
+    (Field::Fat, &[&["lipides"]]),
+    (Field::Carbohydrates, &[&["glucides"]]),
+    (Field::Fiber, &[&["fibres alimentaires"]]),
+    // Not "Sel chlorure de sodium (g/100 g)", which comes first.
+    (Field::Sodium, &[&["sodium", "mg"]]),
+];
+
+const USDA_COLUMNS: [ColumnSpec; 9] = [
+    (Field::Code, &[&["ndb_no"], &["fdc_id"]]),
+    (Field::NameEn, &[&["shrt_desc"], &["description"]]),
+    (Field::NameFr, &[]),
+    (Field::EnergyKcal, &[&["energ_kcal"], &["energy", "kcal"]]),
+    (Field::Protein, &[&["protein"]]),
+    (Field::Fat, &[&["lipid_tot"], &["total lipid"]]),
+    (Field::Carbohydrates, &[&["carbohydrt"], &["carbohydrate"]]),
+    (Field::Fiber, &[&["fiber"]]),
+    (Field::Sodium, &[&["sodium"]]),
+];
+
+const CUSTOM_COLUMNS: [ColumnSpec; 9] = [
+    (Field::Code, &[&["code"]]),
+    (Field::NameEn, &[&["name_en"]]),
+    (Field::NameFr, &[&["name_fr"]]),
+    (Field::EnergyKcal, &[&["kcal"]]),
+    (Field::Protein, &[&["protein"]]),
+    (Field::Fat, &[&["fat"]]),
+    (Field::Carbohydrates, &[&["carbohydrate"]]),
+    (Field::Fiber, &[&["fiber"], &["fibre"]]),
+    (Field::Sodium, &[&["sodium"]]),
+];
+
+/// Reads a composition table. The delimiter is guessed from the header line, and files
+/// that are not UTF-8 (CIQUAL ships in Windows-1252) are read as Latin-1.
+pub fn parse(bytes: &[u8], format: FoodTableFormat) -> Result<FoodTable, Error> {
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|b| *b as char).collect(),
+    };
+    let text = text.trim_start_matches('\u{feff}');
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter(text.lines().next().unwrap_or("")))
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| invalid_file(format, e.to_string()))?
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+
+    let spec = match format {
+        FoodTableFormat::Ciqual => &CIQUAL_COLUMNS,
+        FoodTableFormat::Usda => &USDA_COLUMNS,
+        FoodTableFormat::Custom => &CUSTOM_COLUMNS,
+    };
+    let columns: Vec<(Field, Option<usize>)> = spec
+        .iter()
+        .map(|(field, alternatives)| (*field, find_column(&headers, alternatives)))
+        .collect();
+    let column = |wanted: fn(&Field) -> bool| columns.iter().find(|(f, _)| wanted(f)).and_then(|(_, i)| *i);
+
+    if column(|f| matches!(f, Field::Code)).is_none()
+        || column(|f| matches!(f, Field::EnergyKcal)).is_none()
+        || (column(|f| matches!(f, Field::NameEn)).is_none() && column(|f| matches!(f, Field::NameFr)).is_none())
+    {
+        return Err(Error::BadRequest(json!({
+            "message": "The file is missing the code, name or energy column",
+            "operation": "import_foods",
+            "format": format.as_str(),
+            "headers": headers,
+            "stage": "header_validation"
+        })));
+    }
+
+    let mut table = FoodTable::default();
+    for row in reader.records() {
+        let row = row.map_err(|e| invalid_file(format, e.to_string()))?;
+        let mut record = FoodRecord::default();
+        for (field, index) in &columns {
+            let Some(value) = index.and_then(|i| row.get(i)).map(str::trim) else {
+                continue;
+            };
+            match field {
+                Field::Code => record.source_code = value.to_string(),
+                Field::NameEn => record.name_en = Some(value.to_string()).filter(|v| !v.is_empty()),
+                Field::NameFr => record.name_fr = Some(value.to_string()).filter(|v| !v.is_empty()),
+                Field::EnergyKcal => record.energy_kcal = parse_amount(value),
+                Field::Protein => record.protein_g = parse_amount(value),
+                Field::Fat => record.fat_g = parse_amount(value),
+                Field::Carbohydrates => record.carbohydrates_g = parse_amount(value),
+                Field::Fiber => record.fiber_g = parse_amount(value),
+                Field::Sodium => record.sodium_mg = parse_amount(value),
+            }
+        }
+
+        if record.source_code.is_empty() || (record.name_en.is_none() && record.name_fr.is_none()) {
+            table.skipped += 1;
+        } else {
+            table.records.push(record);
+        }
+    }
+    Ok(table)
+}
+
+/// Reads an amount as written in composition tables: decimal commas, "traces" for
+/// next to nothing, "< 0,5" for values under the detection limit (the limit is kept)
+/// and "-" when the nutrient was not measured.
+pub fn parse_amount(value: &str) -> Option<f64> {
+    let value = value.trim().to_lowercase();
+    if value.starts_with("trace") {
+        return Some(0.0);
+    }
+    value
+        .trim_start_matches('<')
+        .trim()
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v >= 0.0)
+}
+
+fn find_column(headers: &[String], alternatives: &[&[&str]]) -> Option<usize> {
+    alternatives.iter().find_map(|fragments| {
+        headers
+            .iter()
+            .position(|h| fragments.iter().all(|fragment| h.contains(fragment)))
+    })
+}
+
+fn delimiter(header_line: &str) -> u8 {
+    [b';', b'\t', b',']
+        .into_iter()
+        .max_by_key(|d| header_line.matches(*d as char).count())
+        .unwrap_or(b',')
+}
+
+fn invalid_file(format: FoodTableFormat, error: String) -> Error {
+    Error::BadRequest(json!({
+        "message": "Invalid food composition file",
+        "operation": "import_foods",
+        "format": format.as_str(),
+        "error": error,
+        "stage": "csv_parse"
+    }))
+}
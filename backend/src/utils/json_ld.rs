@@ -0,0 +1,286 @@
+use crate::dto::ingredient_dto::IngredientRecipeViewDto;
+use crate::dto::recipe_dto::RecipeViewDto;
+use serde_json::{json, Value as JsonValue};
+
+/// Renders a recipe view as a schema.org `Recipe` node. `origin` is prepended to
+/// relative image paths and used for the canonical url.
+pub fn to_recipe_node(recipe: &RecipeViewDto, lang: &str, origin: &str) -> JsonValue {
+    let origin = origin.trim_end_matches('/');
+
+    let ingredients: Vec<String> = recipe
+        .ingredient_groups
+        .iter()
+        .flat_map(|g| g.ingredients.iter())
+        .map(ingredient_line)
+        .collect();
+
+    let sections: Vec<JsonValue> = recipe
+        .step_groups
+        .iter()
+        .map(|group| {
+            let steps: Vec<JsonValue> = group
+                .steps
+                .iter()
+                .enumerate()
+                .map(|(i, step)| {
+                    json!({
+                        "@type": "HowToStep",
+                        "position": i + 1,
+                        "text": step.instruction,
+                    })
+                })
+                .collect();
+            json!({
+                "@type": "HowToSection",
+                "name": group.title,
+                "itemListElement": steps,
+            })
+        })
+        .collect();
+    let instructions = match sections.as_slice() {
+        [single] => single["itemListElement"].clone(),
+        _ => JsonValue::Array(sections),
+    };
+
+    let mut node = json!({
+        "@context": "https://schema.org",
+        "@type": "Recipe",
+        "@id": format!("{}/recipes/{}", origin, recipe.id),
+        "url": format!("{}/recipes/{}", origin, recipe.id),
+        "name": recipe.title,
+        "description": recipe.description,
+        "inLanguage": lang,
+        "recipeYield": recipe.servings.to_string(),
+        "prepTime": format_duration(recipe.prep_time_minutes),
+        "cookTime": format_duration(recipe.cook_time_minutes),
+        "totalTime": format_duration(recipe.prep_time_minutes + recipe.cook_time_minutes),
+        "keywords": recipe.tags.iter().map(|t| t.name.clone()).collect::<Vec<_>>().join(", "),
+        "recipeIngredient": ingredients,
+        "recipeInstructions": instructions,
+    });
+
+    if !recipe.image_url.is_empty() {
+        node["image"] = json!(absolute_url(&recipe.image_url, origin));
+    }
+    if let Some(author) = &recipe.author {
+        node["author"] = json!({ "@type": "Person", "name": author });
+    }
+    node
+}
+
+/// "2 ½ cup flour (sifted)"; units that carry no quantity ("to taste") trail the name.
+pub fn ingredient_line(ingredient: &IngredientRecipeViewDto) -> String {
+    let quantity = ingredient
+        .display_quantity
+        .clone()
+        .unwrap_or_else(|| ingredient.quantity.normalize().to_string());
+
+    let mut line = if ingredient.unit.conversion_factor > 0.0 {
+        format!("{} {} {}", quantity, ingredient.unit.symbol, ingredient.name)
+    } else {
+        format!("{} {}", ingredient.name, ingredient.unit.symbol)
+    };
+    if let Some(note) = ingredient.note.as_deref().filter(|n| !n.is_empty()) {
+        line.push_str(&format!(" ({})", note));
+    }
+    line
+}
+
+fn absolute_url(path: &str, origin: &str) -> String {
+    if path.starts_with("http://") || path.starts_with("https://") {
+        path.to_string()
+    } else {
+        format!("{}/{}", origin, path.trim_start_matches('/'))
+    }
+}
+
+/// Minutes as an ISO-8601 duration (90 → "PT1H30M").
+pub fn format_duration(minutes: i32) -> String {
+    let minutes = minutes.max(0);
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("PT{}M", m),
+        (h, 0) => format!("PT{}H", h),
+        (h, m) => format!("PT{}H{}M", h, m),
+    }
+}
+
+/// Parses an ISO-8601 duration ("PT1H30M", "P1DT2H", "PT45S") into whole minutes.
+pub fn parse_duration(value: &str) -> Option<i32> {
+    let value = value.trim().to_uppercase();
+    let rest = value.strip_prefix('P')?;
+
+    let mut minutes = 0.0;
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in rest.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' | '.' | ',' => number.push(if c == ',' { '.' } else { c }),
+            unit => {
+                let n: f64 = number.parse().ok()?;
+                number.clear();
+                minutes += match (unit, in_time) {
+                    ('W', false) => n * 7.0 * 24.0 * 60.0,
+                    ('D', false) => n * 24.0 * 60.0,
+                    ('H', true) => n * 60.0,
+                    ('M', true) => n,
+                    ('S', true) => n / 60.0,
+                    _ => return None,
+                };
+            }
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    Some(minutes.round() as i32)
+}
+
+/// Reads `recipeYield`, which may be a number, "4 servings", "Serves 4-6" or a list
+/// of any of these. The first number found wins.
+pub fn parse_yield(value: &JsonValue) -> Option<i32> {
+    match value {
+        JsonValue::Number(n) => n.as_f64().map(|n| n.round() as i32),
+        JsonValue::String(s) => s
+            .split(|c: char| !c.is_ascii_digit())
+            .find(|part| !part.is_empty())
+            .and_then(|part| part.parse().ok()),
+        JsonValue::Array(items) => items.iter().find_map(parse_yield),
+        JsonValue::Object(o) => o.get("value").and_then(parse_yield),
+        _ => None,
+    }
+}
+
+/// Finds the `Recipe` node in a JSON-LD document, whether it is the root, part of
+/// a top-level array or nested in `@graph`.
+pub fn find_recipe_node(document: &JsonValue) -> Option<&JsonValue> {
+    match document {
+        JsonValue::Array(items) => items.iter().find_map(find_recipe_node),
+        JsonValue::Object(o) => {
+            if has_type(document, "Recipe") {
+                return Some(document);
+            }
+            o.get("@graph").and_then(find_recipe_node)
+        }
+        _ => None,
+    }
+}
+
+fn has_type(node: &JsonValue, expected: &str) -> bool {
+    let matches = |t: &JsonValue| {
+        t.as_str()
+            .map(|s| s.rsplit(['/', ':']).next() == Some(expected))
+            .unwrap_or(false)
+    };
+    match node.get("@type") {
+        Some(JsonValue::Array(types)) => types.iter().any(matches),
+        Some(t) => matches(t),
+        None => false,
+    }
+}
+
+/// Plain text of a property that may be a string, a list of strings or an object
+/// with a `name`/`text`.
+pub fn text(value: Option<&JsonValue>) -> Option<String> {
+    let text = match value? {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Array(items) => return items.iter().find_map(|i| text(Some(i))),
+        JsonValue::Object(o) => return text(o.get("text").or_else(|| o.get("name"))),
+        JsonValue::Number(n) => n.to_string(),
+        _ => return None,
+    };
+    let text = decode_entities(text.trim());
+    (!text.is_empty()).then_some(text)
+}
+
+/// Image of the recipe: a url, an `ImageObject` or a list of either.
+pub fn image_url(value: Option<&JsonValue>) -> Option<String> {
+    match value? {
+        JsonValue::String(s) => Some(s.clone()),
+        JsonValue::Array(items) => items.iter().find_map(|i| image_url(Some(i))),
+        JsonValue::Object(o) => image_url(o.get("url").or_else(|| o.get("contentUrl"))),
+        _ => None,
+    }
+}
+
+/// `recipeIngredient` (or the legacy `ingredients`) as one string per line.
+pub fn ingredient_lines(recipe: &JsonValue) -> Vec<String> {
+    let value = recipe.get("recipeIngredient").or_else(|| recipe.get("ingredients"));
+    match value {
+        Some(JsonValue::Array(items)) => items.iter().filter_map(|i| text(Some(i))).collect(),
+        Some(JsonValue::String(s)) => s
+            .lines()
+            .map(|l| decode_entities(l.trim()))
+            .filter(|l| !l.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `recipeInstructions` as named sections of steps. Loose steps are collected in
+/// an unnamed section; a single string is split on line breaks.
+pub fn instruction_sections(recipe: &JsonValue) -> Vec<(Option<String>, Vec<String>)> {
+    let mut sections: Vec<(Option<String>, Vec<String>)> = Vec::new();
+    let mut loose: Vec<String> = Vec::new();
+
+    match recipe.get("recipeInstructions") {
+        Some(JsonValue::String(s)) => loose.extend(split_lines(s)),
+        Some(JsonValue::Array(items)) => {
+            for item in items {
+                if has_type(item, "HowToSection") {
+                    if !loose.is_empty() {
+                        sections.push((None, std::mem::take(&mut loose)));
+                    }
+                    let steps = match item.get("itemListElement") {
+                        Some(JsonValue::Array(steps)) => steps.iter().flat_map(step_texts).collect(),
+                        Some(step) => step_texts(step),
+                        None => Vec::new(),
+                    };
+                    sections.push((text(item.get("name")), steps));
+                } else {
+                    loose.extend(step_texts(item));
+                }
+            }
+        }
+        Some(item @ JsonValue::Object(_)) => loose.extend(step_texts(item)),
+        _ => {}
+    }
+
+    if !loose.is_empty() {
+        sections.push((None, loose));
+    }
+    sections
+}
+
+/// A `HowToStep`, a `HowToDirection`/`HowToTip` or a bare string.
+fn step_texts(step: &JsonValue) -> Vec<String> {
+    match step {
+        JsonValue::String(s) => split_lines(s),
+        JsonValue::Object(o) => match o.get("itemListElement") {
+            Some(JsonValue::Array(items)) => items.iter().flat_map(step_texts).collect(),
+            _ => text(o.get("text").or_else(|| o.get("name"))).into_iter().collect(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+fn split_lines(value: &str) -> Vec<String> {
+    value
+        .lines()
+        .map(|l| decode_entities(l.trim()))
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+/// Blogs often double-encode their JSON-LD; undo the usual suspects.
+fn decode_entities(value: &str) -> String {
+    value
+        .replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#039;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+}
@@ -134,6 +134,7 @@ pub async fn process_ocr_to_dto(
                     note: None,
                 }
             ],
+            canonical_ingredient_id: None,
         }
     }).collect();
 
@@ -8,5 +8,16 @@ pub mod file_upload;
 pub mod llm_prompt;
 pub mod schema;
 pub mod notification_hub;
+pub mod recipe_diff;
+pub mod serving_scaler;
+pub mod json_ld;
+pub mod cooklang;
+pub mod pdf;
+pub mod pagination;
+pub mod food_composition;
+pub mod dietary;
+pub mod shopping_list;
+pub mod icalendar;
+pub mod recipe_text;
 
 pub use {self::hasher::*};
\ No newline at end of file
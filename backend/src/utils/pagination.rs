@@ -0,0 +1,44 @@
+use crate::errors::Error;
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+/// Position of the last recipe of a page in the listing order
+/// (sort key, then `created_at, id`), handed to clients as an opaque string.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RecipeCursor {
+    /// Value of the sort key, unset when sorting by creation date.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<SortValue>,
+    pub created_at: DateTime<FixedOffset>,
+    pub id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum SortValue {
+    Number(f64),
+    Text(String),
+}
+
+impl RecipeCursor {
+    pub fn encode(&self) -> String {
+        let raw = serde_json::to_vec(self).unwrap_or_default();
+        general_purpose::URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, Error> {
+        general_purpose::URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|raw| serde_json::from_slice(&raw).ok())
+            .ok_or_else(|| Error::BadRequest(json!({
+                "message": "Invalid pagination cursor",
+                "operation": "decode_cursor",
+                "cursor": cursor,
+                "stage": "validation"
+            })))
+    }
+}
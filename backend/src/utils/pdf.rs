@@ -0,0 +1,569 @@
+use crate::dto::pdf_dto::PdfLayout;
+use crate::dto::recipe_dto::RecipeViewDto;
+use crate::utils::json_ld;
+use pdf_writer::types::{ActionType, AnnotationType};
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use qrcode::{Color, QrCode};
+use std::collections::VecDeque;
+
+// Only the standard Helvetica faces are used, so no font data is embedded. Text
+// is written in WinAnsiEncoding, which covers French.
+const REGULAR: Name<'static> = Name(b"F1");
+const BOLD: Name<'static> = Name(b"F2");
+const LEADING: f32 = 1.35;
+
+/// Glyph widths of Helvetica for ASCII 32..=126, in thousandths of the font size.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Page geometry and type sizes, in points.
+#[derive(Clone, Copy)]
+struct Style {
+    width: f32,
+    height: f32,
+    margin: f32,
+    title: f32,
+    heading: f32,
+    body: f32,
+    small: f32,
+    qr: f32,
+}
+
+impl Style {
+    fn for_layout(layout: PdfLayout) -> Self {
+        match layout {
+            PdfLayout::Page => Style {
+                width: 612.0,
+                height: 792.0,
+                margin: 54.0,
+                title: 22.0,
+                heading: 13.0,
+                body: 10.5,
+                small: 8.5,
+                qr: 72.0,
+            },
+            PdfLayout::Card => Style {
+                width: 432.0,
+                height: 288.0,
+                margin: 18.0,
+                title: 13.0,
+                heading: 9.0,
+                body: 7.5,
+                small: 6.0,
+                qr: 44.0,
+            },
+        }
+    }
+
+    fn content_width(&self) -> f32 {
+        self.width - 2.0 * self.margin
+    }
+}
+
+enum Link {
+    Uri(String),
+    /// Index of the target page in the final document.
+    Page(usize),
+}
+
+struct PageOut {
+    content: Content,
+    links: Vec<(Rect, Link)>,
+}
+
+/// Flows text from the top of the page down, starting a new page when full.
+struct Layout {
+    style: Style,
+    pages: Vec<PageOut>,
+    y: f32,
+    /// Bottom edge and width of the area kept clear for the QR code.
+    reserved: Option<(f32, f32)>,
+}
+
+impl Layout {
+    fn new(style: Style) -> Self {
+        let mut layout = Layout {
+            style,
+            pages: Vec::new(),
+            y: 0.0,
+            reserved: None,
+        };
+        layout.new_page();
+        layout
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(PageOut {
+            content: Content::new(),
+            links: Vec::new(),
+        });
+        self.y = self.style.height - self.style.margin;
+        self.reserved = None;
+    }
+
+    fn page(&mut self) -> &mut PageOut {
+        self.pages.last_mut().expect("a layout always has a page")
+    }
+
+    fn ensure(&mut self, height: f32) {
+        if self.y - height < self.style.margin {
+            self.new_page();
+        }
+    }
+
+    fn space(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn width_at(&self) -> f32 {
+        match self.reserved {
+            Some((bottom, width)) if self.y > bottom => self.style.content_width() - width,
+            _ => self.style.content_width(),
+        }
+    }
+
+    fn draw(&mut self, x: f32, baseline: f32, text: &str, font: Name<'static>, size: f32) {
+        let content = &mut self.page().content;
+        content.begin_text();
+        content.set_font(font, size);
+        content.next_line(x, baseline);
+        content.show(Str(&encode(text)));
+        content.end_text();
+    }
+
+    /// Wraps `text` to the available width. `marker` ("•", "3.") hangs in front of
+    /// the first line and every line is indented past it.
+    fn paragraph(&mut self, text: &str, marker: &str, font: Name<'static>, size: f32) {
+        let leading = size * LEADING;
+        let marker_width = if marker.is_empty() { 0.0 } else { text_width(marker, font, size) + size * 0.4 };
+        let mut words: VecDeque<String> = text.split_whitespace().map(str::to_string).collect();
+        let mut first = true;
+
+        while !words.is_empty() {
+            self.ensure(leading);
+            let width = self.width_at() - marker_width;
+            let line = take_line(&mut words, font, size, width);
+            let baseline = self.y - size;
+            if first && !marker.is_empty() {
+                self.draw(self.style.margin, baseline, marker, font, size);
+            }
+            self.draw(self.style.margin + marker_width, baseline, &line, font, size);
+            self.y -= leading;
+            first = false;
+        }
+    }
+
+    fn heading(&mut self, text: &str) {
+        let size = self.style.heading;
+        // Keep a heading together with at least two lines of what follows.
+        self.ensure(size * LEADING + self.style.body * LEADING * 2.0);
+        self.space(size * 0.5);
+        self.paragraph(text, "", BOLD, size);
+        self.space(size * 0.2);
+    }
+
+    fn muted(&mut self, text: &str) {
+        self.page().content.set_fill_gray(0.4);
+        self.paragraph(text, "", REGULAR, self.style.small);
+        self.page().content.set_fill_gray(0.0);
+    }
+
+    /// Draws a QR code in the top right corner of the current page, linked to `url`.
+    fn qr_code(&mut self, url: &str) {
+        let Ok(code) = QrCode::new(url.as_bytes()) else {
+            return;
+        };
+        let modules = code.width();
+        let colors = code.to_colors();
+        let size = self.style.qr;
+        let module = size / modules as f32;
+        let left = self.style.width - self.style.margin - size;
+        let top = self.style.height - self.style.margin;
+
+        let page = self.page();
+        page.content.set_fill_gray(0.0);
+        for row in 0..modules {
+            let mut col = 0;
+            while col < modules {
+                if colors[row * modules + col] != Color::Dark {
+                    col += 1;
+                    continue;
+                }
+                // One rectangle per horizontal run of dark modules.
+                let start = col;
+                while col < modules && colors[row * modules + col] == Color::Dark {
+                    col += 1;
+                }
+                page.content.rect(
+                    left + start as f32 * module,
+                    top - (row + 1) as f32 * module,
+                    (col - start) as f32 * module,
+                    module,
+                );
+            }
+        }
+        page.content.fill_nonzero();
+        page.links.push((Rect::new(left, top - size, left + size, top), Link::Uri(url.to_string())));
+
+        let gap = self.style.margin / 3.0;
+        self.reserved = Some((top - size - gap, size + gap));
+    }
+
+    fn recipe(&mut self, recipe: &RecipeViewDto, url: Option<&str>, lang: &str) {
+        if let Some(url) = url {
+            self.qr_code(url);
+        }
+        self.paragraph(&recipe.title, "", BOLD, self.style.title);
+        self.space(self.style.small * 0.5);
+
+        let mut meta = vec![label(lang, &format!("Serves {}", recipe.servings), &format!("{} portions", recipe.servings))];
+        if recipe.prep_time_minutes > 0 {
+            meta.push(format!("{} {}", label(lang, "Prep", "Préparation"), format_minutes(recipe.prep_time_minutes)));
+        }
+        if recipe.cook_time_minutes > 0 {
+            meta.push(format!("{} {}", label(lang, "Cook", "Cuisson"), format_minutes(recipe.cook_time_minutes)));
+        }
+        if let Some(from) = recipe.scaled_from_servings {
+            meta.push(label(lang, &format!("scaled from {}", from), &format!("adapté de {} portions", from)));
+        }
+        self.muted(&meta.join("  •  "));
+        if let Some(author) = recipe.author.as_deref().filter(|a| !a.is_empty()) {
+            self.muted(&label(lang, &format!("by {}", author), &format!("par {}", author)));
+        }
+        if !recipe.description.trim().is_empty() {
+            self.space(self.style.body * 0.5);
+            self.paragraph(&recipe.description, "", REGULAR, self.style.body);
+        }
+
+        if recipe.ingredient_groups.iter().any(|g| !g.ingredients.is_empty()) {
+            self.heading(&label(lang, "Ingredients", "Ingrédients"));
+            let named = recipe.ingredient_groups.len() > 1;
+            for group in &recipe.ingredient_groups {
+                if named && !group.title.is_empty() {
+                    self.space(self.style.body * 0.3);
+                    self.paragraph(&group.title, "", BOLD, self.style.body);
+                }
+                for ingredient in &group.ingredients {
+                    self.paragraph(&json_ld::ingredient_line(ingredient), "•", REGULAR, self.style.body);
+                }
+            }
+        }
+
+        if recipe.step_groups.iter().any(|g| !g.steps.is_empty()) {
+            self.heading(&label(lang, "Steps", "Étapes"));
+            let named = recipe.step_groups.len() > 1;
+            let mut number = 0;
+            for group in &recipe.step_groups {
+                if named && !group.title.is_empty() {
+                    self.space(self.style.body * 0.3);
+                    self.paragraph(&group.title, "", BOLD, self.style.body);
+                }
+                for step in &group.steps {
+                    number += 1;
+                    let text = match step.duration_minutes.filter(|d| *d > 0) {
+                        Some(minutes) => format!("{} ({})", step.instruction, format_minutes(minutes)),
+                        None => step.instruction.clone(),
+                    };
+                    self.paragraph(&text, &format!("{}.", number), REGULAR, self.style.body);
+                    self.space(self.style.body * 0.3);
+                }
+            }
+        }
+    }
+}
+
+/// Renders one recipe. `url` is encoded in a QR code linking back to the recipe.
+pub fn render_recipe(recipe: &RecipeViewDto, layout: PdfLayout, lang: &str, url: Option<&str>) -> Vec<u8> {
+    let mut body = Layout::new(Style::for_layout(layout));
+    body.recipe(recipe, url, lang);
+    write(body.style, body.pages, &recipe.title, &[], false)
+}
+
+/// Renders a cookbook: a cover, a table of contents linking to each recipe and the
+/// recipes themselves, each starting on a new page.
+pub fn render_cookbook(title: &str, recipes: &[(RecipeViewDto, String)], layout: PdfLayout, lang: &str) -> Vec<u8> {
+    let style = Style::for_layout(layout);
+
+    let mut body = Layout::new(style);
+    let mut starts = Vec::new();
+    for (i, (recipe, url)) in recipes.iter().enumerate() {
+        if i > 0 {
+            body.new_page();
+        }
+        starts.push(body.pages.len() - 1);
+        body.recipe(recipe, Some(url), lang);
+    }
+
+    // The contents never change length with the page numbers they print, so a
+    // first pass tells how many pages precede the recipes.
+    let front = 1 + contents(style, recipes, &starts, 0, lang).pages.len();
+
+    let mut cover = Layout::new(style);
+    cover.space((style.height - 2.0 * style.margin) / 3.0);
+    cover.paragraph(title, "", BOLD, style.title * 1.3);
+    cover.muted(&label(lang, &format!("{} recipes", recipes.len()), &format!("{} recettes", recipes.len())));
+
+    let outline: Vec<(String, usize)> = recipes
+        .iter()
+        .zip(&starts)
+        .map(|((recipe, _), start)| (recipe.title.clone(), front + start))
+        .collect();
+    let mut pages = cover.pages;
+    pages.extend(contents(style, recipes, &starts, front, lang).pages);
+    pages.extend(body.pages);
+    write(style, pages, title, &outline, true)
+}
+
+/// Table of contents; `front` is the number of pages before the first recipe.
+fn contents(style: Style, recipes: &[(RecipeViewDto, String)], starts: &[usize], front: usize, lang: &str) -> Layout {
+    let line = style.body * 1.6;
+    let number_width = text_width("0000", REGULAR, style.body);
+    let right = style.width - style.margin;
+
+    let mut toc = Layout::new(style);
+    toc.paragraph(&label(lang, "Contents", "Table des matières"), "", BOLD, style.heading * 1.4);
+    toc.space(style.heading * 0.6);
+    for ((recipe, _), start) in recipes.iter().zip(starts) {
+        toc.ensure(line);
+        let baseline = toc.y - style.body;
+        let page_number = (front + start + 1).to_string();
+        let name = truncate(&recipe.title, REGULAR, style.body, style.content_width() - number_width);
+        toc.draw(style.margin, baseline, &name, REGULAR, style.body);
+        toc.draw(right - text_width(&page_number, REGULAR, style.body), baseline, &page_number, REGULAR, style.body);
+        let rect = Rect::new(style.margin, baseline - style.body * 0.3, right, baseline + style.body);
+        toc.page().links.push((rect, Link::Page(front + start)));
+        toc.y -= line;
+    }
+    toc
+}
+
+/// Assembles the laid out pages into a PDF file.
+fn write(style: Style, pages: Vec<PageOut>, title: &str, outline: &[(String, usize)], numbered: bool) -> Vec<u8> {
+    let catalog_id = Ref::new(1);
+    let tree_id = Ref::new(2);
+    let regular_id = Ref::new(3);
+    let bold_id = Ref::new(4);
+    let info_id = Ref::new(5);
+    let outline_id = Ref::new(6);
+    let page_ids: Vec<Ref> = (0..pages.len()).map(|i| Ref::new(7 + 2 * i as i32)).collect();
+    let outline_ids: Vec<Ref> = (0..outline.len())
+        .map(|i| Ref::new(7 + 2 * pages.len() as i32 + i as i32))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    let mut catalog = pdf.catalog(catalog_id);
+    catalog.pages(tree_id);
+    if !outline.is_empty() {
+        catalog.outlines(outline_id);
+    }
+    catalog.finish();
+    pdf.pages(tree_id).kids(page_ids.iter().copied()).count(pages.len() as i32);
+    pdf.type1_font(regular_id)
+        .base_font(Name(b"Helvetica"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.type1_font(bold_id)
+        .base_font(Name(b"Helvetica-Bold"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.document_info(info_id).title(TextStr(title)).creator(TextStr("Home Recipes"));
+
+    let total = pages.len();
+    for (i, mut page_out) in pages.into_iter().enumerate() {
+        if numbered && i > 0 {
+            let number = format!("{} / {}", i + 1, total);
+            let x = (style.width - text_width(&number, REGULAR, style.small)) / 2.0;
+            page_out.content.set_fill_gray(0.4);
+            page_out.content.begin_text();
+            page_out.content.set_font(REGULAR, style.small);
+            page_out.content.next_line(x, style.margin / 2.0);
+            page_out.content.show(Str(&encode(&number)));
+            page_out.content.end_text();
+        }
+
+        let content_id = Ref::new(page_ids[i].get() + 1);
+        let mut page = pdf.page(page_ids[i]);
+        page.media_box(Rect::new(0.0, 0.0, style.width, style.height));
+        page.parent(tree_id);
+        page.contents(content_id);
+        page.resources().fonts().pair(REGULAR, regular_id).pair(BOLD, bold_id);
+        if !page_out.links.is_empty() {
+            let mut annotations = page.annotations();
+            for (rect, link) in &page_out.links {
+                let mut annotation = annotations.push();
+                annotation.subtype(AnnotationType::Link);
+                annotation.rect(*rect);
+                annotation.border(0.0, 0.0, 0.0, None);
+                match link {
+                    Link::Uri(uri) => {
+                        annotation.action().action_type(ActionType::Uri).uri(Str(uri.as_bytes()));
+                    }
+                    Link::Page(target) => {
+                        annotation
+                            .action()
+                            .action_type(ActionType::GoTo)
+                            .destination()
+                            .page(page_ids[*target])
+                            .xyz(0.0, style.height, None);
+                    }
+                }
+            }
+        }
+        page.finish();
+        pdf.stream(content_id, &page_out.content.finish());
+    }
+
+    if !outline.is_empty() {
+        pdf.outline(outline_id)
+            .first(outline_ids[0])
+            .last(outline_ids[outline_ids.len() - 1])
+            .count(outline.len() as i32);
+        for (i, (entry, target)) in outline.iter().enumerate() {
+            let mut item = pdf.outline_item(outline_ids[i]);
+            item.parent(outline_id).title(TextStr(entry));
+            if i > 0 {
+                item.prev(outline_ids[i - 1]);
+            }
+            if i + 1 < outline_ids.len() {
+                item.next(outline_ids[i + 1]);
+            }
+            item.dest().page(page_ids[*target]).xyz(0.0, style.height, None);
+        }
+    }
+
+    pdf.finish()
+}
+
+/// Pops as many words as fit in `width`. A word wider than the line is cut.
+fn take_line(words: &mut VecDeque<String>, font: Name<'static>, size: f32, width: f32) -> String {
+    let mut line = String::new();
+    while let Some(word) = words.front() {
+        let candidate = if line.is_empty() { word.clone() } else { format!("{} {}", line, word) };
+        if text_width(&candidate, font, size) <= width {
+            line = candidate;
+            words.pop_front();
+            continue;
+        }
+        if line.is_empty() {
+            let mut head = String::new();
+            for c in word.chars() {
+                head.push(c);
+                if text_width(&head, font, size) > width && head.chars().count() > 1 {
+                    head.pop();
+                    break;
+                }
+            }
+            let tail = word[head.len()..].to_string();
+            if tail.is_empty() {
+                words.pop_front();
+            } else {
+                words[0] = tail;
+            }
+            line = head;
+        }
+        break;
+    }
+    line
+}
+
+fn truncate(text: &str, font: Name<'static>, size: f32, width: f32) -> String {
+    if text_width(text, font, size) <= width {
+        return text.to_string();
+    }
+    let mut out: String = text.to_string();
+    while !out.is_empty() && text_width(&format!("{}…", out), font, size) > width {
+        out.pop();
+    }
+    format!("{}…", out.trim_end())
+}
+
+fn label(lang: &str, en: &str, fr: &str) -> String {
+    if lang == "fr" { fr.to_string() } else { en.to_string() }
+}
+
+/// 95 → "1 h 35 min".
+fn format_minutes(minutes: i32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{} min", m),
+        (h, 0) => format!("{} h", h),
+        (h, m) => format!("{} h {} min", h, m),
+    }
+}
+
+/// Text as WinAnsiEncoding bytes; anything the encoding lacks becomes "?".
+fn encode(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            ' '..='~' => out.push(c as u8),
+            '\u{A0}'..='\u{FF}' => out.push(c as u32 as u8),
+            '\t' | '\n' | '\r' => out.push(b' '),
+            '€' => out.push(0x80),
+            '…' => out.push(0x85),
+            'Œ' => out.push(0x8C),
+            '‘' => out.push(0x91),
+            '’' => out.push(0x92),
+            '“' => out.push(0x93),
+            '”' => out.push(0x94),
+            '•' => out.push(0x95),
+            '–' => out.push(0x96),
+            '—' => out.push(0x97),
+            'œ' => out.push(0x9C),
+            '⅓' => out.extend_from_slice(b"1/3"),
+            '⅔' => out.extend_from_slice(b"2/3"),
+            '⅛' => out.extend_from_slice(b"1/8"),
+            _ => out.push(b'?'),
+        }
+    }
+    out
+}
+
+fn text_width(text: &str, font: Name<'static>, size: f32) -> f32 {
+    let bold = font == BOLD;
+    let units: u32 = encode(text).into_iter().map(|b| glyph_width(b, bold) as u32).sum();
+    units as f32 * size / 1000.0
+}
+
+fn glyph_width(byte: u8, bold: bool) -> u16 {
+    let table = if bold { &HELVETICA_BOLD_WIDTHS } else { &HELVETICA_WIDTHS };
+    let ascii = |c: u8| table[(c - 32) as usize];
+    match byte {
+        32..=126 => ascii(byte),
+        0x85 | 0x97 | 0x8C => 1000,
+        0x9C => 944,
+        0x91 | 0x92 => if bold { 278 } else { 222 },
+        0x93 | 0x94 => if bold { 500 } else { 333 },
+        0x95 => 350,
+        0xB0 => 400,
+        0xBC..=0xBE => 834,
+        // Accented letters are as wide as their base letter.
+        0xC0..=0xC5 => ascii(b'A'),
+        0xC7 => ascii(b'C'),
+        0xC8..=0xCB => ascii(b'E'),
+        0xCC..=0xCF => ascii(b'I'),
+        0xD1 => ascii(b'N'),
+        0xD2..=0xD6 | 0xD8 => ascii(b'O'),
+        0xD9..=0xDC => ascii(b'U'),
+        0xE0..=0xE5 => ascii(b'a'),
+        0xE7 => ascii(b'c'),
+        0xE8..=0xEB => ascii(b'e'),
+        0xEC..=0xEF => ascii(b'i'),
+        0xF1 => ascii(b'n'),
+        0xF2..=0xF6 | 0xF8 => ascii(b'o'),
+        0xF9..=0xFC => ascii(b'u'),
+        0xFD | 0xFF => ascii(b'y'),
+        _ => 556,
+    }
+}
@@ -0,0 +1,475 @@
+use crate::dto::ingredient_dto::IngredientEditorDto;
+use crate::dto::recipe_dto::RecipeEditorDto;
+use crate::dto::recipe_version_dto::{
+    ChangeKind, FieldChangeDto, IngredientChangeDto, RecipeVersionDiffDto, StepChangeDto,
+    TranslationChangeDto,
+};
+use crate::dto::step_dto::StepEditorDto;
+use crate::dto::tag_dto::TagDto;
+use serde_json::json;
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+struct FlatIngredient<'a> {
+    group: Option<String>,
+    ingredient: &'a IngredientEditorDto,
+}
+
+struct FlatStep<'a> {
+    group: Option<String>,
+    step: &'a StepEditorDto,
+}
+
+/// Compares two recipe snapshots. `from` is the older state, `to` the newer one.
+pub fn diff(
+    recipe_id: Uuid,
+    from_version_id: Uuid,
+    to_version_id: Option<Uuid>,
+    from: &RecipeEditorDto,
+    to: &RecipeEditorDto,
+    lang: &str,
+) -> RecipeVersionDiffDto {
+    let (tags_added, tags_removed) = diff_tags(&from.tags, &to.tags);
+
+    RecipeVersionDiffDto {
+        recipe_id,
+        from_version_id,
+        to_version_id,
+        fields: diff_fields(from, to),
+        translations: diff_recipe_translations(from, to),
+        tags_added,
+        tags_removed,
+        ingredients: diff_ingredients(from, to, lang),
+        steps: diff_steps(from, to, lang),
+    }
+}
+
+fn diff_fields(from: &RecipeEditorDto, to: &RecipeEditorDto) -> Vec<FieldChangeDto> {
+    let candidates = [
+        ("primary_language", json!(from.primary_language), json!(to.primary_language)),
+        ("image_url", json!(from.image_url), json!(to.image_url)),
+        ("servings", json!(from.servings), json!(to.servings)),
+        ("prep_time_minutes", json!(from.prep_time_minutes), json!(to.prep_time_minutes)),
+        ("cook_time_minutes", json!(from.cook_time_minutes), json!(to.cook_time_minutes)),
+        ("is_private", json!(from.is_private), json!(to.is_private)),
+    ];
+
+    candidates
+        .into_iter()
+        .filter(|(_, from, to)| from != to)
+        .map(|(field, from, to)| FieldChangeDto {
+            field: field.to_string(),
+            from,
+            to,
+        })
+        .collect()
+}
+
+fn diff_recipe_translations(
+    from: &RecipeEditorDto,
+    to: &RecipeEditorDto,
+) -> Vec<TranslationChangeDto> {
+    let languages: BTreeSet<&str> = from
+        .translations
+        .iter()
+        .chain(to.translations.iter())
+        .map(|t| t.language_code.as_str())
+        .collect();
+
+    let mut changes = Vec::new();
+    for lang in languages {
+        let old = from.translations.iter().find(|t| t.language_code == lang);
+        let new = to.translations.iter().find(|t| t.language_code == lang);
+        push_text_change(
+            &mut changes,
+            lang,
+            "title",
+            old.map(|t| t.title.as_str()),
+            new.map(|t| t.title.as_str()),
+        );
+        push_text_change(
+            &mut changes,
+            lang,
+            "description",
+            old.and_then(|t| t.description.as_deref()),
+            new.and_then(|t| t.description.as_deref()),
+        );
+    }
+    changes
+}
+
+fn diff_tags(from: &[TagDto], to: &[TagDto]) -> (Vec<TagDto>, Vec<TagDto>) {
+    let has_tag = |tags: &[TagDto], tag: &TagDto| {
+        tags.iter()
+            .any(|t| t.id == tag.id || t.name.to_lowercase() == tag.name.to_lowercase())
+    };
+
+    let added = to.iter().filter(|t| !has_tag(from, t)).cloned().collect();
+    let removed = from.iter().filter(|t| !has_tag(to, t)).cloned().collect();
+    (added, removed)
+}
+
+fn diff_ingredients(
+    from: &RecipeEditorDto,
+    to: &RecipeEditorDto,
+    lang: &str,
+) -> Vec<IngredientChangeDto> {
+    let old = flatten_ingredients(from, lang);
+    let new = flatten_ingredients(to, lang);
+
+    // Restoring a version recreates rows with fresh ids, so fall back to the name.
+    let pairs = match_items(
+        &old,
+        &new,
+        |a, b| a.ingredient.id == b.ingredient.id,
+        |a, b| {
+            a.ingredient.translations.iter().any(|ta| {
+                b.ingredient.translations.iter().any(|tb| {
+                    ta.language_code == tb.language_code
+                        && ta.data.trim().to_lowercase() == tb.data.trim().to_lowercase()
+                })
+            })
+        },
+    );
+
+    let mut changes = Vec::new();
+    let mut matched_new = vec![false; new.len()];
+
+    for (old_idx, item) in old.iter().enumerate() {
+        let Some(new_idx) = pairs[old_idx] else {
+            changes.push(IngredientChangeDto {
+                change: ChangeKind::Removed,
+                name: ingredient_name(item.ingredient, lang),
+                group: item.group.clone(),
+                from_quantity: Some(item.ingredient.quantity),
+                to_quantity: None,
+                quantity_delta: None,
+                from_unit: Some(item.ingredient.unit.clone()),
+                to_unit: None,
+                translations: vec![],
+            });
+            continue;
+        };
+        matched_new[new_idx] = true;
+
+        let a = item.ingredient;
+        let b = new[new_idx].ingredient;
+
+        let mut translations = Vec::new();
+        for lang_code in translation_languages(a, b) {
+            let ta = a.translations.iter().find(|t| t.language_code == lang_code);
+            let tb = b.translations.iter().find(|t| t.language_code == lang_code);
+            push_text_change(
+                &mut translations,
+                lang_code,
+                "data",
+                ta.map(|t| t.data.as_str()),
+                tb.map(|t| t.data.as_str()),
+            );
+            push_text_change(
+                &mut translations,
+                lang_code,
+                "note",
+                ta.and_then(|t| t.note.as_deref()),
+                tb.and_then(|t| t.note.as_deref()),
+            );
+        }
+
+        let unit_changed = a.unit_id != b.unit_id;
+        let quantity_changed = a.quantity != b.quantity;
+        if !unit_changed && !quantity_changed && translations.is_empty() {
+            continue;
+        }
+
+        changes.push(IngredientChangeDto {
+            change: ChangeKind::Modified,
+            name: ingredient_name(b, lang),
+            group: new[new_idx].group.clone(),
+            from_quantity: Some(a.quantity),
+            to_quantity: Some(b.quantity),
+            quantity_delta: (!unit_changed).then(|| b.quantity - a.quantity),
+            from_unit: Some(a.unit.clone()),
+            to_unit: Some(b.unit.clone()),
+            translations,
+        });
+    }
+
+    for (new_idx, item) in new.iter().enumerate() {
+        if matched_new[new_idx] {
+            continue;
+        }
+        changes.push(IngredientChangeDto {
+            change: ChangeKind::Added,
+            name: ingredient_name(item.ingredient, lang),
+            group: item.group.clone(),
+            from_quantity: None,
+            to_quantity: Some(item.ingredient.quantity),
+            quantity_delta: None,
+            from_unit: None,
+            to_unit: Some(item.ingredient.unit.clone()),
+            translations: vec![],
+        });
+    }
+
+    changes
+}
+
+fn diff_steps(from: &RecipeEditorDto, to: &RecipeEditorDto, lang: &str) -> Vec<StepChangeDto> {
+    let old = flatten_steps(from, lang);
+    let new = flatten_steps(to, lang);
+
+    let pairs = match_items(
+        &old,
+        &new,
+        |a, b| a.step.id == b.step.id,
+        |a, b| {
+            a.step.translations.iter().any(|ta| {
+                b.step.translations.iter().any(|tb| {
+                    ta.language_code == tb.language_code
+                        && ta.instruction.trim() == tb.instruction.trim()
+                })
+            })
+        },
+    );
+
+    // A matched step only counts as moved when it falls outside the longest run
+    // of steps that kept their relative order; inserts and deletes shift positions
+    // without reordering anything.
+    let matched: Vec<(usize, usize)> = pairs
+        .iter()
+        .enumerate()
+        .filter_map(|(old_idx, new_idx)| new_idx.map(|n| (old_idx, n)))
+        .collect();
+    let in_order = longest_increasing_run(&matched.iter().map(|(_, n)| *n).collect::<Vec<_>>());
+
+    let mut changes = Vec::new();
+    let mut matched_new = vec![false; new.len()];
+
+    for (pair_idx, (old_idx, new_idx)) in matched.iter().copied().enumerate() {
+        matched_new[new_idx] = true;
+        let a = old[old_idx].step;
+        let b = new[new_idx].step;
+
+        let mut kinds = Vec::new();
+        if !in_order[pair_idx] {
+            kinds.push(ChangeKind::Moved);
+        }
+
+        let mut translations = Vec::new();
+        let languages: BTreeSet<&str> = a
+            .translations
+            .iter()
+            .chain(b.translations.iter())
+            .map(|t| t.language_code.as_str())
+            .collect();
+        for lang_code in languages {
+            push_text_change(
+                &mut translations,
+                lang_code,
+                "instruction",
+                a.translations
+                    .iter()
+                    .find(|t| t.language_code == lang_code)
+                    .map(|t| t.instruction.as_str()),
+                b.translations
+                    .iter()
+                    .find(|t| t.language_code == lang_code)
+                    .map(|t| t.instruction.as_str()),
+            );
+        }
+        if !translations.is_empty()
+            || a.duration_minutes != b.duration_minutes
+            || a.image_url != b.image_url
+        {
+            kinds.push(ChangeKind::Modified);
+        }
+
+        if kinds.is_empty() {
+            continue;
+        }
+
+        changes.push(StepChangeDto {
+            changes: kinds,
+            group: new[new_idx].group.clone(),
+            from_position: Some(old_idx as i32 + 1),
+            to_position: Some(new_idx as i32 + 1),
+            from_duration_minutes: a.duration_minutes,
+            to_duration_minutes: b.duration_minutes,
+            translations,
+        });
+    }
+
+    for (old_idx, item) in old.iter().enumerate() {
+        if pairs[old_idx].is_none() {
+            changes.push(StepChangeDto {
+                changes: vec![ChangeKind::Removed],
+                group: item.group.clone(),
+                from_position: Some(old_idx as i32 + 1),
+                to_position: None,
+                from_duration_minutes: item.step.duration_minutes,
+                to_duration_minutes: None,
+                translations: step_texts(item.step, true),
+            });
+        }
+    }
+
+    for (new_idx, item) in new.iter().enumerate() {
+        if !matched_new[new_idx] {
+            changes.push(StepChangeDto {
+                changes: vec![ChangeKind::Added],
+                group: item.group.clone(),
+                from_position: None,
+                to_position: Some(new_idx as i32 + 1),
+                from_duration_minutes: None,
+                to_duration_minutes: item.step.duration_minutes,
+                translations: step_texts(item.step, false),
+            });
+        }
+    }
+
+    changes
+}
+
+fn flatten_ingredients<'a>(recipe: &'a RecipeEditorDto, lang: &str) -> Vec<FlatIngredient<'a>> {
+    let mut groups: Vec<_> = recipe.ingredient_groups.iter().collect();
+    groups.sort_by_key(|g| g.position);
+
+    let mut flat = Vec::new();
+    for group in groups {
+        let title = group
+            .translations
+            .iter()
+            .find(|t| t.language_code == lang)
+            .or_else(|| group.translations.first())
+            .map(|t| t.title.clone());
+
+        let mut ingredients: Vec<_> = group.ingredients.iter().collect();
+        ingredients.sort_by_key(|i| i.position);
+        flat.extend(ingredients.into_iter().map(|ingredient| FlatIngredient {
+            group: title.clone(),
+            ingredient,
+        }));
+    }
+    flat
+}
+
+fn flatten_steps<'a>(recipe: &'a RecipeEditorDto, lang: &str) -> Vec<FlatStep<'a>> {
+    let mut groups: Vec<_> = recipe.step_groups.iter().collect();
+    groups.sort_by_key(|g| g.position);
+
+    let mut flat = Vec::new();
+    for group in groups {
+        let title = group
+            .translations
+            .iter()
+            .find(|t| t.language_code == lang)
+            .or_else(|| group.translations.first())
+            .map(|t| t.title.clone());
+
+        let mut steps: Vec<_> = group.steps.iter().collect();
+        steps.sort_by_key(|s| s.position);
+        flat.extend(steps.into_iter().map(|step| FlatStep {
+            group: title.clone(),
+            step,
+        }));
+    }
+    flat
+}
+
+/// Pairs each old item with at most one new item, first by id, then by content.
+fn match_items<T>(
+    old: &[T],
+    new: &[T],
+    same_id: impl Fn(&T, &T) -> bool,
+    same_content: impl Fn(&T, &T) -> bool,
+) -> Vec<Option<usize>> {
+    let mut pairs: Vec<Option<usize>> = vec![None; old.len()];
+    let mut taken = vec![false; new.len()];
+
+    for matcher in [&same_id as &dyn Fn(&T, &T) -> bool, &same_content] {
+        for (old_idx, a) in old.iter().enumerate() {
+            if pairs[old_idx].is_some() {
+                continue;
+            }
+            if let Some(new_idx) = (0..new.len()).find(|&n| !taken[n] && matcher(a, &new[n])) {
+                pairs[old_idx] = Some(new_idx);
+                taken[new_idx] = true;
+            }
+        }
+    }
+    pairs
+}
+
+/// Flags the elements belonging to one longest strictly increasing subsequence.
+fn longest_increasing_run(values: &[usize]) -> Vec<bool> {
+    let n = values.len();
+    let mut length = vec![1usize; n];
+    let mut previous = vec![None; n];
+
+    for i in 0..n {
+        for j in 0..i {
+            if values[j] < values[i] && length[j] + 1 > length[i] {
+                length[i] = length[j] + 1;
+                previous[i] = Some(j);
+            }
+        }
+    }
+
+    let mut keep = vec![false; n];
+    let mut cursor = (0..n).max_by_key(|&i| length[i]);
+    while let Some(i) = cursor {
+        keep[i] = true;
+        cursor = previous[i];
+    }
+    keep
+}
+
+fn translation_languages<'a>(
+    a: &'a IngredientEditorDto,
+    b: &'a IngredientEditorDto,
+) -> BTreeSet<&'a str> {
+    a.translations
+        .iter()
+        .chain(b.translations.iter())
+        .map(|t| t.language_code.as_str())
+        .collect()
+}
+
+fn ingredient_name(ingredient: &IngredientEditorDto, lang: &str) -> String {
+    ingredient
+        .translations
+        .iter()
+        .find(|t| t.language_code == lang)
+        .or_else(|| ingredient.translations.first())
+        .map(|t| t.data.clone())
+        .unwrap_or_default()
+}
+
+fn step_texts(step: &StepEditorDto, removed: bool) -> Vec<TranslationChangeDto> {
+    step.translations
+        .iter()
+        .map(|t| TranslationChangeDto {
+            language_code: t.language_code.clone(),
+            field: "instruction".to_string(),
+            from: removed.then(|| t.instruction.clone()),
+            to: (!removed).then(|| t.instruction.clone()),
+        })
+        .collect()
+}
+
+fn push_text_change(
+    changes: &mut Vec<TranslationChangeDto>,
+    language_code: &str,
+    field: &str,
+    from: Option<&str>,
+    to: Option<&str>,
+) {
+    if from != to {
+        changes.push(TranslationChangeDto {
+            language_code: language_code.to_string(),
+            field: field.to_string(),
+            from: from.map(str::to_string),
+            to: to.map(str::to_string),
+        });
+    }
+}
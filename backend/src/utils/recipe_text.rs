@@ -0,0 +1,18 @@
+/// Longest ingredient name accepted by `IngredientTranslationInput`.
+pub const MAX_INGREDIENT_NAME: usize = 50;
+
+/// Title of a generated group or document, in French or else in English.
+pub fn default_title(language: &str, en: &str, fr: &str) -> String {
+    if language == "fr" { fr.to_string() } else { en.to_string() }
+}
+
+/// Download name for an exported recipe: "Tarte au sucre" → "tarte-au-sucre.cook".
+pub fn file_name(title: &str, extension: &str) -> String {
+    let slug: String = title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug.split('-').filter(|s| !s.is_empty()).collect::<Vec<_>>().join("-");
+    if slug.is_empty() { format!("recipe.{}", extension) } else { format!("{}.{}", slug, extension) }
+}
@@ -0,0 +1,37 @@
+use crate::dto::ingredient_dto::IngredientRecipeViewDto;
+use crate::dto::recipe_dto::RecipeViewDto;
+use crate::utils::unit::{round_for_unit, UnitConverter};
+use rust_decimal::prelude::ToPrimitive;
+
+/// Scales every ingredient of `recipe` to `target_servings`.
+pub fn scale_recipe(recipe: &mut RecipeViewDto, target_servings: i32, converter: &UnitConverter) {
+    if recipe.servings <= 0 || target_servings <= 0 {
+        return;
+    }
+
+    let ratio = target_servings as f64 / recipe.servings as f64;
+    for group in &mut recipe.ingredient_groups {
+        for ingredient in &mut group.ingredients {
+            scale_ingredient(ingredient, ratio, converter);
+        }
+    }
+
+    recipe.scaled_from_servings = Some(recipe.servings);
+    recipe.servings = target_servings;
+}
+
+/// Multiplies the ingredient quantity by `ratio`, then moves it to a larger or
+/// smaller unit of the same family when that reads better (16 tbsp → 1 cup).
+pub fn scale_ingredient(ingredient: &mut IngredientRecipeViewDto, ratio: f64, converter: &UnitConverter) {
+    let quantity = ingredient.quantity.to_f64().unwrap_or(0.0) * ratio;
+    if quantity <= 0.0 {
+        return;
+    }
+
+    let (quantity, unit) = converter.fit(quantity, &ingredient.unit);
+    let (rounded, display) = round_for_unit(quantity, &unit);
+
+    ingredient.quantity = rounded;
+    ingredient.display_quantity = Some(display);
+    ingredient.unit = unit;
+}
@@ -0,0 +1,87 @@
+use crate::dto::shopping_list_dto::Aisle;
+use crate::dto::unit_dto::UnitDto;
+use crate::utils::unit::UnitConverter;
+use uuid::Uuid;
+
+/// One ingredient line of a recipe on the list, already scaled to the servings
+/// asked for.
+pub struct ShoppingLine {
+    pub name: String,
+    pub quantity: f64,
+    pub unit: UnitDto,
+    pub canonical_ingredient_id: Option<Uuid>,
+    pub aisle: Aisle,
+}
+
+/// Ingredient to buy once the lines of every recipe are merged.
+pub struct MergedItem {
+    /// Identifies the item across rebuilds, so its checked state is kept.
+    pub key: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit_id: Uuid,
+    pub canonical_ingredient_id: Option<Uuid>,
+    pub aisle: Aisle,
+}
+
+struct Pending {
+    item: MergedItem,
+    unit: UnitDto,
+    /// Sum in the base unit for convertible units, in `unit` otherwise.
+    total: f64,
+    convertible: bool,
+}
+
+/// Merges lines of the same ingredient: the catalog entry when there is one, the
+/// name otherwise. Weights and volumes are summed through their base unit (g, ml)
+/// and expressed in the unit of the first line, moved to a more readable unit of
+/// its family; counted units (pieces, cloves) only merge with the same unit.
+pub fn merge_lines(lines: Vec<ShoppingLine>, converter: &UnitConverter) -> Vec<MergedItem> {
+    let mut pending: Vec<Pending> = Vec::new();
+
+    for line in lines {
+        let identity = match line.canonical_ingredient_id {
+            Some(id) => id.to_string(),
+            None => line.name.trim().to_lowercase(),
+        };
+        let convertible = converter.is_convertible(&line.unit);
+        let family = if convertible { UnitConverter::base_id(&line.unit) } else { line.unit.id };
+        let key = format!("{}|{}", identity, family);
+        let quantity = line.quantity.max(0.0);
+        let amount = if convertible { UnitConverter::to_base(quantity, &line.unit) } else { quantity };
+
+        match pending.iter_mut().find(|p| p.item.key == key) {
+            Some(p) => p.total += amount,
+            None => pending.push(Pending {
+                item: MergedItem {
+                    key,
+                    name: line.name.trim().to_string(),
+                    quantity: None,
+                    unit_id: line.unit.id,
+                    canonical_ingredient_id: line.canonical_ingredient_id,
+                    aisle: line.aisle,
+                },
+                unit: line.unit,
+                total: amount,
+                convertible,
+            }),
+        }
+    }
+
+    pending
+        .into_iter()
+        .map(|p| {
+            let mut item = p.item;
+            if p.total > 0.0 {
+                let (quantity, unit) = if p.convertible {
+                    converter.fit(UnitConverter::from_base(p.total, &p.unit), &p.unit)
+                } else {
+                    (p.total, p.unit)
+                };
+                item.quantity = Some(quantity);
+                item.unit_id = unit.id;
+            }
+            item
+        })
+        .collect()
+}
@@ -1,122 +1,454 @@
-﻿use std::fmt;
-use serde::{Deserialize, Serialize};
-use std::str::FromStr;
-use utoipa::ToSchema;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,ToSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum IngredientUnit {
-    #[serde(alias = "g", alias = "gram", alias = "grams")]
-    Gram,
-    #[serde(alias = "kg", alias = "kilogram", alias = "kilograms")]
-    Kilogram,
-
-    #[serde(alias = "ml", alias = "milliliter", alias = "milliliters")]
-    Milliliter,
-    #[serde(alias = "l", alias = "liter", alias = "liters")]
-    Liter,
-
-    #[serde(alias = "pc", alias = "piece", alias = "pieces")]
-    Piece,
-
-    #[serde(alias = "tsp", alias = "teaspoon", alias = "teaspoons")]
-    Teaspoon,
-    #[serde(alias = "tbsp", alias = "tablespoon", alias = "tablespoons")]
-    Tablespoon,
-    #[serde(alias = "cup", alias = "cups", alias = "Cups")]
-    Cup,
-}
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum UnitCategory {
-    Weight,
-    Volume,
-    Count,
+use crate::dto::density_dto::{DensityDto, DensityStatus};
+use crate::dto::ingredient_dto::IngredientEquivalentDto;
+use crate::dto::recipe_dto::RecipeViewDto;
+use crate::dto::unit_dto::{UnitDto, UnitSystem};
+use migration::prelude::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use uuid::Uuid;
+
+/// Fractions a cook can actually measure, with their display glyph.
+const FRACTIONS: [(f64, &str); 7] = [
+    (0.0, ""),
+    (0.25, "¼"),
+    (1.0 / 3.0, "⅓"),
+    (0.5, "½"),
+    (2.0 / 3.0, "⅔"),
+    (0.75, "¾"),
+    (1.0, ""),
+];
+
+/// How far a value may sit from a quarter and still be promoted.
+const PROMOTION_TOLERANCE: f64 = 0.01;
+
+/// How much precision a cross-system conversion may lose to rounding before a
+/// smaller unit is preferred.
+const CONVERSION_TOLERANCE: f64 = 0.05;
+
+/// Codes of the two base units a density can bridge.
+const WEIGHT_BASE: &str = "GRAM";
+const VOLUME_BASE: &str = "MILLILITER";
+
+/// Spellings found in recipes for each unit code, in English and French. Cups and
+/// spoons written in English are read as US measures, in French as metric ones.
+const UNIT_ALIASES: [(&str, &[&str]); 20] = [
+    ("GRAM", &["g", "gr", "gram", "grams", "gramme", "grammes"]),
+    ("KILOGRAM", &["kg", "kilo", "kilos", "kilogram", "kilograms", "kilogramme", "kilogrammes"]),
+    ("MILLILITER", &["ml", "milliliter", "milliliters", "millilitre", "millilitres"]),
+    ("LITER", &["l", "liter", "liters", "litre", "litres"]),
+    ("CUP", &["metric cup", "metric cups", "tasse", "tasses"]),
+    ("TABLESPOON", &["c. à soupe", "c.à.s", "cuillère à soupe", "cuillères à soupe"]),
+    ("TEASPOON", &["c. à thé", "c.à.t", "c. à café", "cuillère à thé", "cuillères à thé"]),
+    ("US_CUP", &["cup", "cups", "us cup", "us cups", "cup (us)", "tasse (us)"]),
+    ("US_TABLESPOON", &["tbsp", "tbs", "tbsps", "tablespoon", "tablespoons", "us tbsp", "tbsp (us)"]),
+    ("US_TEASPOON", &["tsp", "tsps", "teaspoon", "teaspoons", "us tsp", "tsp (us)"]),
+    ("POUND", &["lb", "lbs", "pound", "pounds", "livre", "livres"]),
+    ("OUNCE", &["oz", "ounce", "ounces", "once", "onces"]),
+    ("CLOVE", &["clove", "cloves", "gousse", "gousses"]),
+    ("PINCH", &["pinch", "pinches", "pincée", "pincées"]),
+    ("CAN", &["can", "cans", "tin", "tins", "boîte", "boîtes"]),
+    ("SLICE", &["slice", "slices", "tranche", "tranches"]),
+    ("BUNCH", &["bunch", "bunches", "botte", "bottes"]),
+    ("PIECE", &["piece", "pieces", "pc", "pcs", "pièce", "pièces"]),
+    ("BOX", &["box", "boxes", "packet", "packets", "paquet", "paquets", "sachet", "sachets"]),
+    ("TO_TASTE", &["to taste", "au goût"]),
+];
+
+/// Nobody measures 34 tablespoons; past this count a larger unit is preferred.
+const MAX_FRACTION_COUNT: f64 = 16.0;
+
+/// Conversion engine driven by the `ingredient_units` table. A convertible unit
+/// points at its base (g or ml) through `base_unit_id`, and `conversion_factor`
+/// says how many base units it holds. Weight and volume are bridged with the
+/// per-ingredient densities of `ingredient_densities`.
+pub struct UnitConverter {
+    units: Vec<UnitDto>,
+    densities: Vec<DensityDto>,
 }
 
-impl IngredientUnit {
-    pub fn category(&self) -> UnitCategory {
-        match self {
-            IngredientUnit::Gram | IngredientUnit::Kilogram => UnitCategory::Weight,
-            IngredientUnit::Milliliter | IngredientUnit::Liter => UnitCategory::Volume,
-            IngredientUnit::Teaspoon
-            | IngredientUnit::Tablespoon
-            | IngredientUnit::Cup => UnitCategory::Volume,
-            IngredientUnit::Piece => UnitCategory::Count,
-        }
+impl UnitConverter {
+    pub fn new(units: Vec<UnitDto>) -> Self {
+        Self { units, densities: Vec::new() }
     }
 
-    pub fn to_base(&self, value: f64) -> f64 {
-        match self {
-            // Weight → grams
-            IngredientUnit::Gram => value,
-            IngredientUnit::Kilogram => value * 1000.0,
+    pub fn with_densities(mut self, densities: Vec<DensityDto>) -> Self {
+        self.densities = densities;
+        self
+    }
 
-            // Volume → milliliters
-            IngredientUnit::Milliliter => value,
-            IngredientUnit::Liter => value * 1000.0,
-            IngredientUnit::Teaspoon => value * 5.0,
-            IngredientUnit::Tablespoon => value * 15.0,
-            IngredientUnit::Cup => value * 240.0,
+    pub fn find(&self, id: Uuid) -> Option<&UnitDto> {
+        self.units.iter().find(|u| u.id == id)
+    }
+
+    pub fn base_id(unit: &UnitDto) -> Uuid {
+        unit.base_unit_id.unwrap_or(unit.id)
+    }
+
+    pub fn base_unit(&self, unit: &UnitDto) -> Option<&UnitDto> {
+        self.find(Self::base_id(unit))
+    }
+
+    /// Units like "pinch" or "to taste" have no factor and never convert.
+    pub fn is_convertible(&self, unit: &UnitDto) -> bool {
+        unit.conversion_factor > 0.0
+            && (unit.base_unit_id.is_some()
+                || self.units.iter().any(|u| u.base_unit_id == Some(unit.id)))
+    }
+
+    pub fn to_base(value: f64, unit: &UnitDto) -> f64 {
+        value * unit.conversion_factor
+    }
+
+    pub fn from_base(value: f64, unit: &UnitDto) -> f64 {
+        value / unit.conversion_factor
+    }
 
-            // Count → pieces
-            IngredientUnit::Piece => value,
+    /// Converts between two units of the same family (both weights or both volumes).
+    pub fn convert(&self, value: f64, from: &UnitDto, to: &UnitDto) -> Option<f64> {
+        if from.id == to.id {
+            return Some(value);
         }
+        if !self.is_convertible(from)
+            || !self.is_convertible(to)
+            || Self::base_id(from) != Self::base_id(to)
+        {
+            return None;
+        }
+        Some(Self::from_base(Self::to_base(value, from), to))
+    }
+
+    /// Converts between any two measurable units, going through `grams_per_ml` when
+    /// one is a weight and the other a volume.
+    pub fn convert_with_density(
+        &self,
+        value: f64,
+        from: &UnitDto,
+        to: &UnitDto,
+        grams_per_ml: f64,
+    ) -> Option<f64> {
+        if let Some(converted) = self.convert(value, from, to) {
+            return Some(converted);
+        }
+        if grams_per_ml <= 0.0 || !self.is_convertible(from) || !self.is_convertible(to) {
+            return None;
+        }
+        let base_value = self.bridge(Self::to_base(value, from), from, grams_per_ml)?;
+        let target_base = self.base_unit(to)?;
+        if self.opposite_base(from)?.id != target_base.id {
+            return None;
+        }
+        Some(Self::from_base(base_value, to))
+    }
+
+    /// Finds the density whose English or French name best matches an ingredient
+    /// name: an exact match first, otherwise the longest name contained as whole
+    /// words ("brown sugar" wins over "sugar" for "packed brown sugar").
+    pub fn density_for(&self, ingredient_name: &str) -> Option<&DensityDto> {
+        let name = ingredient_name.trim().to_lowercase();
+        let padded = format!(" {} ", name);
+
+        self.densities
+            .iter()
+            .filter_map(|d| {
+                [&d.name_en, &d.name_fr]
+                    .into_iter()
+                    .map(|n| n.to_lowercase())
+                    .filter(|n| !n.is_empty())
+                    .filter(|n| *n == name || padded.contains(&format!(" {} ", n)))
+                    .map(|n| (n == name, n.len()))
+                    .max()
+                    .map(|score| (score, d))
+            })
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, d)| d)
     }
 
-    pub fn from_base(&self, value: f64) -> f64 {
-        match self {
-            IngredientUnit::Gram => value,
-            IngredientUnit::Kilogram => value / 1000.0,
+    /// Weight of a measured quantity in grams; volumes need `grams_per_ml`.
+    pub fn to_grams(&self, value: f64, unit: &UnitDto, grams_per_ml: Option<f64>) -> Option<f64> {
+        let gram = self.units.iter().find(|u| u.code == WEIGHT_BASE)?;
+        self.convert_with_density(value, unit, gram, grams_per_ml.unwrap_or(0.0))
+    }
 
-            IngredientUnit::Milliliter => value,
-            IngredientUnit::Liter => value / 1000.0,
-            IngredientUnit::Teaspoon => value / 5.0,
-            IngredientUnit::Tablespoon => value / 15.0,
-            IngredientUnit::Cup => value / 240.0,
+    /// Expresses a weight as a volume (or the reverse) in `system`, picking the
+    /// most readable unit.
+    pub fn equivalent(
+        &self,
+        value: f64,
+        unit: &UnitDto,
+        grams_per_ml: f64,
+        system: &str,
+    ) -> Option<(f64, UnitDto)> {
+        if !self.is_convertible(unit) {
+            return None;
+        }
+        let target_base = self.opposite_base(unit)?;
+        let base_value = self.bridge(Self::to_base(value, unit), unit, grams_per_ml)?;
 
-            IngredientUnit::Piece => value,
+        let mut candidates = self.family(target_base, system);
+        if candidates.is_empty() {
+            candidates = self.family(target_base, UnitSystem::Metric.as_str());
         }
+        Self::pick(base_value, &candidates)
     }
 
-    pub fn convert(value: f64, from: IngredientUnit, to: IngredientUnit) -> Option<f64> {
-        if from.category() != to.category() {
+    /// The weight base for a volume unit and the volume base for a weight unit.
+    fn opposite_base(&self, unit: &UnitDto) -> Option<&UnitDto> {
+        let code = match self.base_unit(unit)?.code.as_str() {
+            WEIGHT_BASE => VOLUME_BASE,
+            VOLUME_BASE => WEIGHT_BASE,
+            _ => return None,
+        };
+        self.units.iter().find(|u| u.code == code)
+    }
+
+    /// Moves a quantity already expressed in its base unit across to the other base.
+    fn bridge(&self, base_value: f64, unit: &UnitDto, grams_per_ml: f64) -> Option<f64> {
+        if grams_per_ml <= 0.0 {
             return None;
         }
+        match self.base_unit(unit)?.code.as_str() {
+            WEIGHT_BASE => Some(base_value / grams_per_ml),
+            VOLUME_BASE => Some(base_value * grams_per_ml),
+            _ => None,
+        }
+    }
+
+    /// Units sharing the base of `unit`, restricted to one measuring system, largest first.
+    fn family(&self, unit: &UnitDto, system: &str) -> Vec<&UnitDto> {
+        let mut members: Vec<&UnitDto> = self
+            .units
+            .iter()
+            .filter(|u| u.conversion_factor > 0.0 && u.is_active)
+            .filter(|u| Self::base_id(u) == Self::base_id(unit) && u.system == system)
+            .collect();
+        members.sort_by(|a, b| b.conversion_factor.total_cmp(&a.conversion_factor));
+        members
+    }
+
+    /// Moves `value` to a larger or smaller unit of the same system when that reads
+    /// better (16 tbsp → 1 cup, 1000 g → 1 kg). Precise units (ml, l) never switch
+    /// to spoon or cup measures.
+    pub fn fit(&self, value: f64, unit: &UnitDto) -> (f64, UnitDto) {
+        if !self.is_convertible(unit) {
+            return (value, unit.clone());
+        }
+
+        let members: Vec<&UnitDto> = self
+            .family(unit, &unit.system)
+            .into_iter()
+            .filter(|u| unit.is_fraction_allowed || !u.is_fraction_allowed)
+            .collect();
+        let base_value = Self::to_base(value, unit);
+
+        let promoted = members
+            .iter()
+            .filter(|u| u.conversion_factor > unit.conversion_factor)
+            .map(|u| (Self::from_base(base_value, u), *u))
+            .find(|(v, u)| *v >= 1.0 && lands_on_quarter(*v, u));
+        if let Some((v, u)) = promoted {
+            return (v, u.clone());
+        }
 
-        let base = from.to_base(value);
-        Some(to.from_base(base))
+        if value >= min_readable(unit) {
+            return (value, unit.clone());
+        }
+
+        let smaller: Vec<&UnitDto> = members
+            .iter()
+            .filter(|u| u.conversion_factor < unit.conversion_factor)
+            .copied()
+            .collect();
+
+        smaller
+            .iter()
+            .map(|u| (Self::from_base(base_value, u), *u))
+            .find(|(v, u)| *v >= min_readable(u))
+            .or_else(|| smaller.last().map(|u| (Self::from_base(base_value, u), *u)))
+            .map(|(v, u)| (v, u.clone()))
+            .unwrap_or_else(|| (value, unit.clone()))
+    }
+
+    /// Expresses `value` in the given measuring system, picking the largest unit
+    /// that still rounds cleanly. Units outside metric/imperial are left alone.
+    pub fn to_system(&self, value: f64, unit: &UnitDto, system: UnitSystem) -> (f64, UnitDto) {
+        if unit.system == system.as_str() || !self.is_convertible(unit) {
+            return (value, unit.clone());
+        }
+
+        let candidates = self.family(unit, system.as_str());
+        Self::pick(Self::to_base(value, unit), &candidates)
+            .unwrap_or_else(|| (value, unit.clone()))
+    }
+
+    /// Chooses the largest candidate that still rounds cleanly, falling back to the
+    /// largest readable one and then to the smallest unit available.
+    fn pick(base_value: f64, candidates: &[&UnitDto]) -> Option<(f64, UnitDto)> {
+        let readable: Vec<&UnitDto> = candidates
+            .iter()
+            .filter(|u| Self::from_base(base_value, u) >= min_readable(u))
+            .copied()
+            .collect();
+
+        readable
+            .iter()
+            .find(|u| {
+                let v = Self::from_base(base_value, u);
+                rounding_error(v, u) <= CONVERSION_TOLERANCE
+                    && !(u.is_fraction_allowed && v > MAX_FRACTION_COUNT)
+            })
+            .or_else(|| readable.first())
+            .or_else(|| candidates.last())
+            .map(|u| (Self::from_base(base_value, u), (*u).clone()))
     }
 }
 
-impl fmt::Display for IngredientUnit {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            IngredientUnit::Gram => write!(f, "Gram"),
-            IngredientUnit::Kilogram => write!(f, "Kilogram"),
-            IngredientUnit::Milliliter => write!(f, "Milliliter"),
-            IngredientUnit::Liter => write!(f, "Liter"),
-            IngredientUnit::Teaspoon => write!(f, "Teaspoon"),
-            IngredientUnit::Tablespoon => write!(f, "Tablespoon"),
-            IngredientUnit::Cup => write!(f, "Cup"),
-            IngredientUnit::Piece => write!(f, "Piece"),
+/// Renders every ingredient of `recipe` in the requested measuring system.
+pub fn convert_recipe(recipe: &mut RecipeViewDto, system: UnitSystem, converter: &UnitConverter) {
+    for group in &mut recipe.ingredient_groups {
+        for ingredient in &mut group.ingredients {
+            let value = ingredient.quantity.to_f64().unwrap_or(0.0);
+            if value <= 0.0 || ingredient.unit.system == system.as_str() {
+                continue;
+            }
+
+            let (value, unit) = converter.to_system(value, &ingredient.unit, system);
+            if unit.id == ingredient.unit.id {
+                continue;
+            }
+
+            let (quantity, display) = round_for_unit(value, &unit);
+            ingredient.quantity = quantity;
+            ingredient.display_quantity = Some(display);
+            ingredient.unit = unit;
         }
     }
 }
-impl FromStr for IngredientUnit {
-    type Err = ();
-
-    fn from_str(input: &str) -> Result<IngredientUnit, Self::Err> {
-        match input.trim().to_lowercase().as_str() {
-            "g" | "gram" | "grams" => Ok(IngredientUnit::Gram),
-            "kg" | "kilogram" | "kilograms" => Ok(IngredientUnit::Kilogram),
-            "ml" | "milliliter" | "milliliters" => Ok(IngredientUnit::Milliliter),
-            "l" | "liter" | "liters" => Ok(IngredientUnit::Liter),
-            "tsp" | "teaspoon" | "teaspoons" => Ok(IngredientUnit::Teaspoon),
-            "tbsp" | "tablespoon" | "tablespoons" => Ok(IngredientUnit::Tablespoon),
-            "cup" | "cups" => Ok(IngredientUnit::Cup),
-            "pc" | "piece" | "pieces" => Ok(IngredientUnit::Piece),
-            "" => Ok(IngredientUnit::Piece), // fallback for empty strings
-            _ => Err(()),
+
+/// Adds the weight↔volume equivalent of every ingredient that has a density on
+/// file, and flags the measurable ones that do not.
+pub fn attach_equivalents(
+    recipe: &mut RecipeViewDto,
+    system: Option<UnitSystem>,
+    converter: &UnitConverter,
+) {
+    for group in &mut recipe.ingredient_groups {
+        for ingredient in &mut group.ingredients {
+            let value = ingredient.quantity.to_f64().unwrap_or(0.0);
+            if value <= 0.0 || converter.opposite_base(&ingredient.unit).is_none() {
+                continue;
+            }
+
+            let Some(density) = converter.density_for(&ingredient.name) else {
+                ingredient.density_status = Some(DensityStatus::Missing);
+                continue;
+            };
+            ingredient.density_status = Some(DensityStatus::Known);
+
+            let target_system = system
+                .map(|s| s.as_str().to_string())
+                .unwrap_or_else(|| ingredient.unit.system.clone());
+            if let Some((v, unit)) =
+                converter.equivalent(value, &ingredient.unit, density.grams_per_ml, &target_system)
+            {
+                let (quantity, display_quantity) = round_for_unit(v, &unit);
+                ingredient.equivalent = Some(IngredientEquivalentDto {
+                    quantity,
+                    display_quantity,
+                    unit,
+                    grams_per_ml: density.grams_per_ml,
+                });
+            }
         }
     }
 }
+
+/// Resolves a unit as written in a recipe ("tbsp", "c. à soupe", "Cups") to a row
+/// of `ingredient_units`.
+pub fn find_unit<'a>(text: &str, units: &'a [UnitDto]) -> Option<&'a UnitDto> {
+    let text = text.trim().to_lowercase();
+    if text.is_empty() {
+        return None;
+    }
+
+    let by_alias = UNIT_ALIASES
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&text.as_str()))
+        .and_then(|(code, _)| units.iter().find(|u| u.code == *code));
+
+    by_alias.or_else(|| {
+        let singular = text.strip_suffix('s').unwrap_or(&text);
+        units.iter().find(|u| {
+            [&u.code, &u.symbol, &u.name_en, &u.name_fr]
+                .iter()
+                .map(|field| field.to_lowercase())
+                .any(|field| field == text || field == singular)
+        })
+    })
+}
+
+/// Unit of an imported ingredient whose own unit was not recognised: "piece" when it
+/// has a quantity, "to taste" otherwise.
+pub fn fallback_unit(units: &[UnitDto], has_quantity: bool) -> Option<&UnitDto> {
+    let code = if has_quantity { "PIECE" } else { "TO_TASTE" };
+    units.iter().find(|u| u.code == code)
+}
+
+fn min_readable(unit: &UnitDto) -> f64 {
+    if unit.is_fraction_allowed { 0.25 } else { 1.0 }
+}
+
+/// A promoted value has to land on a quarter of a fractional or US unit, otherwise
+/// 17 tbsp would become 1 cup. Decimal units take it only when it shows without
+/// rounding, so 1234 g stays in grams rather than becoming 1.23 kg.
+fn lands_on_quarter(value: f64, unit: &UnitDto) -> bool {
+    if !unit.is_fraction_allowed && unit.system != UnitSystem::Imperial.as_str() {
+        return rounding_error(value, unit) < 1e-9;
+    }
+    let quarters = (value * 4.0).round() / 4.0;
+    (quarters - value).abs() <= PROMOTION_TOLERANCE
+}
+
+fn rounding_error(value: f64, unit: &UnitDto) -> f64 {
+    let (rounded, _) = round_for_unit(value, unit);
+    ((rounded.to_f64().unwrap_or(0.0) - value) / value).abs()
+}
+
+/// Returns the whole-plus-fraction value closest to `value` and its glyph.
+fn snap_to_fraction(value: f64) -> (f64, &'static str) {
+    let whole = value.trunc();
+    let remainder = value - whole;
+    let (fraction, glyph) = FRACTIONS
+        .iter()
+        .min_by(|a, b| (a.0 - remainder).abs().total_cmp(&(b.0 - remainder).abs()))
+        .copied()
+        .unwrap_or((0.0, ""));
+    (whole + fraction, glyph)
+}
+
+/// Rounds a quantity the way it should be printed for `unit`: ½ and ¼ style
+/// fractions for spoon, cup and count units, decimals otherwise.
+pub fn round_for_unit(value: f64, unit: &UnitDto) -> (Decimal, String) {
+    if unit.is_fraction_allowed && value > 0.125 && value < 10.0 {
+        let (snapped, glyph) = snap_to_fraction(value);
+        let whole = snapped.trunc() as i64;
+        let display = match (whole, glyph) {
+            (0, g) => g.to_string(),
+            (w, "") => w.to_string(),
+            (w, g) => format!("{} {}", w, g),
+        };
+        let quantity = Decimal::from_f64(snapped).unwrap_or(Decimal::ZERO).round_dp(3);
+        return (quantity, display);
+    }
+
+    let decimals = if value >= 100.0 {
+        0
+    } else if value >= 10.0 {
+        1
+    } else {
+        2
+    };
+    let quantity = Decimal::from_f64(value)
+        .unwrap_or(Decimal::ZERO)
+        .round_dp(decimals)
+        .normalize();
+    (quantity, quantity.to_string())
+}